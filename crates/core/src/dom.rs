@@ -28,22 +28,36 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     pub use timeout::{delay, request_timeout_callback, TimeoutCallbackHandle};
     pub use dispatch::Dispatch;
     use crate::dom::events::MountEvent;
+    pub use events::SyntheticEvent;
+    pub use simulate::FireEventError;
+    pub use test_program::TestProgram;
     pub use window::Window;
     pub use dom_node::DomNode;
     pub use document::Document;
     pub use time::Time;
+    pub use render_document::{document_view, render_document, DocumentHead};
+    #[cfg(feature = "with-serde")]
+    pub use state_transfer::{app_state_script, read_app_state, render_with_state, StateTransferError, APP_STATE_ELEMENT_ID};
 
     mod application;
     pub mod dispatch;
     mod dom_node;
     mod dom_patch;
     mod dom_attr;
+    mod hydrate;
+    mod render_document;
+    #[cfg(feature = "with-serde")]
+    mod state_transfer;
+    #[cfg(feature = "with-serde")]
+    pub mod live_view;
     pub mod events;
     mod http;
     mod program;
     pub mod util;
     mod raf;
     mod ric;
+    mod simulate;
+    mod test_program;
     mod window;
     mod document;
     mod time;
@@ -57,6 +71,9 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
         WebEvent(web_sys::Event),
         /// custom event here follows
         MountEvent(MountEvent),
+        /// an event which is not triggered by the browser,
+        /// such as the events forwarded by a live view client
+        Synthetic(SyntheticEvent),
     }
 
 }}
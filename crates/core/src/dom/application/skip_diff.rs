@@ -102,7 +102,7 @@ pub fn skip_if(shall: bool, children: impl IntoIterator<Item = SkipDiff>) -> Ski
 }
 
 /// combination of TreePath and SkipDiff
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SkipPath {
     pub(crate) path: TreePath,
     pub(crate) skip_diff: Option<SkipDiff>,
@@ -126,12 +126,4 @@ impl SkipPath {
             },
         }
     }
-
-    pub(crate) fn backtrack(&self) -> Self {
-        Self {
-            path: self.path.backtrack(),
-            //TODO: here the skip_diff can not back track as we lose that info already
-            skip_diff: None,
-        }
-    }
 }
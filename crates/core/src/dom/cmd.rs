@@ -1,12 +1,19 @@
 use crate::dom::Effects;
 use futures::channel::mpsc;
 use futures::channel::mpsc::UnboundedReceiver;
+#[cfg(feature = "with-dom")]
+use futures::future::LocalBoxFuture;
 use futures::StreamExt;
 use std::future::Future;
 use std::pin::Pin;
 #[cfg(feature = "with-dom")]
 use wasm_bindgen::closure::Closure;
 
+/// the next msg of a command which is not done yet, returned together with the command
+/// so it can be polled again for its next msg
+#[cfg(feature = "with-dom")]
+pub(crate) type PendingCommand<MSG> = LocalBoxFuture<'static, Option<(MSG, Command<MSG>)>>;
+
 /// Cmd is a way to tell the Runtime that something needs to be executed
 pub struct Cmd<MSG> {
     /// commands
@@ -142,6 +149,16 @@ where
             Self::Sub(task) => task.next().await,
         }
     }
+
+    /// return the next value together with this command,
+    /// so the command can be polled again for its next value
+    #[cfg(feature = "with-dom")]
+    pub(crate) fn into_next(mut self) -> PendingCommand<MSG> {
+        Box::pin(async move {
+            let msg = self.next().await?;
+            Some((msg, self))
+        })
+    }
 }
 
 /// Action is used to do asynchronous operations
@@ -156,6 +156,8 @@ pub struct StatelessModel<MSG> {
     pub view: Box<Node<MSG>>,
     /// component type id
     pub type_id: TypeId,
+    /// the static stylesheet of the component
+    pub stylesheet: fn() -> Vec<String>,
 }
 
 impl<MSG> StatelessModel<MSG> {
@@ -169,11 +171,12 @@ impl<MSG> StatelessModel<MSG> {
         StatelessModel {
             type_id: self.type_id,
             view: Box::new(self.view.map_msg(cb.clone())),
+            stylesheet: self.stylesheet,
         }
     }
 
     /// return the attribute values of the view node matching the attribute name `name`
-    pub fn attribute_value(&self, name: &AttributeName) -> Option<Vec<&AttributeValue<MSG>>> {
+    pub fn attribute_value(&self, name: &str) -> Option<Vec<&AttributeValue<MSG>>> {
         self.view.attribute_value(name)
     }
 
@@ -188,6 +191,7 @@ impl<MSG> Clone for StatelessModel<MSG> {
         Self {
             view: self.view.clone(),
             type_id: self.type_id,
+            stylesheet: self.stylesheet,
         }
     }
 }
@@ -208,6 +212,7 @@ where
     Node::Leaf(Leaf::StatelessComponent(StatelessModel {
         view: Box::new(app_view),
         type_id,
+        stylesheet: <COMP as Component>::stylesheet,
     }))
 }
 
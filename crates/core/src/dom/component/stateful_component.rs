@@ -1,9 +1,12 @@
 use crate::{
     dom::{
         events::on_component_mount, program::MountProcedure, Application, Cmd, Component, DomAttr,
-        DomAttrValue, DomNode, Program,
+        DomAttrValue, DomNode, Program, StatelessModel,
+    },
+    html::{attributes::class, div},
+    vdom::{
+        Attribute, AttributeName, AttributeValue, Element, Leaf, Node, TemplatedView, TreePath,
     },
-    vdom::{Attribute, AttributeName, Leaf, Node},
 };
 use std::{any::TypeId, cell::RefCell, fmt, rc::Rc};
 
@@ -20,6 +23,13 @@ pub trait StatefulComponent {
     /// return the DomNode which contains the children DomNode
     fn child_container(&self) -> Option<DomNode>;
 
+    /// return the path of the node in the view which contains the children.
+    /// This is used for placing the children when the component is rendered without a DOM,
+    /// the children are placed after the view if there is none.
+    fn child_container_path(&self) -> Option<TreePath> {
+        None
+    }
+
     /// append a child into this component
     fn append_children(&mut self, _children: Vec<DomNode>) {}
 
@@ -41,6 +51,10 @@ pub struct StatefulModel<MSG> {
     pub comp: Rc<RefCell<dyn StatefulComponent>>,
     /// component type id
     pub type_id: TypeId,
+    /// the static stylesheet of the component
+    pub stylesheet: fn() -> Vec<String>,
+    /// the view of the component, without the event listeners
+    comp_view: Rc<dyn Fn() -> Node<MSG>>,
     /// component attributes
     pub attrs: Vec<Attribute<MSG>>,
     /// external children component
@@ -61,9 +75,13 @@ impl<MSG> StatefulModel<MSG> {
         MSG2: 'static,
         MSG: 'static,
     {
+        let comp_view = self.comp_view;
+        let view_cb = cb.clone();
         StatefulModel {
             type_id: self.type_id,
             comp: self.comp,
+            stylesheet: self.stylesheet,
+            comp_view: Rc::new(move || comp_view().map_msg(view_cb.clone())),
             attrs: self
                 .attrs
                 .into_iter()
@@ -76,6 +94,37 @@ impl<MSG> StatefulModel<MSG> {
                 .collect(),
         }
     }
+
+    /// return the view of the component with the children placed in its child container,
+    /// wrapped in the element which the component is mounted to in the DOM.
+    /// This doesn't need a DOM, so it can be used for rendering the component in the server.
+    /// The event listeners in the view of the component are not included.
+    pub fn view(&self) -> Node<MSG> {
+        let mut view = (self.comp_view)();
+        let children = self.children.clone();
+        let container_path = self.comp.borrow().child_container_path();
+        let container = container_path
+            .as_ref()
+            .and_then(|path| find_node_mut(&mut view, path));
+        let comp_children = match container {
+            Some(container) => {
+                container
+                    .add_children(children)
+                    .expect("child container must be an element");
+                vec![view]
+            }
+            None => {
+                if let Some(path) = container_path {
+                    log::warn!("child container is not found in the view at {path:?}");
+                }
+                [view].into_iter().chain(children).collect()
+            }
+        };
+        div(
+            [class("component")].into_iter().chain(self.attrs.clone()),
+            comp_children,
+        )
+    }
 }
 
 impl<MSG> Clone for StatefulModel<MSG> {
@@ -83,6 +132,8 @@ impl<MSG> Clone for StatefulModel<MSG> {
         Self {
             comp: Rc::clone(&self.comp),
             type_id: self.type_id,
+            stylesheet: self.stylesheet,
+            comp_view: Rc::clone(&self.comp_view),
             attrs: self.attrs.clone(),
             children: self.children.clone(),
         }
@@ -128,7 +179,7 @@ where
 
 /// create a stateful component node
 pub fn stateful_component<COMP, MSG, MSG2>(
-    app: COMP,
+    mut app: COMP,
     attrs: impl IntoIterator<Item = Attribute<MSG>>,
     children: impl IntoIterator<Item = Node<MSG>>,
 ) -> Node<MSG>
@@ -140,8 +191,16 @@ where
     let type_id = TypeId::of::<COMP>();
     let attrs = attrs.into_iter().collect::<Vec<_>>();
 
+    // the attributes are passed to `attribute_changed` when the component is created,
+    // the same way as when it is mounted, so its view can be rendered without a DOM
+    for attr in attrs.iter().filter(|attr| !attr.is_event_listener()) {
+        app.attribute_changed(plain_dom_attr(attr));
+    }
     let app = Rc::new(RefCell::new(app));
 
+    let view_app = Rc::clone(&app);
+    let comp_view = move || without_event_listeners(<COMP as Component>::view(&view_app.borrow()));
+
     let mut program = Program::from_rc_app(Rc::clone(&app));
     let children: Vec<Node<MSG>> = children.into_iter().collect();
     let mount_event = on_component_mount(move |me| {
@@ -159,11 +218,136 @@ where
     Node::Leaf(Leaf::StatefulComponent(StatefulModel {
         comp: app,
         type_id,
+        stylesheet: <COMP as Component>::stylesheet,
+        comp_view: Rc::new(comp_view),
         attrs: attrs.into_iter().chain([mount_event]).collect(),
         children: children.into_iter().collect(),
     }))
 }
 
+/// convert the attribute into a `DomAttr` without a `Program`,
+/// the event listeners are skipped since they can only be called in the DOM
+fn plain_dom_attr<MSG>(attr: &Attribute<MSG>) -> DomAttr {
+    DomAttr {
+        namespace: attr.namespace.clone(),
+        name: attr.name.clone(),
+        value: attr
+            .value
+            .iter()
+            .filter_map(|value| match value {
+                AttributeValue::Simple(v) => Some(DomAttrValue::Simple(v.clone())),
+                AttributeValue::Style(v) => Some(DomAttrValue::Style(v.clone())),
+                AttributeValue::EventListener(_)
+                | AttributeValue::ComponentEventListener(_)
+                | AttributeValue::Empty => None,
+            })
+            .collect(),
+    }
+}
+
+/// find the node at this path
+fn find_node_mut<'a, MSG>(node: &'a mut Node<MSG>, path: &TreePath) -> Option<&'a mut Node<MSG>> {
+    path.path
+        .iter()
+        .try_fold(node, |node, idx| node.children_mut()?.get_mut(*idx))
+}
+
+/// convert the node into a node of another msg type by removing its event listeners,
+/// which are the only part of the node that produces a msg
+fn without_event_listeners<MSG, MSG2>(node: Node<MSG>) -> Node<MSG2>
+where
+    MSG: 'static,
+    MSG2: 'static,
+{
+    match node {
+        Node::Element(elm) => Node::Element(Element {
+            namespace: elm.namespace,
+            tag: elm.tag,
+            attrs: elm
+                .attrs
+                .into_iter()
+                .filter_map(attr_without_event_listeners)
+                .collect(),
+            children: elm
+                .children
+                .into_iter()
+                .map(without_event_listeners)
+                .collect(),
+            self_closing: elm.self_closing,
+        }),
+        Node::Leaf(leaf) => match leaf {
+            Leaf::Text(v) => Node::Leaf(Leaf::Text(v)),
+            Leaf::Symbol(v) => Node::Leaf(Leaf::Symbol(v)),
+            Leaf::TrustedHtml(v) => Node::Leaf(Leaf::TrustedHtml(v)),
+            Leaf::Comment(v) => Node::Leaf(Leaf::Comment(v)),
+            Leaf::DocType(v) => Node::Leaf(Leaf::DocType(v)),
+            Leaf::NodeList(nodes) => Node::Leaf(Leaf::NodeList(
+                nodes.into_iter().map(without_event_listeners).collect(),
+            )),
+            Leaf::Fragment(nodes) => Node::Leaf(Leaf::Fragment(
+                nodes.into_iter().map(without_event_listeners).collect(),
+            )),
+            Leaf::StatefulComponent(comp) => {
+                let comp_view = comp.comp_view;
+                Node::Leaf(Leaf::StatefulComponent(StatefulModel {
+                    comp: comp.comp,
+                    type_id: comp.type_id,
+                    stylesheet: comp.stylesheet,
+                    comp_view: Rc::new(move || without_event_listeners(comp_view())),
+                    attrs: comp
+                        .attrs
+                        .into_iter()
+                        .filter_map(attr_without_event_listeners)
+                        .collect(),
+                    children: comp
+                        .children
+                        .into_iter()
+                        .map(without_event_listeners)
+                        .collect(),
+                }))
+            }
+            Leaf::StatelessComponent(comp) => {
+                Node::Leaf(Leaf::StatelessComponent(StatelessModel {
+                    view: Box::new(without_event_listeners(*comp.view)),
+                    type_id: comp.type_id,
+                    stylesheet: comp.stylesheet,
+                }))
+            }
+            Leaf::TemplatedView(view) => Node::Leaf(Leaf::TemplatedView(TemplatedView {
+                view: Box::new(without_event_listeners(*view.view)),
+                skip_diff: view.skip_diff,
+            })),
+            // the view of the lazy view is built right away, since its view function
+            // would create the event listeners again
+            Leaf::LazyView(view) => without_event_listeners(view.view().clone()),
+        },
+    }
+}
+
+/// the attribute without its event listener values,
+/// `None` if the attribute is an event listener
+fn attr_without_event_listeners<MSG, MSG2>(attr: Attribute<MSG>) -> Option<Attribute<MSG2>> {
+    if attr.is_event_listener() {
+        return None;
+    }
+    Some(Attribute {
+        namespace: attr.namespace,
+        name: attr.name,
+        value: attr
+            .value
+            .into_iter()
+            .filter_map(|value| match value {
+                AttributeValue::Simple(v) => Some(AttributeValue::Simple(v)),
+                AttributeValue::Style(v) => Some(AttributeValue::Style(v)),
+                AttributeValue::Empty => Some(AttributeValue::Empty),
+                AttributeValue::EventListener(_) | AttributeValue::ComponentEventListener(_) => {
+                    None
+                }
+            })
+            .collect(),
+    })
+}
+
 #[cfg(feature = "with-dom")]
 impl From<wasm_bindgen::JsValue> for DomAttrValue {
     fn from(val: wasm_bindgen::JsValue) -> Self {
@@ -22,9 +22,9 @@ use web_sys::{
 #[derive(Debug)]
 pub struct DomAttr {
     /// namespace of the attribute
-    pub namespace: Option<&'static str>,
+    pub namespace: Option<Namespace>,
     /// the name of the attribute
-    pub name: &'static str,
+    pub name: AttributeName,
     /// the value of the attribute
     pub value: Vec<DomAttrValue>,
 }
@@ -81,11 +81,7 @@ impl DomAttr {
     }
 
     /// set the style of this element
-    pub(crate) fn set_element_style(
-        element: &Element,
-        attr_name: AttributeName,
-        styles: Vec<Style>,
-    ) {
+    pub(crate) fn set_element_style(element: &Element, attr_name: &str, styles: Vec<Style>) {
         if let Some(merged_styles) = Style::merge_to_string(&styles) {
             // set the styles
             element
@@ -100,11 +96,48 @@ impl DomAttr {
         }
     }
 
+    /// set and remove only these style properties of the element,
+    /// the rest of the style properties are left as is
+    pub(crate) fn update_element_styles(
+        element: &Element,
+        set: &[Style],
+        remove: &[Style],
+    ) -> Result<(), JsValue> {
+        use wasm_bindgen::JsCast;
+        // html, svg and mathml elements all have the style property
+        let style: web_sys::CssStyleDeclaration =
+            js_sys::Reflect::get(element, &intern("style").into())?.unchecked_into();
+        for s in remove {
+            style.remove_property(&s.name)?;
+        }
+        for s in set {
+            style.set_property(&s.name, &s.value.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// add and remove only these class names of the element,
+    /// the class names which are added by other means are left as is
+    pub(crate) fn update_element_classes(
+        element: &Element,
+        add: &[String],
+        remove: &[String],
+    ) -> Result<(), JsValue> {
+        let class_list = element.class_list();
+        for name in remove {
+            class_list.remove_1(name)?;
+        }
+        for name in add {
+            class_list.add_1(name)?;
+        }
+        Ok(())
+    }
+
     /// set simple values
     pub(crate) fn set_element_simple_values(
         element: &Element,
-        attr_name: AttributeName,
-        attr_namespace: Option<Namespace>,
+        attr_name: &str,
+        attr_namespace: Option<&str>,
         plain_values: Vec<Value>,
     ) {
         if let Some(merged_plain_values) = Value::merge_to_string(plain_values.iter()) {
@@ -185,7 +218,7 @@ impl DomAttr {
             DomAttr::set_disabled(element, false);
         }
         //actually remove the element
-        element.remove_attribute(intern(attr.name))?;
+        element.remove_attribute(intern(&attr.name))?;
 
         Ok(())
     }
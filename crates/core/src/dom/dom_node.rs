@@ -4,13 +4,13 @@ use crate::dom::GroupedDomAttrValues;
 use crate::dom::StatefulComponent;
 use crate::dom::StatefulModel;
 use crate::html::lookup;
-use crate::vdom::TreePath;
+use crate::vdom::{AttributeName, TreePath};
 use crate::{
     dom::document,
     dom::events::MountEvent,
     dom::{Application, Program},
     vdom,
-    vdom::{Attribute, Leaf},
+    vdom::{Attribute, Leaf, Style},
 };
 use indexmap::IndexMap;
 use std::borrow::Cow;
@@ -22,7 +22,7 @@ use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use web_sys::{self, Node};
 
 pub(crate) type EventClosure = Closure<dyn FnMut(web_sys::Event)>;
-pub type NamedEventClosures = IndexMap<&'static str, EventClosure>;
+pub type NamedEventClosures = IndexMap<AttributeName, EventClosure>;
 
 /// A counter part of the vdom Node
 /// This is needed, so that we can
@@ -59,6 +59,9 @@ pub enum DomInner {
         fragment: web_sys::DocumentFragment,
         ///
         children: Rc<RefCell<Vec<DomNode>>>,
+        /// marks the location of the fragment in the DOM while it has no children,
+        /// since the children of a mounted fragment are moved into the parent of the fragment
+        marker: web_sys::Comment,
     },
     /// StatefulComponent
     StatefulComponent {
@@ -138,6 +141,7 @@ impl From<web_sys::Node> for DomNode {
                     inner: DomInner::Fragment {
                         fragment,
                         children: Rc::new(RefCell::new(vec![])),
+                        marker: document().create_comment(""),
                     },
                 }
             }
@@ -273,15 +277,31 @@ impl DomNode {
                 }
             }
             DomInner::Fragment {
-                fragment, children, ..
+                fragment,
+                children,
+                marker,
             } => {
+                // the children of a mounted fragment are moved into the parent of the fragment,
+                // so the new children are inserted after the last child of the fragment,
+                // or in place of the marker when the fragment has no children
+                let last_child = children.borrow().last().map(|last| last.last_dom_node());
+                let (parent, next_sibling) = match last_child {
+                    Some(last) => (last.parent_node(), last.next_sibling()),
+                    None => (marker.parent_node(), Some(marker.clone().unchecked_into())),
+                };
+                let parent = parent.unwrap_or_else(|| fragment.clone().unchecked_into());
+                let next_sibling =
+                    next_sibling.filter(|next| parent.is_same_node(next.parent_node().as_ref()));
                 for child in for_append.into_iter() {
-                    fragment
-                        .append_child(&child.as_node())
-                        .expect("append child");
+                    parent
+                        .insert_before(&child.as_node(), next_sibling.as_ref())
+                        .expect("insert child");
                     child.dispatch_mount_event();
                     children.borrow_mut().push(child);
                 }
+                if !children.borrow().is_empty() {
+                    Self::remove_from_parent(marker);
+                }
             }
             _ => unreachable!(
                 "appending should only be called to Element and Fragment, found: {:#?}",
@@ -290,11 +310,78 @@ impl DomNode {
         }
     }
 
+    /// the first actual DOM node of this node,
+    /// the nodes of a fragment are located by its first child or its marker
+    fn first_dom_node(&self) -> web_sys::Node {
+        match &self.inner {
+            DomInner::Fragment {
+                children, marker, ..
+            } => children
+                .borrow()
+                .first()
+                .map(|first| first.first_dom_node())
+                .unwrap_or_else(|| marker.clone().unchecked_into()),
+            _ => self.as_node(),
+        }
+    }
+
+    /// the last actual DOM node of this node,
+    /// the nodes of a fragment are located by its last child or its marker
+    fn last_dom_node(&self) -> web_sys::Node {
+        match &self.inner {
+            DomInner::Fragment {
+                children, marker, ..
+            } => children
+                .borrow()
+                .last()
+                .map(|last| last.last_dom_node())
+                .unwrap_or_else(|| marker.clone().unchecked_into()),
+            _ => self.as_node(),
+        }
+    }
+
+    /// put the marker of the fragment in place of its children, before all of them are removed,
+    /// so the fragment can still be located when new children are appended into it
+    fn put_fragment_marker(&self) {
+        let DomInner::Fragment { marker, .. } = &self.inner else {
+            return;
+        };
+        let first = self.first_dom_node();
+        if let Some(parent) = first.parent_node() {
+            parent
+                .insert_before(marker, Some(&first))
+                .expect("insert marker");
+        }
+    }
+
+    /// remove the actual DOM nodes of this node from their parent,
+    /// which are the children and the marker of a fragment
+    fn remove_dom_nodes(&self) {
+        match &self.inner {
+            DomInner::Fragment {
+                children, marker, ..
+            } => {
+                for child in children.borrow().iter() {
+                    child.remove_dom_nodes();
+                }
+                Self::remove_from_parent(marker);
+            }
+            _ => Self::remove_from_parent(&self.as_node()),
+        }
+    }
+
+    /// the children of an element or a fragment,
+    /// which needs to be synced with the actual children
+    fn children_list(&self) -> &Rc<RefCell<Vec<DomNode>>> {
+        match &self.inner {
+            DomInner::Element { children, .. } | DomInner::Fragment { children, .. } => children,
+            _ => unreachable!("parent must be an element or a fragment"),
+        }
+    }
+
     /// Insert the DomNode `for_insert` before `self` DomNode
     pub(crate) fn insert_before(&self, target_element: &DomNode, for_insert: Vec<DomNode>) {
-        let DomInner::Element { children, .. } = &self.inner else {
-            unreachable!("parent must be an element");
-        };
+        let children = self.children_list();
 
         let mut target_index = None;
         for (i, child) in children.borrow().iter().enumerate() {
@@ -326,9 +413,7 @@ impl DomNode {
 
     /// Insert the DomNode `for_insert` after `self` DomNode
     pub(crate) fn insert_after(&self, target_element: &DomNode, for_insert: Vec<DomNode>) {
-        let DomInner::Element { children, .. } = &self.inner else {
-            unreachable!("parent must be an element");
-        };
+        let children = self.children_list();
         let mut target_index = None;
         for (i, child) in children.borrow().iter().enumerate() {
             if target_element == child {
@@ -354,7 +439,7 @@ impl DomNode {
     /// Replace the child `child` DomNode with a replacement DomNode `replacement`
     pub(crate) fn replace_child(&self, target_child: &DomNode, replacement: DomNode) {
         match &self.inner {
-            DomInner::Element { children, .. } => {
+            DomInner::Element { children, .. } | DomInner::Fragment { children, .. } => {
                 let mut child_index = None;
                 for (i, ch) in children.borrow().iter().enumerate() {
                     if ch == target_child {
@@ -386,9 +471,7 @@ impl DomNode {
     /// Remove the DomNode `child` from the children of `self`
     pub(crate) fn remove_children(&self, for_remove: &[&DomNode]) {
         match &self.inner {
-            DomInner::Element {
-                element, children, ..
-            } => {
+            DomInner::Element { children, .. } => {
                 let mut child_indexes = vec![];
                 for (i, ch) in children.borrow().iter().enumerate() {
                     for remove_node in for_remove.iter() {
@@ -405,15 +488,38 @@ impl DomNode {
                 // with the rev child index, we remove the correct child_index
                 for child_index in child_indexes.into_iter().rev() {
                     let child = children.borrow_mut().remove(child_index);
-                    element
-                        .remove_child(&child.as_node())
-                        .expect("remove child");
+                    child.remove_dom_nodes();
+                }
+            }
+            DomInner::Fragment { children, .. } => {
+                let mut child_indexes = vec![];
+                for (i, ch) in children.borrow().iter().enumerate() {
+                    if for_remove.contains(&ch) {
+                        child_indexes.push(i);
+                    }
+                }
+                assert_eq!(child_indexes.len(), for_remove.len(), "must find all");
+
+                if child_indexes.len() == children.borrow().len() {
+                    self.put_fragment_marker();
+                }
+                for child_index in child_indexes.into_iter().rev() {
+                    let child = children.borrow_mut().remove(child_index);
+                    child.remove_dom_nodes();
                 }
             }
             _ => todo!(),
         }
     }
 
+    /// remove the node from its actual parent,
+    /// which is the parent of the fragment when the fragment is already mounted
+    fn remove_from_parent(node: &web_sys::Node) {
+        if let Some(parent) = node.parent_node() {
+            parent.remove_child(node).expect("remove child");
+        }
+    }
+
     /// remove all the children of this element
     pub(crate) fn clear_children(&self) {
         match &self.inner {
@@ -430,6 +536,14 @@ impl DomNode {
                         .expect("must remove child");
                 }
             }
+            DomInner::Fragment { children, .. } => {
+                if !children.borrow().is_empty() {
+                    self.put_fragment_marker();
+                }
+                for child in children.borrow_mut().drain(..) {
+                    child.remove_dom_nodes();
+                }
+            }
             _ => todo!(),
         }
     }
@@ -455,8 +569,8 @@ impl DomNode {
             DomInner::Element {
                 element, listeners, ..
             } => {
-                let attr_name = intern(attr.name);
-                let attr_namespace = attr.namespace;
+                let attr_name = attr.name.clone();
+                let attr_namespace = attr.namespace.clone();
 
                 let GroupedDomAttrValues {
                     listeners: event_callbacks,
@@ -464,26 +578,28 @@ impl DomNode {
                     styles,
                 } = attr.group_values();
 
-                Self::add_event_dom_listeners(element, attr_name, &event_callbacks)
+                Self::add_event_dom_listeners(element, &attr_name, &event_callbacks)
                     .expect("event listeners");
                 let is_none = listeners.borrow().is_none();
                 if is_none {
-                    let listener_closures: IndexMap<
-                        &'static str,
-                        Closure<dyn FnMut(web_sys::Event)>,
-                    > = IndexMap::from_iter(event_callbacks.into_iter().map(|c| (attr_name, c)));
+                    let listener_closures: NamedEventClosures = IndexMap::from_iter(
+                        event_callbacks
+                            .into_iter()
+                            .map(|c| (attr_name.clone(), c)),
+                    );
                     *listeners.borrow_mut() = Some(listener_closures);
                 } else if let Some(listeners) = listeners.borrow_mut().as_mut() {
                     for event_cb in event_callbacks.into_iter() {
-                        listeners.insert(attr_name, event_cb);
+                        listeners.insert(attr_name.clone(), event_cb);
                     }
                 }
 
+                let attr_name = intern(&attr_name);
                 DomAttr::set_element_style(element, attr_name, styles);
                 DomAttr::set_element_simple_values(
                     element,
                     attr_name,
-                    attr_namespace,
+                    attr_namespace.as_deref(),
                     plain_values,
                 );
             }
@@ -506,10 +622,24 @@ impl DomNode {
         DomAttr::remove_element_dom_attr(element, attr)
     }
 
+    pub(crate) fn update_styles(&self, set: &[Style], remove: &[Style]) -> Result<(), JsValue> {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("expecting an element");
+        };
+        DomAttr::update_element_styles(element, set, remove)
+    }
+
+    pub(crate) fn update_classes(&self, add: &[String], remove: &[String]) -> Result<(), JsValue> {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("expecting an element");
+        };
+        DomAttr::update_element_classes(element, add, remove)
+    }
+
     /// attach and event listener to an event target
     pub(crate) fn add_event_dom_listeners(
         target: &web_sys::EventTarget,
-        attr_name: &'static str,
+        attr_name: &str,
         event_listeners: &[EventClosure],
     ) -> Result<(), JsValue> {
         for event_cb in event_listeners.iter() {
@@ -543,7 +673,7 @@ impl DomNode {
         }
     }
 
-    fn dispatch_mount_event(&self) {
+    pub(crate) fn dispatch_mount_event(&self) {
         if self.should_dispatch_mount_event() {
             let event_target: web_sys::EventTarget = self.as_element().unchecked_into();
             event_target
@@ -696,6 +826,10 @@ where
             Leaf::Symbol(symbol) => DomNode {
                 inner: DomInner::Symbol(symbol.clone()),
             },
+            // trusted html is inserted the same way as symbols, since both are html markup
+            Leaf::TrustedHtml(html) => DomNode {
+                inner: DomInner::Symbol(html.clone()),
+            },
             Leaf::Comment(comment) => DomNode {
                 inner: DomInner::Comment(document().create_comment(comment)),
             },
@@ -719,6 +853,7 @@ where
             Leaf::TemplatedView(view) => {
                 unreachable!("template view should not be created: {:#?}", view)
             }
+            Leaf::LazyView(view) => self.create_dom_node(view.view()),
             Leaf::DocType(_) => unreachable!("doc type is never converted"),
         }
     }
@@ -728,10 +863,13 @@ where
         nodes: impl IntoIterator<Item = &'a vdom::Node<APP::MSG>>,
     ) -> DomNode {
         let fragment = document().create_document_fragment();
+        let marker = document().create_comment("");
+        fragment.append_child(&marker).expect("append marker");
         let dom_node = DomNode {
             inner: DomInner::Fragment {
                 fragment,
                 children: Rc::new(RefCell::new(vec![])),
+                marker,
             },
         };
         let children = nodes
@@ -7,6 +7,7 @@ use crate::dom::DomNode;
 use crate::dom::{Application, Program};
 use crate::vdom::ComponentEventCallback;
 use crate::vdom::EventCallback;
+use crate::vdom::Style;
 use crate::vdom::TreePath;
 use crate::vdom::{Attribute, AttributeValue, Patch, PatchType};
 use indexmap::IndexMap;
@@ -57,6 +58,20 @@ pub enum PatchVariant {
         /// the attributes names to be removed
         attrs: Vec<DomAttr>,
     },
+    /// Set and remove the style properties of the target node
+    UpdateStyles {
+        /// the style properties to be set
+        set: Vec<Style>,
+        /// the style properties to be removed
+        remove: Vec<Style>,
+    },
+    /// Add and remove the class names of the target node
+    UpdateClasses {
+        /// the class names to be added
+        add: Vec<String>,
+        /// the class names to be removed
+        remove: Vec<String>,
+    },
     /// Replace the target node with the replacement node
     ReplaceNode {
         /// the replacement node
@@ -113,7 +128,7 @@ impl DomNode {
 
     pub(crate) fn find_all_nodes(
         &self,
-        nodes_to_find: &[(&TreePath, Option<&&'static str>)],
+        nodes_to_find: &[(&TreePath, Option<&str>)],
     ) -> IndexMap<TreePath, (DomNode, DomNode)> {
         let mut nodes_to_patch = IndexMap::with_capacity(nodes_to_find.len());
         for (path, tag) in nodes_to_find {
@@ -148,8 +163,8 @@ where
 {
     pub(crate) fn convert_attr(&self, attr: &Attribute<APP::MSG>) -> DomAttr {
         DomAttr {
-            namespace: attr.namespace,
-            name: attr.name,
+            namespace: attr.namespace.clone(),
+            name: attr.name.clone(),
             value: attr
                 .value
                 .iter()
@@ -204,7 +219,7 @@ where
         target_node: &DomNode,
         patches: &[Patch<APP::MSG>],
     ) -> Result<Vec<DomPatch>, JsValue> {
-        let nodes_to_find: Vec<(&TreePath, Option<&&'static str>)> = patches
+        let nodes_to_find: Vec<(&TreePath, Option<&str>)> = patches
             .iter()
             .map(|patch| (patch.path(), patch.tag()))
             .chain(
@@ -223,7 +238,7 @@ where
             if let Some((target_node, target_parent)) = nodes_lookup.get(patch_path) {
                 let target_tag = target_node.tag();
                 if let (Some(patch_tag), Some(target_tag)) = (patch_tag, target_tag) {
-                    if **patch_tag != target_tag{
+                    if *patch_tag != target_tag{
                         panic!(
                             "expecting a tag: {patch_tag:?}, but found: {target_tag:?}"
                         );
@@ -301,6 +316,24 @@ where
                     attrs: attrs.iter().map(|a| self.convert_attr(a)).collect(),
                 },
             },
+            PatchType::UpdateStyles { set, remove } => DomPatch {
+                patch_path,
+                target_element,
+                target_parent,
+                patch_variant: PatchVariant::UpdateStyles {
+                    set: set.iter().map(|s| (*s).clone()).collect(),
+                    remove: remove.iter().map(|s| (*s).clone()).collect(),
+                },
+            },
+            PatchType::UpdateClasses { add, remove } => DomPatch {
+                patch_path,
+                target_element,
+                target_parent,
+                patch_variant: PatchVariant::UpdateClasses {
+                    add: add.clone(),
+                    remove: remove.clone(),
+                },
+            },
 
             PatchType::ReplaceNode { replacement } => {
                 let replacement = replacement
@@ -435,6 +468,12 @@ where
                     }
                 }
             }
+            PatchVariant::UpdateStyles { set, remove } => {
+                target_element.update_styles(&set, &remove)?;
+            }
+            PatchVariant::UpdateClasses { add, remove } => {
+                target_element.update_classes(&add, &remove)?;
+            }
 
             // This also removes the associated closures and event listeners to the node being replaced
             // including the associated closures of the descendant of replaced node
@@ -7,6 +7,7 @@ use crate::vdom;
 use crate::vdom::ComponentEventCallback;
 use crate::vdom::{Attribute, AttributeValue, EventCallback};
 use wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
 pub use web_sys::ClipboardEvent;
 pub use web_sys::{
     AnimationEvent, FocusEvent, HashChangeEvent, KeyboardEvent, MouseEvent, Selection, TouchEvent,
@@ -57,6 +58,36 @@ impl Event {
             _ => None,
         }
     }
+
+    /// the key of the keyboard event, ie: `Enter`, `a`.
+    /// Unlike `KeyboardEvent::key`, this also works with a [`SyntheticEvent`]
+    pub fn key(&self) -> Option<String> {
+        match self {
+            Event::WebEvent(web_event) => web_event
+                .dyn_ref::<KeyboardEvent>()
+                .map(|keyboard_event| keyboard_event.key()),
+            Event::Synthetic(synthetic) => synthetic.key.clone(),
+            Event::MountEvent(_) => None,
+        }
+    }
+
+    /// the `(client_x, client_y)` position of the mouse event relative to the viewport.
+    /// Unlike the methods of `MouseEvent`, this also works with a [`SyntheticEvent`]
+    pub fn client_position(&self) -> Option<(i32, i32)> {
+        match self {
+            Event::WebEvent(web_event) => web_event
+                .dyn_ref::<MouseEvent>()
+                .map(|mouse_event| (mouse_event.client_x(), mouse_event.client_y())),
+            Event::Synthetic(synthetic) => synthetic.client_position,
+            Event::MountEvent(_) => None,
+        }
+    }
+}
+
+impl From<SyntheticEvent> for Event {
+    fn from(synthetic_event: SyntheticEvent) -> Self {
+        Event::Synthetic(synthetic_event)
+    }
 }
 
 impl From<MountEvent> for Event {
@@ -81,7 +112,7 @@ impl From<web_sys::MouseEvent> for Event {
 }
 
 /// an event builder
-pub fn on<F, MSG>(event_name: &'static str, f: F) -> Attribute<MSG>
+pub fn on<F, MSG>(event_name: impl Into<vdom::AttributeName>, f: F) -> Attribute<MSG>
 where
     F: FnMut(Event) -> MSG + 'static,
     MSG: 'static,
@@ -138,6 +169,94 @@ impl MountEvent {
     }
 }
 
+/// An event which is not triggered by the browser, but created from plain data.
+///
+/// This is used to call the event listeners of a view which is not mounted in the browser,
+/// such as the view of a live view app running on the server.
+/// The event listeners receive the input value and checked state from this event,
+/// while the typed web events, ie: `MouseEvent`, `KeyboardEvent` are empty placeholders
+/// which methods can not be called.
+/// Use [`Event::key`] and [`Event::client_position`] in an [`on`] listener
+/// to read the key and the mouse position of this event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(default))]
+pub struct SyntheticEvent {
+    /// the event type, ie: click, input
+    pub event_type: String,
+    /// the value of the input, textarea or select element where the event is triggered
+    pub value: Option<String>,
+    /// the checked state of the input checkbox, or the open state of the details element
+    pub checked: Option<bool>,
+    /// the key of the keyboard event
+    pub key: Option<String>,
+    /// the `(client_x, client_y)` position of the mouse event
+    pub client_position: Option<(i32, i32)>,
+}
+
+impl SyntheticEvent {
+    /// create a synthetic event of this event type
+    pub fn new(event_type: impl ToString) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// set the value of this event
+    pub fn with_value(mut self, value: impl ToString) -> Self {
+        self.value = Some(value.to_string());
+        self
+    }
+
+    /// set the checked state of this event
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// set the key of this event
+    pub fn with_key(mut self, key: impl ToString) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    /// set the `(client_x, client_y)` position of this event
+    pub fn with_client_position(mut self, client_x: i32, client_y: i32) -> Self {
+        self.client_position = Some((client_x, client_y));
+        self
+    }
+
+    /// create a synthetic event from the web event, taking the value and checked state of its
+    /// target element, and the key or mouse position of the keyboard and mouse events
+    pub fn from_web_event(web_event: &web_sys::Event) -> Self {
+        let mut event = Self::new(web_event.type_());
+        if let Some(keyboard_event) = web_event.dyn_ref::<KeyboardEvent>() {
+            event.key = Some(keyboard_event.key());
+        } else if let Some(mouse_event) = web_event.dyn_ref::<MouseEvent>() {
+            event.client_position = Some((mouse_event.client_x(), mouse_event.client_y()));
+        }
+        if let Some(target) = web_event.target() {
+            if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+                event.value = Some(input.value());
+                event.checked = Some(input.checked());
+            } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+                event.value = Some(textarea.value());
+            } else if let Some(select) = target.dyn_ref::<HtmlSelectElement>() {
+                event.value = Some(select.value());
+            } else if let Some(details) = target.dyn_ref::<HtmlDetailsElement>() {
+                event.checked = Some(details.open());
+            }
+        }
+        event
+    }
+
+    /// an empty placeholder passed as the typed web event to the event listeners
+    fn placeholder<T: JsCast>() -> T {
+        JsValue::UNDEFINED.unchecked_into()
+    }
+}
+
 /// custom mount event
 pub fn on_mount<F, MSG>(mut f: F) -> Attribute<MSG>
 where
@@ -219,55 +338,53 @@ macro_rules! declare_html_events{
     }
 }
 
+/// cast the web event into the typed event,
+/// synthetic events are given a placeholder
+fn cast_web_event<T: JsCast>(event: Event, error: &str) -> T {
+    match event {
+        Event::Synthetic(_) => SyntheticEvent::placeholder(),
+        _ => {
+            let web_event = event.as_web().expect("must be a web_sys event");
+            web_event.dyn_into().expect(error)
+        }
+    }
+}
+
 /// convert a generic event to MouseEvent
 fn to_mouse_event(event: Event) -> MouseEvent {
-    let web_event = event.as_web().expect("must be a web_sys event");
-    web_event.dyn_into().expect("Unable to cast to mouse event")
+    cast_web_event(event, "Unable to cast to mouse event")
 }
 
 fn to_focus_event(event: Event) -> FocusEvent {
-    let web_event = event.as_web().expect("must be a web_sys event");
-    web_event.dyn_into().expect("Unable to cast to focus event")
+    cast_web_event(event, "Unable to cast to focus event")
 }
 
 fn to_keyboard_event(event: Event) -> KeyboardEvent {
-    let web_event = event.as_web().expect("must be a web_sys event");
-    web_event
-        .dyn_into()
-        .expect("unable to cast to keyboard event")
+    cast_web_event(event, "unable to cast to keyboard event")
 }
 
 fn to_animation_event(event: Event) -> AnimationEvent {
-    let web_event = event.as_web().expect("must be a web_sys event");
-    web_event
-        .dyn_into()
-        .expect("unable to cast to animation event")
+    cast_web_event(event, "unable to cast to animation event")
 }
 
 fn to_transition_event(event: Event) -> TransitionEvent {
-    let web_event = event.as_web().expect("must be a web_sys event");
-    web_event
-        .dyn_into()
-        .expect("unable to cast to transition event")
+    cast_web_event(event, "unable to cast to transition event")
 }
 
 fn to_touch_event(event: Event) -> TouchEvent {
-    let web_event = event.as_web().expect("must be web sys event");
-    web_event.dyn_into().expect("unable to cast to touch event")
+    cast_web_event(event, "unable to cast to touch event")
 }
 
 fn to_webevent(event: Event) -> web_sys::Event {
     match event {
         Event::WebEvent(event) => event,
+        Event::Synthetic(_) => SyntheticEvent::placeholder(),
         _ => panic!("not a web_event"),
     }
 }
 
 fn to_hashchange_event(event: Event) -> HashChangeEvent {
-    let web_event = event.as_web().expect("must be a web_sys event");
-    web_event
-        .dyn_into()
-        .expect("unable to cast to hashchange event")
+    cast_web_event(event, "unable to cast to hashchange event")
 }
 
 /// TODO: expand this much farther by getting the InputEvent data, data_transfer, event_type,
@@ -277,11 +394,16 @@ fn to_hashchange_event(event: Event) -> HashChangeEvent {
 pub struct InputEvent {
     /// the actual dom event
     pub event: web_sys::Event,
+    /// the value of a synthetic event
+    synthetic_value: Option<String>,
 }
 
 impl InputEvent {
     fn new(event: web_sys::Event) -> Self {
-        InputEvent { event }
+        InputEvent {
+            event,
+            synthetic_value: None,
+        }
     }
 
     /// call prevent default on the underlying event
@@ -298,6 +420,9 @@ impl InputEvent {
     /// TODO: this should be optional since there will be custom component
     /// aside from `input`, `textarea`, `select`
     pub fn value(&self) -> String {
+        if let Some(value) = &self.synthetic_value {
+            return value.clone();
+        }
         let target: EventTarget = self.event.target().expect("Unable to get event target");
         if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
             input.value()
@@ -338,11 +463,22 @@ impl InputEvent {
 }
 
 fn to_input_event(event: Event) -> InputEvent {
-    let web_event = event.as_web().expect("must be a web event");
-    InputEvent::new(web_event)
+    match event {
+        Event::Synthetic(synthetic) => InputEvent {
+            event: SyntheticEvent::placeholder(),
+            synthetic_value: Some(synthetic.value.unwrap_or_default()),
+        },
+        _ => {
+            let web_event = event.as_web().expect("must be a web event");
+            InputEvent::new(web_event)
+        }
+    }
 }
 
 fn to_checked(event: Event) -> bool {
+    if let Event::Synthetic(synthetic) = &event {
+        return synthetic.checked.unwrap_or(false);
+    }
     let web_event = event.as_web().expect("must be a web event");
     let target: EventTarget = web_event.target().expect("Unable to get event target");
     if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
@@ -353,6 +489,9 @@ fn to_checked(event: Event) -> bool {
 }
 
 fn to_open(event: Event) -> bool {
+    if let Event::Synthetic(synthetic) = &event {
+        return synthetic.checked.unwrap_or(false);
+    }
     let web_event = event.as_web().expect("must be a web event");
     let target: EventTarget = web_event.target().expect("Unable to get event target");
     if let Some(details) = target.dyn_ref::<HtmlDetailsElement>() {
@@ -366,14 +505,13 @@ fn to_open(event: Event) -> bool {
 /// therefore trying to access the data on the target element triggered from paste will get an
 /// empty text
 fn to_clipboard_event(event: Event) -> ClipboardEvent {
-    event
-        .as_web()
-        .expect("must be a web event")
-        .dyn_into()
-        .expect("unable to cast to clipboard event")
+    cast_web_event(event, "unable to cast to clipboard event")
 }
 
-fn to_selection(_event: Event) -> Option<Selection> {
+fn to_selection(event: Event) -> Option<Selection> {
+    if let Event::Synthetic(_) = event {
+        return None;
+    }
     if let Ok(Some(selection)) = document().get_selection() {
         Some(selection)
     } else {
@@ -0,0 +1,326 @@
+//! Hydration attaches the app to the html which was already rendered by the server,
+//! reusing the existing DOM nodes instead of re-creating them.
+//!
+//! The existing DOM is walked along with the app's current view, each matching DOM node is
+//! wrapped into a `DomNode` with its event listeners attached.
+//! Mismatches between the DOM and the view are logged and repaired in place, so the
+//! resulting DOM is the same as if the app view is freshly mounted.
+use crate::dom::dom_node::DomInner;
+use crate::dom::{document, Application, DomNode, Program};
+use crate::vdom::{self, Attribute, Leaf, TreePath};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::Node;
+
+impl<APP> Program<APP>
+where
+    APP: Application + 'static,
+{
+    /// hydrate the current view of the app with the existing child nodes of the mount node
+    pub(crate) fn hydrate_initial_view(&self, mount_node: &DomNode) -> DomNode {
+        let current_view = self.app_context.current_vdom();
+        let real_view = current_view.unwrap_template_ref();
+        let parent = mount_node.as_node();
+        let mut cursor = parent.first_child();
+        self.hydrate_node(real_view, &parent, &mut cursor, &TreePath::root())
+    }
+
+    /// hydrate the vdom `node` with the DOM node at the `cursor`,
+    /// the cursor is then moved to the next sibling of the hydrated DOM nodes.
+    fn hydrate_node(
+        &self,
+        node: &vdom::Node<APP::MSG>,
+        parent: &Node,
+        cursor: &mut Option<Node>,
+        path: &TreePath,
+    ) -> DomNode {
+        match node {
+            vdom::Node::Element(elm) => {
+                remove_whitespace_nodes(parent, cursor);
+                match cursor.take() {
+                    Some(existing) if is_same_element(&existing, elm.tag()) => {
+                        *cursor = existing.next_sibling();
+                        self.hydrate_element(elm, existing.unchecked_into(), path)
+                    }
+                    existing => {
+                        log::warn!(
+                            "hydration mismatch at {path:?}: expecting a <{}> element, found: {:?}",
+                            elm.tag(),
+                            existing.as_ref().map(|n| n.node_name())
+                        );
+                        self.replace_with_created(node, parent, existing, cursor)
+                    }
+                }
+            }
+            vdom::Node::Leaf(leaf) => self.hydrate_leaf(node, leaf, parent, cursor, path),
+        }
+    }
+
+    fn hydrate_element(
+        &self,
+        elm: &vdom::Element<APP::MSG>,
+        element: web_sys::Element,
+        path: &TreePath,
+    ) -> DomNode {
+        let attrs = Attribute::merge_attributes_of_same_name(elm.attributes().iter());
+        remove_stale_attributes(&element, &attrs, path);
+
+        let children = Rc::new(RefCell::new(vec![]));
+        let dom_node = DomNode {
+            inner: DomInner::Element {
+                element: element.clone(),
+                listeners: Rc::new(RefCell::new(None)),
+                children: Rc::clone(&children),
+                has_mount_callback: elm.has_mount_callback(),
+            },
+        };
+        // setting the attributes which are already in the DOM has no visible effect,
+        // but this will attach the event listeners to the element
+        let dom_attrs = attrs.iter().map(|a| self.convert_attr(a));
+        dom_node.set_dom_attrs(dom_attrs).expect("set dom attrs");
+
+        let parent: Node = element.unchecked_into();
+        let mut cursor = parent.first_child();
+        let hydrated_children: Vec<DomNode> = elm
+            .children()
+            .iter()
+            .enumerate()
+            .map(|(i, child)| self.hydrate_node(child, &parent, &mut cursor, &path.traverse(i)))
+            .collect();
+
+        // the DOM nodes that are not in the view
+        while let Some(excess) = cursor {
+            cursor = excess.next_sibling();
+            if !is_whitespace_text(&excess) {
+                log::warn!(
+                    "hydration mismatch at {path:?}: removing excess node: {:?}",
+                    excess.node_name()
+                );
+            }
+            parent.remove_child(&excess).expect("must remove child");
+        }
+
+        *children.borrow_mut() = hydrated_children;
+        dom_node.dispatch_mount_event();
+        dom_node
+    }
+
+    fn hydrate_leaf(
+        &self,
+        node: &vdom::Node<APP::MSG>,
+        leaf: &Leaf<APP::MSG>,
+        parent: &Node,
+        cursor: &mut Option<Node>,
+        path: &TreePath,
+    ) -> DomNode {
+        match leaf {
+            Leaf::Text(txt) => match cursor.take() {
+                Some(existing) if existing.node_type() == Node::TEXT_NODE => {
+                    let text_node: web_sys::Text = existing.unchecked_into();
+                    if !split_merged_text(&text_node, txt) && text_node.data() != *txt {
+                        log::warn!(
+                            "hydration mismatch at {path:?}: expecting text {txt:?}, found: {:?}",
+                            text_node.data()
+                        );
+                        text_node.set_data(txt);
+                    }
+                    *cursor = text_node.next_sibling();
+                    DomNode {
+                        inner: DomInner::Text(text_node),
+                    }
+                }
+                // an empty text is rendered as nothing by the server
+                existing if txt.is_empty() => {
+                    let text_node = document().create_text_node("");
+                    parent
+                        .insert_before(&text_node, existing.as_ref())
+                        .expect("must insert text node");
+                    *cursor = existing;
+                    DomNode {
+                        inner: DomInner::Text(text_node),
+                    }
+                }
+                existing => {
+                    log::warn!(
+                        "hydration mismatch at {path:?}: expecting text {txt:?}, found: {:?}",
+                        existing.as_ref().map(|n| n.node_name())
+                    );
+                    self.replace_with_created(node, parent, existing, cursor)
+                }
+            },
+            Leaf::Comment(comment) => {
+                remove_whitespace_nodes(parent, cursor);
+                match cursor.take() {
+                    Some(existing) if existing.node_type() == Node::COMMENT_NODE => {
+                        *cursor = existing.next_sibling();
+                        let comment_node: web_sys::Comment = existing.unchecked_into();
+                        if comment_node.data() != *comment {
+                            comment_node.set_data(comment);
+                        }
+                        DomNode {
+                            inner: DomInner::Comment(comment_node),
+                        }
+                    }
+                    existing => {
+                        log::warn!(
+                            "hydration mismatch at {path:?}: expecting a comment, found: {:?}",
+                            existing.as_ref().map(|n| n.node_name())
+                        );
+                        self.replace_with_created(node, parent, existing, cursor)
+                    }
+                }
+            }
+            // symbols are not tracked as DOM nodes, so we only skip the nodes
+            // which the browser parsed from the symbol markup.
+            Leaf::Symbol(html) | Leaf::TrustedHtml(html) => {
+                for parsed in parse_nodes(html) {
+                    skip_parsed_node(&parsed, cursor);
+                }
+                DomNode {
+                    inner: DomInner::Symbol(html.clone()),
+                }
+            }
+            Leaf::Fragment(nodes) | Leaf::NodeList(nodes) => {
+                let children: Vec<DomNode> = nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, child)| self.hydrate_node(child, parent, cursor, &path.traverse(i)))
+                    .collect();
+                // the server rendered html has no marker, it is inserted where the empty fragment is
+                let marker = document().create_comment("");
+                if children.is_empty() {
+                    parent
+                        .insert_before(&marker, cursor.as_ref())
+                        .expect("insert marker");
+                }
+                DomNode {
+                    inner: DomInner::Fragment {
+                        fragment: document().create_document_fragment(),
+                        children: Rc::new(RefCell::new(children)),
+                        marker,
+                    },
+                }
+            }
+            // stateful components manage their own DOM, so it is always created
+            Leaf::StatefulComponent(_) => {
+                remove_whitespace_nodes(parent, cursor);
+                let existing = cursor.take();
+                self.replace_with_created(node, parent, existing, cursor)
+            }
+            Leaf::StatelessComponent(comp) => {
+                self.hydrate_node(comp.view.unwrap_template_ref(), parent, cursor, path)
+            }
+            Leaf::TemplatedView(view) => self.hydrate_node(&view.view, parent, cursor, path),
+            Leaf::LazyView(view) => self.hydrate_node(view.view(), parent, cursor, path),
+            Leaf::DocType(_) => unreachable!("doc type is never converted"),
+        }
+    }
+
+    /// create the dom node of `node` and put it in place of the `existing` DOM node.
+    /// It is appended to the parent if there is no existing node left.
+    fn replace_with_created(
+        &self,
+        node: &vdom::Node<APP::MSG>,
+        parent: &Node,
+        existing: Option<Node>,
+        cursor: &mut Option<Node>,
+    ) -> DomNode {
+        let created_node = self.create_dom_node(node);
+        if let Some(existing) = existing {
+            *cursor = existing.next_sibling();
+            parent
+                .replace_child(&created_node.as_node(), &existing)
+                .expect("must replace child");
+        } else {
+            parent
+                .append_child(&created_node.as_node())
+                .expect("must append child");
+        }
+        created_node.dispatch_mount_event();
+        created_node
+    }
+}
+
+fn is_same_element(node: &Node, tag: &str) -> bool {
+    node.node_type() == Node::ELEMENT_NODE
+        && node.unchecked_ref::<web_sys::Element>().local_name() == tag
+}
+
+/// whitespace in between elements are added by the pretty render of the server,
+/// this is not part of the view
+fn is_whitespace_text(node: &Node) -> bool {
+    node.node_type() == Node::TEXT_NODE
+        && node
+            .text_content()
+            .map(|text| text.trim().is_empty())
+            .unwrap_or(true)
+}
+
+/// remove the whitespace text nodes at the cursor
+fn remove_whitespace_nodes(parent: &Node, cursor: &mut Option<Node>) {
+    while let Some(node) = cursor.as_ref().filter(|node| is_whitespace_text(node)) {
+        let next = node.next_sibling();
+        parent.remove_child(node).expect("must remove child");
+        *cursor = next;
+    }
+}
+
+/// remove the attributes in the DOM element that are not in the view
+fn remove_stale_attributes<MSG>(
+    element: &web_sys::Element,
+    attrs: &[Attribute<MSG>],
+    path: &TreePath,
+) {
+    let dom_attrs = element.attributes();
+    let stale_attrs: Vec<String> = (0..dom_attrs.length())
+        .filter_map(|i| dom_attrs.item(i))
+        .map(|attr| attr.name())
+        .filter(|name| !attrs.iter().any(|attr| attr.name() == name))
+        .collect();
+    for name in stale_attrs {
+        log::warn!("hydration mismatch at {path:?}: removing attribute: {name}");
+        element
+            .remove_attribute(&name)
+            .expect("must remove attribute");
+    }
+}
+
+/// adjacent texts are merged into one text node when the browser parses the server rendered html,
+/// so the text node is split at the end of the `expected` text if it starts with it.
+/// Returns whether the text node is split
+fn split_merged_text(text_node: &web_sys::Text, expected: &str) -> bool {
+    let data = text_node.data();
+    let is_merged = data.len() > expected.len() && data.starts_with(expected);
+    if is_merged {
+        let at = expected.encode_utf16().count() as u32;
+        text_node.split_text(at).expect("must split text");
+    }
+    is_merged
+}
+
+/// move the cursor past the DOM node which the browser created for the `parsed` node.
+/// A text merged with the adjacent texts is split, so only the parsed text is skipped
+fn skip_parsed_node(parsed: &Node, cursor: &mut Option<Node>) {
+    let Some(existing) = cursor.take() else {
+        return;
+    };
+    if parsed.node_type() == Node::TEXT_NODE && existing.node_type() == Node::TEXT_NODE {
+        let parsed_text = parsed.text_content().unwrap_or_default();
+        split_merged_text(existing.unchecked_ref(), &parsed_text);
+    }
+    *cursor = existing.next_sibling();
+}
+
+/// the DOM nodes the browser creates when parsing the html markup
+fn parse_nodes(html: &str) -> Vec<Node> {
+    // a template parses the html the same way as the body, ie: `<tr>` is kept as is,
+    // while it would be dropped when parsed inside of a `<div>`
+    let template: web_sys::HtmlTemplateElement = document()
+        .create_element("template")
+        .expect("create element")
+        .unchecked_into();
+    template.set_inner_html(html);
+    let nodes = template.content().child_nodes();
+    (0..nodes.length()).filter_map(|i| nodes.get(i)).collect()
+}
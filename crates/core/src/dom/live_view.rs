@@ -0,0 +1,64 @@
+//! Run the app on the server while the browser only mirrors its view.
+//!
+//! The [`LiveServer`] runs the `update`, `view` and `diff` of the app, then sends the
+//! resulting patches as [`SerializablePatch`] to the client.
+//! The [`LiveClient`] is a thin app in the browser which applies the patches into its view,
+//! and forwards the events triggered in the view back to the server as a [`SyntheticEvent`].
+//!
+//! The messages are sent as json through a [`Transport`], such as a websocket.
+//! Use [`channel`] to connect the server and the client in the same process.
+use crate::dom::SyntheticEvent;
+use crate::vdom::{ListenerId, SerializableNode, SerializablePatch};
+use serde::{Deserialize, Serialize};
+
+pub use client::{LiveClient, LiveClientMsg};
+pub use server::LiveServer;
+pub use transport::{channel, ChannelTransport, Transport};
+
+mod client;
+mod server;
+mod transport;
+
+/// The messages sent by the server to the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// the whole view of the app, the patches that follows are relative to this view
+    Mount {
+        /// the view of the app
+        view: SerializableNode,
+    },
+    /// the patches to be applied into the view of the client
+    Patch {
+        /// the patches
+        patches: Vec<SerializablePatch>,
+    },
+}
+
+/// The messages sent by the client to the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// an event is triggered in an element with this event listener
+    Event {
+        /// the id of the event listener
+        listener: ListenerId,
+        /// the event
+        event: SyntheticEvent,
+    },
+}
+
+/// Errors in a live view session
+#[derive(Debug, thiserror::Error)]
+pub enum LiveViewError {
+    /// the other side closed the connection
+    #[error("the connection is closed")]
+    Closed,
+    /// the message can not be serialized or deserialized
+    #[error("invalid message: {0}")]
+    InvalidMessage(#[from] serde_json::Error),
+    /// the client sent an event for a listener which the server does not know of
+    #[error("unknown event listener: {0:?}")]
+    UnknownListener(ListenerId),
+    /// a message is received before the view is mounted
+    #[error("the view is not mounted yet")]
+    NotMounted,
+}
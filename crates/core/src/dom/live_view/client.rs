@@ -0,0 +1,134 @@
+use crate::dom::live_view::{ClientMessage, LiveViewError, ServerMessage, Transport};
+use crate::dom::{Application, Cmd, Event, MountProcedure, Program, SyntheticEvent};
+use crate::html::{comment, doctype, fragment, text, trusted_html};
+use crate::vdom::{
+    element_ns, Attribute, AttributeValue, EventCallback, ListenerId, Namespace, Node,
+    SerializableAttribute, SerializableAttributeValue, SerializableNode,
+};
+use std::rc::Rc;
+
+/// A thin app in the browser which mirrors the view of the app running in the [`LiveServer`].
+///
+/// The patches sent by the server are applied into the mirrored view, which is then
+/// updated into the DOM by the [`Program`], while the events triggered in the view are
+/// forwarded to the server.
+///
+/// [`LiveServer`]: crate::dom::live_view::LiveServer
+pub struct LiveClient<T> {
+    transport: Rc<T>,
+    /// the view of the app in the server
+    view: SerializableNode,
+}
+
+/// The msg of the [`LiveClient`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveClientMsg {
+    /// a message is received from the server
+    Received(String),
+    /// an event is triggered in an element with this event listener
+    Event(ListenerId, SyntheticEvent),
+}
+
+impl<T> LiveClient<T>
+where
+    T: Transport + 'static,
+{
+    /// Wait for the server to send the view of the app then mount it into `mount_node`,
+    /// the patches sent by the server are then applied until the connection is closed.
+    pub async fn mount(transport: T, mount_node: &web_sys::Node) -> Result<(), LiveViewError> {
+        let transport = Rc::new(transport);
+        let message = transport.recv().await.ok_or(LiveViewError::Closed)?;
+        let ServerMessage::Mount { view } = serde_json::from_str(&message)? else {
+            return Err(LiveViewError::NotMounted);
+        };
+        let client = LiveClient {
+            transport: Rc::clone(&transport),
+            view,
+        };
+        let mut program = Program::new(client);
+        program.mount(mount_node, MountProcedure::append());
+        while let Some(message) = transport.recv().await {
+            program.dispatch(LiveClientMsg::Received(message));
+        }
+        Ok(())
+    }
+}
+
+impl<T> Application for LiveClient<T>
+where
+    T: Transport + 'static,
+{
+    type MSG = LiveClientMsg;
+
+    fn update(&mut self, msg: LiveClientMsg) -> Cmd<LiveClientMsg> {
+        match msg {
+            LiveClientMsg::Received(message) => match serde_json::from_str(&message) {
+                Ok(ServerMessage::Mount { view }) => self.view = view,
+                Ok(ServerMessage::Patch { patches }) => self.view.apply_patches(&patches),
+                Err(e) => log::error!("invalid message from the server: {e}"),
+            },
+            LiveClientMsg::Event(listener, event) => {
+                let message = ClientMessage::Event { listener, event };
+                let sent = serde_json::to_string(&message)
+                    .map_err(LiveViewError::from)
+                    .and_then(|message| self.transport.send(message));
+                if let Err(e) = sent {
+                    log::error!("unable to send the event to the server: {e}");
+                }
+            }
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<LiveClientMsg> {
+        to_node(&self.view)
+    }
+}
+
+/// convert the node sent by the server into a node which event listeners
+/// produces [`LiveClientMsg::Event`]
+fn to_node(node: &SerializableNode) -> Node<LiveClientMsg> {
+    match node {
+        SerializableNode::Element {
+            namespace,
+            tag,
+            attrs,
+            children,
+            self_closing,
+        } => element_ns(
+            namespace.clone().map(Namespace::from),
+            tag.clone(),
+            attrs.iter().map(to_attribute),
+            children.iter().map(to_node),
+            *self_closing,
+        ),
+        SerializableNode::Text(v) => text(v),
+        SerializableNode::Comment(v) => comment(v.clone()),
+        SerializableNode::Html(v) => trusted_html(v.clone()),
+        SerializableNode::DocType(v) => doctype(v.clone()),
+        SerializableNode::Fragment(nodes) => fragment(nodes.iter().map(to_node)),
+    }
+}
+
+fn to_attribute(attr: &SerializableAttribute) -> Attribute<LiveClientMsg> {
+    Attribute::with_multiple_values(
+        attr.namespace.clone().map(Namespace::from),
+        attr.name.clone(),
+        attr.value.iter().map(|value| match value {
+            SerializableAttributeValue::Simple(v) => AttributeValue::Simple(v.clone()),
+            SerializableAttributeValue::Style(v) => AttributeValue::Style(v.clone()),
+            SerializableAttributeValue::EventListener(listener) => {
+                let listener = *listener;
+                AttributeValue::EventListener(EventCallback::from(move |event: Event| {
+                    let event = match event {
+                        Event::WebEvent(web_event) => SyntheticEvent::from_web_event(&web_event),
+                        Event::MountEvent(_) => SyntheticEvent::new("mount"),
+                        Event::Synthetic(event) => event,
+                    };
+                    LiveClientMsg::Event(listener, event)
+                }))
+            }
+            SerializableAttributeValue::Empty => AttributeValue::Empty,
+        }),
+    )
+}
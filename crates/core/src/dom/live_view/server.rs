@@ -0,0 +1,167 @@
+use crate::dom::cmd::{Command, PendingCommand};
+use crate::dom::live_view::{ClientMessage, LiveViewError, ServerMessage, Transport};
+use crate::dom::{Application, Cmd, Event};
+use crate::vdom::{diff, ListenerRegistry, Node, SerializableNode, SerializablePatch};
+use futures::future::{self, Either};
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use std::rc::Rc;
+
+/// Runs the app on the server, sending the patches of its view to the [`LiveClient`]
+/// and dispatching the events forwarded by the client.
+///
+/// [`LiveClient`]: crate::dom::live_view::LiveClient
+pub struct LiveServer<APP, T>
+where
+    APP: Application,
+{
+    app: APP,
+    /// shared with the message which is being received in `run`
+    transport: Rc<T>,
+    /// the last view that is sent to the client
+    current_vdom: Option<Node<APP::MSG>>,
+    /// the view of the client, with the ids of the event listeners that are sent to it
+    client_view: Option<SerializableNode>,
+    /// the event listeners which are used in the view of the client
+    listeners: ListenerRegistry<APP::MSG>,
+    /// the commands returned by `init` and `update` which are not done yet
+    pending_cmds: FuturesUnordered<PendingCommand<APP::MSG>>,
+}
+
+impl<APP, T> LiveServer<APP, T>
+where
+    APP: Application,
+    APP::MSG: 'static,
+    T: Transport,
+{
+    /// create a live server for this app, communicating with the client through `transport`
+    pub fn new(app: APP, transport: T) -> Self {
+        Self {
+            app,
+            transport: Rc::new(transport),
+            current_vdom: None,
+            client_view: None,
+            listeners: ListenerRegistry::new(),
+            pending_cmds: FuturesUnordered::new(),
+        }
+    }
+
+    /// return a reference to the app
+    pub fn app(&self) -> &APP {
+        &self.app
+    }
+
+    /// the view which is last sent to the client
+    pub fn current_vdom(&self) -> Option<&Node<APP::MSG>> {
+        self.current_vdom.as_ref()
+    }
+
+    /// initialize the app, then send its whole view to the client
+    pub fn mount(&mut self) -> Result<(), LiveViewError> {
+        let cmd = self.app.init();
+        self.queue_cmd(cmd);
+        let view = self.app.view();
+        self.listeners.clear();
+        let view_node = SerializableNode::from_node(&view, &mut self.listeners);
+        self.send(&ServerMessage::Mount {
+            view: view_node.clone(),
+        })?;
+        self.current_vdom = Some(view);
+        self.client_view = Some(view_node);
+        Ok(())
+    }
+
+    /// update the app with this msg, then send the patches of the new view to the client
+    pub fn dispatch(&mut self, msg: APP::MSG) -> Result<(), LiveViewError> {
+        let cmd = self.app.update(msg);
+        self.queue_cmd(cmd);
+        self.update_view()
+    }
+
+    /// handle the message sent by the client
+    pub fn handle_message(&mut self, message: &str) -> Result<(), LiveViewError> {
+        let ClientMessage::Event { listener, event } = serde_json::from_str(message)?;
+        let callback = self
+            .listeners
+            .get(listener)
+            .ok_or(LiveViewError::UnknownListener(listener))?;
+        let msg = callback.emit(Event::from(event));
+        self.dispatch(msg)
+    }
+
+    /// Mount the app, then keep handling the messages from the client and the msgs of the
+    /// commands until the connection is closed.
+    ///
+    /// Invalid messages from the client are logged and ignored.
+    pub async fn run(&mut self) -> Result<(), LiveViewError> {
+        self.mount()?;
+        let transport = Rc::clone(&self.transport);
+        // the message which is being received is kept when a command is done first,
+        // so it is not dropped half way through
+        let mut recv = transport.recv();
+        loop {
+            let next = if self.pending_cmds.is_empty() {
+                Either::Left((&mut recv).await)
+            } else {
+                match future::select(&mut recv, self.pending_cmds.next()).await {
+                    Either::Left((message, _)) => Either::Left(message),
+                    Either::Right((msg, _)) => Either::Right(msg.flatten()),
+                }
+            };
+            if let Either::Left(_) = next {
+                recv = transport.recv();
+            }
+            let result = match next {
+                Either::Left(Some(message)) => self.handle_message(&message),
+                Either::Left(None) => return Ok(()),
+                Either::Right(Some((msg, command))) => {
+                    self.queue_command(command);
+                    self.dispatch(msg)
+                }
+                Either::Right(None) => Ok(()),
+            };
+            match result {
+                Err(LiveViewError::Closed) => return Err(LiveViewError::Closed),
+                Err(e) => log::warn!("error handling the client message: {e}"),
+                Ok(()) => (),
+            }
+        }
+    }
+
+    /// diff the current view with the new view of the app and send the patches to the client.
+    /// The event listeners of the nodes which are removed or replaced in the view of the client
+    /// are removed from the registry
+    fn update_view(&mut self) -> Result<(), LiveViewError> {
+        let (Some(current_vdom), Some(client_view)) =
+            (self.current_vdom.as_ref(), self.client_view.as_mut())
+        else {
+            return Err(LiveViewError::NotMounted);
+        };
+        let view = self.app.view();
+        let patches: Vec<SerializablePatch> = diff(current_vdom, &view)
+            .iter()
+            .map(|patch| SerializablePatch::from_patch(patch, &mut self.listeners))
+            .collect();
+        if !patches.is_empty() {
+            client_view.apply_patches(&patches);
+            self.listeners.retain_used(client_view);
+            self.send(&ServerMessage::Patch { patches })?;
+        }
+        self.current_vdom = Some(view);
+        Ok(())
+    }
+
+    fn queue_cmd(&mut self, cmd: Cmd<APP::MSG>) {
+        for command in cmd.commands {
+            self.queue_command(command);
+        }
+    }
+
+    fn queue_command(&mut self, command: Command<APP::MSG>) {
+        self.pending_cmds.push(command.into_next());
+    }
+
+    fn send(&self, message: &ServerMessage) -> Result<(), LiveViewError> {
+        self.transport.send(serde_json::to_string(message)?)
+    }
+}
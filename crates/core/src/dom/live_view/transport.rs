@@ -0,0 +1,62 @@
+use crate::dom::live_view::LiveViewError;
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::future::LocalBoxFuture;
+use futures::lock::Mutex;
+use futures::StreamExt;
+
+/// Sends and receives the messages in between the live server and the live client
+pub trait Transport {
+    /// send the message to the other side
+    fn send(&self, message: String) -> Result<(), LiveViewError>;
+
+    /// wait for the next message from the other side,
+    /// returns `None` when the connection is closed.
+    /// [`LiveServer::run`] keeps polling the returned future until it is done,
+    /// even when the msg of a command comes first, so it doesn't need to be cancel safe.
+    ///
+    /// [`LiveServer::run`]: crate::dom::live_view::LiveServer::run
+    fn recv(&self) -> LocalBoxFuture<'_, Option<String>>;
+}
+
+/// A transport which sends the messages through an in-process channel
+#[derive(Debug)]
+pub struct ChannelTransport {
+    sender: UnboundedSender<String>,
+    receiver: Mutex<UnboundedReceiver<String>>,
+}
+
+/// Create a pair of connected transports,
+/// the messages sent on one side are received on the other side
+pub fn channel() -> (ChannelTransport, ChannelTransport) {
+    let (tx1, rx1) = mpsc::unbounded();
+    let (tx2, rx2) = mpsc::unbounded();
+    (
+        ChannelTransport {
+            sender: tx1,
+            receiver: Mutex::new(rx2),
+        },
+        ChannelTransport {
+            sender: tx2,
+            receiver: Mutex::new(rx1),
+        },
+    )
+}
+
+impl ChannelTransport {
+    /// close the channel, the other side will no longer receive messages
+    pub fn close(&self) {
+        self.sender.close_channel();
+    }
+}
+
+impl Transport for ChannelTransport {
+    fn send(&self, message: String) -> Result<(), LiveViewError> {
+        self.sender
+            .unbounded_send(message)
+            .map_err(|_| LiveViewError::Closed)
+    }
+
+    fn recv(&self) -> LocalBoxFuture<'_, Option<String>> {
+        Box::pin(async move { self.receiver.lock().await.next().await })
+    }
+}
@@ -13,6 +13,8 @@ use crate::vdom;
 use crate::vdom::diff;
 use crate::vdom::diff_recursive;
 use crate::vdom::Patch;
+#[cfg(feature = "with-ric")]
+use crate::vdom::ResumableDiff;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::VecDeque;
 use std::hash::{Hash, Hasher};
@@ -68,6 +70,22 @@ where
 
     /// keep track of the time when the dom is last updated
     pub(crate) last_update: Rc<RefCell<Option<f64>>>,
+
+    /// the diff of the new view which is not complete yet,
+    /// it is continued in the next idle period
+    #[cfg(feature = "with-ric")]
+    pub(crate) pending_diff: Rc<RefCell<Option<PendingDiff<APP::MSG>>>>,
+}
+
+/// A diff of the new view against the current vdom, which is diffed in time slices
+#[cfg(feature = "with-ric")]
+pub(crate) struct PendingDiff<MSG> {
+    /// the new view
+    view: vdom::Node<MSG>,
+    /// the diff state, which continues where it left off
+    diff: ResumableDiff,
+    /// the patches converted so far, these are applied when the diff is complete
+    dom_patches: Vec<DomPatch>,
 }
 
 pub struct WeakProgram<APP>
@@ -81,6 +99,8 @@ where
     idle_callback_handles: Weak<RefCell<Vec<IdleCallbackHandle>>>,
     animation_frame_handles: Weak<RefCell<Vec<AnimationFrameHandle>>>,
     last_update: Weak<RefCell<Option<f64>>>,
+    #[cfg(feature = "with-ric")]
+    pending_diff: Weak<RefCell<Option<PendingDiff<APP::MSG>>>>,
 }
 
 impl<APP> WeakProgram<APP>
@@ -96,6 +116,8 @@ where
         let idle_callback_handles = self.idle_callback_handles.upgrade()?;
         let animation_frame_handles = self.animation_frame_handles.upgrade()?;
         let last_update = self.last_update.upgrade()?;
+        #[cfg(feature = "with-ric")]
+        let pending_diff = self.pending_diff.upgrade()?;
         Some(Program {
             app_context,
             root_node,
@@ -104,6 +126,8 @@ where
             idle_callback_handles,
             animation_frame_handles,
             last_update,
+            #[cfg(feature = "with-ric")]
+            pending_diff,
         })
     }
 }
@@ -121,6 +145,8 @@ where
             idle_callback_handles: Weak::clone(&self.idle_callback_handles),
             animation_frame_handles: Weak::clone(&self.animation_frame_handles),
             last_update: Weak::clone(&self.last_update),
+            #[cfg(feature = "with-ric")]
+            pending_diff: Weak::clone(&self.pending_diff),
         }
     }
 }
@@ -139,6 +165,8 @@ where
             idle_callback_handles: Rc::downgrade(&self.idle_callback_handles),
             animation_frame_handles: Rc::downgrade(&self.animation_frame_handles),
             last_update: Rc::downgrade(&self.last_update),
+            #[cfg(feature = "with-ric")]
+            pending_diff: Rc::downgrade(&self.pending_diff),
         }
     }
 }
@@ -156,6 +184,8 @@ where
             idle_callback_handles: Rc::clone(&self.idle_callback_handles),
             animation_frame_handles: Rc::clone(&self.animation_frame_handles),
             last_update: Rc::clone(&self.last_update),
+            #[cfg(feature = "with-ric")]
+            pending_diff: Rc::clone(&self.pending_diff),
         }
     }
 }
@@ -201,6 +231,8 @@ where
             idle_callback_handles: Rc::new(RefCell::new(vec![])),
             animation_frame_handles: Rc::new(RefCell::new(vec![])),
             last_update: Rc::new(RefCell::new(None)),
+            #[cfg(feature = "with-ric")]
+            pending_diff: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -292,6 +324,31 @@ where
         ManuallyDrop::new(program)
     }
 
+    /// Instantiate the app and hydrate the html which was rendered by the server into the mount node,
+    /// reusing the existing DOM nodes instead of re-creating them.
+    /// Mismatches in between the server rendered html and the app view are logged and repaired.
+    /// # Example
+    /// ```rust,ignore
+    /// # use sauron::prelude::*;
+    /// # use sauron::document;
+    /// struct App{}
+    /// # impl Application<()> for App{
+    /// #     fn view(&self) -> Node<()>{
+    /// #         text("hello")
+    /// #     }
+    /// #     fn update(&mut self, _: ()) -> Cmd<Self, ()> {
+    /// #         Cmd::none()
+    /// #     }
+    /// # }
+    /// let mount = document().query_selector("#app").ok().flatten().unwrap();
+    /// Program::hydrate(App{}, &mount);
+    /// ```
+    pub fn hydrate(app: APP, mount_node: &web_sys::Node) -> ManuallyDrop<Self> {
+        let mut program = Self::new(app);
+        program.mount(mount_node, MountProcedure::hydrate());
+        ManuallyDrop::new(program)
+    }
+
     /// clear the existing children of the mount before mounting the app
     pub fn clear_append_to_mount(app: APP, mount_node: &web_sys::Node) -> ManuallyDrop<Self> {
         let mut program = Self::new(app);
@@ -350,8 +407,6 @@ where
         *self.mount_node.borrow_mut() = Some(mount_node);
         self.pre_mount();
 
-        let created_node = self.create_initial_view();
-
         let mount_node: DomNode = match mount_procedure.target {
             MountTarget::MountNode => self
                 .mount_node
@@ -382,19 +437,31 @@ where
             }
         };
 
-        match mount_procedure.action {
+        let root_node = match mount_procedure.action {
             MountAction::Append => {
+                let created_node = self.create_initial_view();
                 mount_node.append_children(vec![created_node.clone()]);
+                created_node
             }
             MountAction::ClearAppend => {
+                let created_node = self.create_initial_view();
                 mount_node.clear_children();
                 mount_node.append_children(vec![created_node.clone()]);
+                created_node
             }
             MountAction::Replace => {
+                let created_node = self.create_initial_view();
                 mount_node.replace_node(created_node.clone());
+                created_node
             }
-        }
-        *self.root_node.borrow_mut() = Some(created_node);
+            MountAction::Hydrate => {
+                let hydrated_node = self.hydrate_initial_view(&mount_node);
+                // the children of the mount node could have been repaired during hydration
+                *self.mount_node.borrow_mut() = Some(DomNode::from(mount_node.as_node()));
+                hydrated_node
+            }
+        };
+        *self.root_node.borrow_mut() = Some(root_node);
         self.after_mounted();
     }
 
@@ -459,6 +526,9 @@ where
                 return Ok(())
             }
         }
+        // the unfinished diff is against a view that is now outdated
+        #[cfg(feature = "with-ric")]
+        self.pending_diff.borrow_mut().take();
         log::info!("Doing and update...");
         UPDATE_CNT.with_borrow_mut(|c|*c += 1);
         log::info!("ratio(cancelled/update): {}/{}", CANCEL_CNT.with_borrow(|c|*c), UPDATE_CNT.with_borrow(|c|*c));
@@ -479,6 +549,7 @@ where
             {
                 log::info!("There are {} patches", patches.len());
                 log::info!("patches: {patches:#?}");
+                log::info!("{}", vdom::diff_report(real_current_vdom, real_view));
             }
             self.convert_patches(
                 self.root_node
@@ -528,6 +599,75 @@ where
         Ok(())
     }
 
+    /// execute DOM changes to reflect the APP's view, where the view is diffed in time slices.
+    /// The diffing yields when the deadline runs out and is continued in the next idle period,
+    /// so diffing a very large view doesn't block the user input.
+    ///
+    /// The patches are applied when the diff is complete.
+    /// If a new view is created while the diff is not complete yet,
+    /// the unfinished diff is discarded and the new view is diffed instead,
+    /// since the DOM is not changed until then.
+    #[cfg(feature = "with-ric")]
+    pub fn update_dom_with_deadline(&mut self, deadline: IdleDeadline) -> Result<(), JsValue> {
+        // the patches are located in the DOM, so it must reflect the current vdom
+        self.apply_pending_patches()?;
+        let view = self.app_context.view();
+        let diff = match view.skip_diff() {
+            Some(skip_diff) => ResumableDiff::with_skip_diff(skip_diff),
+            None => ResumableDiff::new(),
+        };
+        *self.pending_diff.borrow_mut() = Some(PendingDiff {
+            view,
+            diff,
+            dom_patches: vec![],
+        });
+        self.resume_diff(deadline)
+    }
+
+    /// continue the pending diff until the deadline runs out,
+    /// then apply the patches if the diff is complete or continue in the next idle period
+    #[cfg(feature = "with-ric")]
+    fn resume_diff(&mut self, deadline: IdleDeadline) -> Result<(), JsValue> {
+        let Some(mut pending_diff) = self.pending_diff.borrow_mut().take() else {
+            return Ok(());
+        };
+        {
+            let current_vdom = self.app_context.current_vdom();
+            let patches = pending_diff.diff.resume(
+                current_vdom.unwrap_template_ref(),
+                pending_diff.view.unwrap_template_ref(),
+                || deadline.time_remaining() <= 0.0,
+            );
+            let dom_patches = self.convert_patches(
+                self.root_node
+                    .borrow()
+                    .as_ref()
+                    .expect("must have a root node"),
+                &patches,
+            )?;
+            pending_diff.dom_patches.extend(dom_patches);
+        }
+
+        if pending_diff.diff.is_complete() {
+            let PendingDiff {
+                view, dom_patches, ..
+            } = pending_diff;
+            self.queue_dom_patches(dom_patches)?;
+            self.app_context.set_current_dom(view);
+            *self.last_update.borrow_mut() = Some(now());
+        } else {
+            *self.pending_diff.borrow_mut() = Some(pending_diff);
+            let program = Program::downgrade(self);
+            let handle = request_idle_callback(move |deadline| {
+                if let Some(mut program) = program.upgrade() {
+                    program.resume_diff(deadline).expect("must resume diff");
+                }
+            })?;
+            self.idle_callback_handles.borrow_mut().push(handle);
+        }
+        Ok(())
+    }
+
     /// patch the DOM to reflect the App's view
     ///
     /// Note: This is in another function so as to allow tests to use this shared code
@@ -567,6 +707,7 @@ where
         {
             log::debug!("There are {} patches", patches.len());
             log::debug!("patches: {patches:#?}");
+            log::debug!("{}", vdom::diff_report(&current_vdom, new_vdom));
         }
 
         self.convert_patches(
@@ -659,7 +800,7 @@ where
     /// - The view is reconstructed with the new state of the app.
     /// - The dom is updated with the newly reconstructed view.
     fn dispatch_inner(&mut self, deadline: Option<IdleDeadline>) {
-        self.dispatch_pending_msgs(deadline)
+        self.dispatch_pending_msgs(deadline.clone())
             .expect("must dispatch msgs");
         // ensure that all pending msgs are all dispatched already
         #[cfg(feature = "ensure-check")]
@@ -685,6 +826,14 @@ where
             );
         }
 
+        #[cfg(feature = "with-ric")]
+        if let Some(deadline) = deadline {
+            self.update_dom_with_deadline(deadline)
+                .expect("must update dom");
+        } else {
+            self.update_dom().expect("must update dom");
+        }
+        #[cfg(not(feature = "with-ric"))]
         self.update_dom().expect("must update dom");
 
         // Ensure all pending patches are applied before emiting the Cmd from update
@@ -0,0 +1,123 @@
+//! Render the app as a complete html document on the server.
+//!
+//! The stylesheets of the app and of every component used in the view are collected into
+//! `<style>` elements in the `<head>`, so the server rendered page is already styled
+//! before the wasm is loaded.
+use crate::dom::Application;
+use crate::html::attributes::{charset, content, name};
+use crate::html::{body, doctype, head, html, meta, node_list, tags, text};
+use crate::vdom::{Leaf, Node};
+
+/// The content of the `<head>` of a rendered document
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentHead {
+    /// the title of the document
+    pub title: Option<String>,
+    /// the `<meta name=".." content="..">` entries, in order
+    pub meta: Vec<(String, String)>,
+}
+
+impl DocumentHead {
+    /// create a document head with this title
+    pub fn with_title(title: impl ToString) -> Self {
+        Self {
+            title: Some(title.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// add a meta entry with `name` and `content`
+    pub fn meta(mut self, name: impl ToString, content: impl ToString) -> Self {
+        self.meta.push((name.to_string(), content.to_string()));
+        self
+    }
+}
+
+/// Create the view of the whole document: the doctype, the `<head>` with the title, meta and
+/// the stylesheets, followed by the `<body>` containing the view of the app.
+///
+/// The stylesheets are written in this order:
+/// the static stylesheet of the app, the dynamic style of the app,
+/// then the static stylesheet of each component found in the view.
+/// Stylesheets with the same content are only written once.
+pub fn document_view<APP>(app: &APP, document_head: &DocumentHead) -> Node<APP::MSG>
+where
+    APP: Application,
+{
+    let view = app.view();
+
+    let mut stylesheets = vec![APP::stylesheet().join(""), app.style().join("")];
+    collect_component_stylesheets(&view, &mut stylesheets);
+    let mut unique_stylesheets: Vec<String> = vec![];
+    for stylesheet in stylesheets {
+        if !stylesheet.is_empty() && !unique_stylesheets.contains(&stylesheet) {
+            unique_stylesheets.push(stylesheet);
+        }
+    }
+
+    let head_nodes =
+        [meta([charset("utf-8")], [])]
+            .into_iter()
+            .chain(
+                document_head
+                    .title
+                    .as_ref()
+                    .map(|title| tags::title([], [text(title)])),
+            )
+            .chain(document_head.meta.iter().map(|(meta_name, meta_content)| {
+                meta([name(meta_name), content(meta_content)], [])
+            }))
+            .chain(
+                unique_stylesheets
+                    .into_iter()
+                    .map(|stylesheet| tags::style([], [text(stylesheet)])),
+            );
+
+    node_list([
+        doctype("html"),
+        html([], [head([], head_nodes), body([], [view])]),
+    ])
+}
+
+/// Render the app as a complete html document, see [`document_view`]
+pub fn render_document<APP>(app: &APP, document_head: &DocumentHead) -> String
+where
+    APP: Application,
+{
+    document_view(app, document_head).render_to_string()
+}
+
+/// collect the static stylesheets of the components used in this node
+fn collect_component_stylesheets<MSG>(node: &Node<MSG>, stylesheets: &mut Vec<String>) {
+    match node {
+        Node::Element(elm) => {
+            for child in elm.children() {
+                collect_component_stylesheets(child, stylesheets);
+            }
+        }
+        Node::Leaf(leaf) => match leaf {
+            Leaf::NodeList(nodes) | Leaf::Fragment(nodes) => {
+                for child in nodes {
+                    collect_component_stylesheets(child, stylesheets);
+                }
+            }
+            Leaf::StatelessComponent(comp) => {
+                stylesheets.push((comp.stylesheet)().join(""));
+                collect_component_stylesheets(&comp.view, stylesheets);
+            }
+            Leaf::StatefulComponent(comp) => {
+                stylesheets.push((comp.stylesheet)().join(""));
+                for child in &comp.children {
+                    collect_component_stylesheets(child, stylesheets);
+                }
+            }
+            Leaf::TemplatedView(view) => collect_component_stylesheets(&view.view, stylesheets),
+            Leaf::LazyView(view) => collect_component_stylesheets(view.view(), stylesheets),
+            Leaf::Text(_)
+            | Leaf::Symbol(_)
+            | Leaf::TrustedHtml(_)
+            | Leaf::Comment(_)
+            | Leaf::DocType(_) => (),
+        },
+    }
+}
@@ -0,0 +1,98 @@
+//! Trigger the event listeners of a view without a browser,
+//! so the wiring of the events in the view can be tested with a plain `cargo test`.
+use crate::dom::{Event, SyntheticEvent};
+use crate::vdom::{Node, SelectorError, TreePath};
+
+/// An error when the event can not be fired on the target node
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FireEventError {
+    /// the css selector of the target is not valid
+    #[error("invalid selector: {0}")]
+    InvalidSelector(#[from] SelectorError),
+    /// no element in the view matches the css selector
+    #[error("no element matches the selector `{0}`")]
+    NoMatch(String),
+    /// there is no node at the path in the view
+    #[error("no node at path {0:?}")]
+    NoNodeAtPath(TreePath),
+}
+
+impl<MSG> Node<MSG>
+where
+    MSG: 'static,
+{
+    /// fire the event on the first element which matches the css `selector`,
+    /// calling the event listeners of the element for this event type,
+    /// ie: the listeners attached with `on_click` for a `click` event.
+    /// Returns the messages of the event listeners, which is empty when the element
+    /// doesn't listen to this event.
+    ///
+    /// The event is only fired on the target element, it doesn't bubble up into its ancestors.
+    /// The event listeners receive the value and checked state from the [`SyntheticEvent`],
+    /// while the typed web events such as `MouseEvent` are placeholders which methods can not be called.
+    /// The key and mouse position of the event are read with [`Event::key`] and [`Event::client_position`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use sauron::{*, dom::SyntheticEvent, html::events::*};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Msg {
+    ///     Increment,
+    ///     SetName(String),
+    /// }
+    ///
+    /// let view: Node<Msg> = div(
+    ///     [],
+    ///     [
+    ///         button([class("inc"), on_click(|_| Msg::Increment)], [text("+")]),
+    ///         input([on_input(|e| Msg::SetName(e.value()))], []),
+    ///     ],
+    /// );
+    /// assert_eq!(
+    ///     view.fire_event("button.inc", SyntheticEvent::new("click")),
+    ///     Ok(vec![Msg::Increment])
+    /// );
+    /// assert_eq!(
+    ///     view.fire_event("input", SyntheticEvent::new("input").with_value("sauron")),
+    ///     Ok(vec![Msg::SetName("sauron".to_string())])
+    /// );
+    /// ```
+    pub fn fire_event(
+        &self,
+        selector: &str,
+        event: SyntheticEvent,
+    ) -> Result<Vec<MSG>, FireEventError> {
+        let (target, _path) = self
+            .query_selector(selector)?
+            .ok_or_else(|| FireEventError::NoMatch(selector.to_string()))?;
+        Ok(target.emit_event(event))
+    }
+
+    /// fire the event on the node at this `path`, located the same way the patch paths are.
+    /// See [`Node::fire_event`]
+    pub fn fire_event_at(
+        &self,
+        path: &TreePath,
+        event: SyntheticEvent,
+    ) -> Result<Vec<MSG>, FireEventError> {
+        let target = self
+            .find_view_node(&path.path)
+            .ok_or_else(|| FireEventError::NoNodeAtPath(path.clone()))?;
+        Ok(target.unwrap_view().emit_event(event))
+    }
+
+    /// call the event listeners of this node which listens to the event type
+    fn emit_event(&self, event: SyntheticEvent) -> Vec<MSG> {
+        let Some(attributes) = self.attributes() else {
+            return vec![];
+        };
+        attributes
+            .iter()
+            .filter(|attr| *attr.name() == event.event_type)
+            .flat_map(|attr| attr.value())
+            .filter_map(|value| value.as_event_listener())
+            .map(|listener| listener.emit(Event::from(event.clone())))
+            .collect()
+    }
+}
@@ -0,0 +1,86 @@
+//! Transfer the state of the app from the server rendered page to the client.
+//!
+//! The server embeds the serialized app state in the page along with the rendered view,
+//! the client then reconstructs the app from it, so the app is hydrated with the
+//! exact same state the view was rendered with.
+use crate::dom::{document, Application, Program};
+use crate::html::{attributes::attr, attributes::id, script, text};
+use crate::vdom::{node_list, Node};
+use serde::{de::DeserializeOwned, Serialize};
+use std::mem::ManuallyDrop;
+
+/// The id of the script element which contains the serialized state of the app
+pub const APP_STATE_ELEMENT_ID: &str = "sauron-app-state";
+
+/// Errors when transferring the app state
+#[derive(Debug, thiserror::Error)]
+pub enum StateTransferError {
+    /// the app state can not be serialized or deserialized
+    #[error("invalid app state: {0}")]
+    InvalidState(#[from] serde_json::Error),
+    /// there is no element containing the app state in the document
+    #[error("no app state found in element with id: {0}")]
+    MissingState(&'static str),
+}
+
+/// Create a script element which contains the serialized `state`.
+/// This can be placed anywhere in the server rendered page.
+pub fn app_state_script<MSG, T>(state: &T) -> Result<Node<MSG>, StateTransferError>
+where
+    T: Serialize,
+{
+    let json = serde_json::to_string(state)?;
+    // the content of script is not escaped when rendered, these characters can only appear
+    // inside json strings, so they are written as unicode escapes instead.
+    // This prevents the state from closing the script element with a `</script>`
+    let json = json
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026");
+    Ok(script(
+        [id(APP_STATE_ELEMENT_ID), attr("type", "application/json")],
+        [text(json)],
+    ))
+}
+
+/// Render the view of the app followed by its serialized state.
+/// On the client, the rendered view is hydrated with [`Program::hydrate_with_state`]
+pub fn render_with_state<APP>(app: &APP) -> Result<Node<APP::MSG>, StateTransferError>
+where
+    APP: Application + Serialize,
+{
+    Ok(node_list([app.view(), app_state_script(app)?]))
+}
+
+/// Read the state which was embedded in the document by [`app_state_script`]
+pub fn read_app_state<T>() -> Result<T, StateTransferError>
+where
+    T: DeserializeOwned,
+{
+    let json = document()
+        .get_element_by_id(APP_STATE_ELEMENT_ID)
+        .and_then(|element| element.text_content())
+        .ok_or(StateTransferError::MissingState(APP_STATE_ELEMENT_ID))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+impl<APP> Program<APP>
+where
+    APP: Application + DeserializeOwned,
+{
+    /// Create the app from the state which was embedded in the document by the server,
+    /// then hydrate the server rendered view in the mount node.
+    /// # Example
+    /// ```rust,ignore
+    /// # use sauron::prelude::*;
+    /// # use sauron::document;
+    /// let mount = document().query_selector("#app").ok().flatten().unwrap();
+    /// Program::<App>::hydrate_with_state(&mount).expect("must have the app state");
+    /// ```
+    pub fn hydrate_with_state(
+        mount_node: &web_sys::Node,
+    ) -> Result<ManuallyDrop<Self>, StateTransferError> {
+        let app: APP = read_app_state()?;
+        Ok(Self::hydrate(app, mount_node))
+    }
+}
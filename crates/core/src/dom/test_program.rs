@@ -0,0 +1,213 @@
+use crate::dom::cmd::{Command, PendingCommand};
+use crate::dom::{Application, Cmd, FireEventError, SyntheticEvent};
+use crate::vdom::Node;
+use futures::future;
+use futures::stream::FuturesUnordered;
+use futures::task::{self, ArcWake};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Runs an app without a browser, for testing the `init`, `update` and `view` of the app natively.
+///
+/// The `Cmd`s returned by `init` and `update` are queued, and their futures are run
+/// when [`TestProgram::settle`] is awaited, dispatching their msgs back into the app.
+/// The commands which depend on the browser, such as timers and the window events, never complete,
+/// these are kept pending.
+///
+/// # Example
+/// ```rust
+/// use futures::executor::block_on;
+/// use sauron::{*, dom::{SyntheticEvent, TestProgram}, html::events::*};
+///
+/// enum Msg {
+///     Increment,
+///     Reset,
+/// }
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     count: i32,
+/// }
+///
+/// impl Application for Counter {
+///     type MSG = Msg;
+///
+///     fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+///         match msg {
+///             Msg::Increment if self.count == 2 => {
+///                 self.count += 1;
+///                 return Cmd::new(async { Msg::Reset });
+///             }
+///             Msg::Increment => self.count += 1,
+///             Msg::Reset => self.count = 0,
+///         }
+///         Cmd::none()
+///     }
+///
+///     fn view(&self) -> Node<Msg> {
+///         button([on_click(|_| Msg::Increment)], [text(self.count)])
+///     }
+/// }
+///
+/// let mut program = TestProgram::new(Counter::default());
+/// program.dispatch(Msg::Increment);
+/// program.fire_event("button", SyntheticEvent::new("click")).unwrap();
+/// assert_eq!(program.view().render_to_string(), "<button>2</button>");
+///
+/// program.dispatch(Msg::Increment);
+/// assert_eq!(program.app().count, 3);
+/// block_on(program.settle());
+/// assert_eq!(program.view().render_to_string(), "<button>0</button>");
+/// ```
+pub struct TestProgram<APP>
+where
+    APP: Application,
+{
+    app: APP,
+    /// the view of the app after the last update
+    current_vdom: Node<APP::MSG>,
+    /// the commands returned by `init` and `update` which are not done yet
+    pending_cmds: FuturesUnordered<PendingCommand<APP::MSG>>,
+}
+
+impl<APP> TestProgram<APP>
+where
+    APP: Application,
+    APP::MSG: 'static,
+{
+    /// initialize the app and create its view,
+    /// the command returned by `init` is run when settled
+    pub fn new(mut app: APP) -> Self {
+        let cmd = app.init();
+        let current_vdom = app.view();
+        let mut program = Self {
+            app,
+            current_vdom,
+            pending_cmds: FuturesUnordered::new(),
+        };
+        program.queue_cmd(cmd);
+        program
+    }
+
+    /// return a reference to the app
+    pub fn app(&self) -> &APP {
+        &self.app
+    }
+
+    /// the view of the app after the last update
+    pub fn view(&self) -> &Node<APP::MSG> {
+        &self.current_vdom
+    }
+
+    /// update the app with the msg, then recreate the view.
+    /// The returned command is run when settled
+    pub fn dispatch(&mut self, msg: APP::MSG) {
+        let cmd = self.app.update(msg);
+        self.queue_cmd(cmd);
+        self.current_vdom = self.app.view();
+    }
+
+    /// dispatch the msgs in order
+    pub fn dispatch_multiple(&mut self, msgs: impl IntoIterator<Item = APP::MSG>) {
+        for msg in msgs {
+            self.dispatch(msg);
+        }
+    }
+
+    /// fire the event on the first element of the view which matches the css `selector`,
+    /// then dispatch the msgs of its event listeners. See [`Node::fire_event`]
+    pub fn fire_event(
+        &mut self,
+        selector: &str,
+        event: SyntheticEvent,
+    ) -> Result<(), FireEventError> {
+        let msgs = self.current_vdom.fire_event(selector, event)?;
+        self.dispatch_multiple(msgs);
+        Ok(())
+    }
+
+    /// returns true if there are commands which are not done yet
+    pub fn has_pending_cmds(&self) -> bool {
+        !self.pending_cmds.is_empty()
+    }
+
+    /// run the pending commands and dispatch their msgs, including the commands
+    /// returned by the updates of these msgs, until none of the pending commands can make progress.
+    /// The commands are polled again whenever they are woken, and the other tasks of the executor
+    /// are given a chance to run before giving up on them.
+    /// The commands which are still waiting, ie: on a timer, are kept pending
+    pub async fn settle(&mut self) {
+        let wake_flag = Arc::new(WakeFlag::default());
+        let waker = task::waker(Arc::clone(&wake_flag));
+        let mut yielded = false;
+        loop {
+            let polled = self
+                .pending_cmds
+                .poll_next_unpin(&mut Context::from_waker(&waker));
+            match polled {
+                Poll::Ready(Some(Some((msg, command)))) => {
+                    self.queue_command(command);
+                    self.dispatch(msg);
+                    yielded = false;
+                }
+                // the command is done
+                Poll::Ready(Some(None)) => yielded = false,
+                // there are no more pending commands
+                Poll::Ready(None) => break,
+                // a command is woken while it is polled, so it can make progress
+                Poll::Pending if wake_flag.take() => (),
+                // the other tasks may wake the commands, ie: by sending into a channel
+                Poll::Pending if !yielded => {
+                    yield_now().await;
+                    yielded = true;
+                }
+                // none of the commands is woken
+                Poll::Pending => break,
+            }
+        }
+    }
+
+    fn queue_cmd(&mut self, cmd: Cmd<APP::MSG>) {
+        for command in cmd.commands {
+            self.queue_command(command);
+        }
+    }
+
+    fn queue_command(&mut self, command: Command<APP::MSG>) {
+        self.pending_cmds.push(command.into_next());
+    }
+}
+
+/// records whether the pending commands are woken
+#[derive(Default)]
+struct WakeFlag(AtomicBool);
+
+impl WakeFlag {
+    /// return whether it is woken, resetting the flag
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+}
+
+impl ArcWake for WakeFlag {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// return to the executor once, so its other tasks can run
+async fn yield_now() {
+    let mut yielded = false;
+    future::poll_fn(|cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
@@ -2,7 +2,7 @@
 use crate::vdom;
 use crate::vdom::Leaf;
 pub use crate::vdom::{element, element_ns};
-use crate::vdom::{Attribute, Node};
+use crate::vdom::{Attribute, Namespace, Node, Tag};
 use std::borrow::Cow;
 pub use tags::{commons::*, self_closing::*, *};
 
@@ -16,6 +16,9 @@ pub mod units;
 #[cfg(feature = "with-dom")]
 pub use crate::dom::events;
 
+/// MathML namespace const, use this when creating a mathml element dynamically in the DOM
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
 /// A help function which render the view when the condition is met, otherwise
 /// just display a `span(vec![], vec![])`
 ///
@@ -58,6 +61,37 @@ where
     }
 }
 
+/// Creates a view which is only built by calling `view_fn` with `args` when it is needed.
+///
+/// When diffed against a lazy view created from the same `view_fn` with equal `args`,
+/// the view is not built again and the whole subtree is skipped from diffing.
+/// This is useful for large views that rarely change, such as the rows of a table.
+///
+/// Note: the `view_fn` is identified by its type, so it should not capture values which affect
+/// the view, pass them in the `args` instead.
+/// The key of a lazy view is read from its view, so lazy views in a keyed list are built
+/// when they are moved to a different position.
+/// # Examples
+/// ```rust
+/// use sauron::{*, html::lazy};
+///
+/// fn view_row(row: &(usize, String)) -> Node<()> {
+///     tr([], [td([], [text(row.0)]), td([], [text(&row.1)])])
+/// }
+///
+/// let old: Node<()> = lazy((1, "one".to_string()), view_row);
+/// let new: Node<()> = lazy((1, "one".to_string()), view_row);
+/// assert!(diff(&old, &new).is_empty());
+/// assert_eq!(new.render_to_string(), "<tr><td>1</td><td>one</td></tr>");
+/// ```
+pub fn lazy<ARGS, F, MSG>(args: ARGS, view_fn: F) -> Node<MSG>
+where
+    ARGS: PartialEq + 'static,
+    F: Fn(&ARGS) -> Node<MSG> + 'static,
+{
+    Node::Leaf(Leaf::LazyView(vdom::LazyView::new(args, view_fn)))
+}
+
 /// Creates an html element with the element tag name and namespace
 /// This is specifically used for creating svg element where a namespace is needed, otherwise the
 /// browser will not render it correctly.
@@ -66,12 +100,12 @@ where
 /// use sauron::{*,html::html_element};
 ///
 /// let html:Node<()> =
-///     html_element(Some("http://www.w3.org/2000/svg"),"svg", vec![width(200), height(200), xmlns("http://www.w3.org/2000/svg")], vec![], false);
+///     html_element(Some("http://www.w3.org/2000/svg".into()),"svg", vec![width(200), height(200), xmlns("http://www.w3.org/2000/svg")], vec![], false);
 /// assert_eq!(node!{<svg width=200 height=200 xmlns="http://www.w3.org/2000/svg"></svg>}, html);
 /// ```
 pub fn html_element<MSG>(
-    namespace: Option<&'static str>,
-    tag: &'static str,
+    namespace: Option<Namespace>,
+    tag: impl Into<Tag>,
     attrs: impl IntoIterator<Item = Attribute<MSG>>,
     children: impl IntoIterator<Item = Node<MSG>>,
     self_closing: bool,
@@ -157,6 +191,21 @@ pub fn symbol<MSG>(s: &str) -> Node<MSG> {
     Node::Leaf(Leaf::Symbol(s.into()))
 }
 
+/// Create a node from trusted html markup, which will be rendered as is without escaping.
+///
+/// Warning: Never pass user supplied content here, as it will not be escaped and
+/// could be used to inject scripts into the page. Use `text` instead for those.
+/// # Example
+/// ```rust
+/// use sauron::{*, html::trusted_html};
+///
+/// let node: Node<()> = div([], [trusted_html("<b>pre-rendered</b>")]);
+/// assert_eq!(node.render_to_string(), "<div><b>pre-rendered</b></div>");
+/// ```
+pub fn trusted_html<MSG>(html: impl Into<Cow<'static, str>>) -> Node<MSG> {
+    Node::Leaf(Leaf::TrustedHtml(html.into()))
+}
+
 fn escape_html_text(s: &str) -> String {
     s.chars()
         .map(|ch| match ch {
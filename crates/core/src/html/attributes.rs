@@ -164,7 +164,7 @@ pub fn attrs_flag<MSG>(
 /// assert_eq!(expected, html.render_to_string());
 /// ```
 pub fn maybe_attr<MSG>(
-    name: vdom::AttributeName,
+    name: impl Into<vdom::AttributeName>,
     value: Option<impl Into<Value>>,
 ) -> Attribute<MSG> {
     if let Some(value) = value {
@@ -241,7 +241,7 @@ pub fn focus<MSG>(is_focus: bool) -> Attribute<MSG> {
 ///
 /// let data_id: Attribute<()> = attr("data-id", 42);
 /// ```
-pub fn attr<MSG>(att: &'static str, v: impl Into<Value>) -> Attribute<MSG> {
+pub fn attr<MSG>(att: impl Into<vdom::AttributeName>, v: impl Into<Value>) -> Attribute<MSG> {
     vdom::attr(att, AttributeValue::from(v.into()))
 }
 
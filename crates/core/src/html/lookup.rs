@@ -6,6 +6,7 @@ use crate::{
             commons::HTML_TAGS, self_closing::HTML_SC_TAGS, HTML_TAGS_NON_COMMON,
             HTML_TAGS_WITH_MACRO_NON_COMMON,
         },
+        MATHML_NAMESPACE,
     },
     svg::{
         attributes::{SVG_ATTRS, SVG_ATTRS_SPECIAL, SVG_ATTRS_XLINK},
@@ -63,6 +64,7 @@ static ALL_ATTRS: Lazy<BTreeMap<&'static str, &'static str>> = Lazy::new(|| {
 /// mounted into the DOM
 ///
 /// Limitations: `script`, `style`,and `a` used inside svg will return `None`, as these are also valid html tags.
+/// Use [`element_namespace`] when the namespace of the parent element is known.
 pub fn tag_namespace(tag: &str) -> Option<&'static str> {
     let is_html = ALL_HTML_TAGS.contains(&tag);
     let is_svg = ALL_SVG_TAGS.contains(&tag);
@@ -81,6 +83,36 @@ pub fn tag_namespace(tag: &str) -> Option<&'static str> {
     }
 }
 
+/// Find the namespace of the element with this tag, given the namespace of its parent element.
+///
+/// `svg` and `math` start the svg and mathml namespace, and their descendants are in the same namespace,
+/// including `a`, `script`, `style` and `title`, unless they are inside an html integration point
+/// such as `foreignObject`, see [`children_namespace`].
+/// Without a parent namespace, this falls back to [`tag_namespace`].
+pub fn element_namespace(tag: &str, parent_namespace: Option<&str>) -> Option<&'static str> {
+    match (tag, parent_namespace) {
+        ("svg", _) => Some(SVG_NAMESPACE),
+        ("math", _) => Some(MATHML_NAMESPACE),
+        (_, Some(SVG_NAMESPACE)) => Some(SVG_NAMESPACE),
+        (_, Some(MATHML_NAMESPACE)) => Some(MATHML_NAMESPACE),
+        _ => tag_namespace(tag),
+    }
+}
+
+/// The namespace of the child elements of the element with this tag and namespace.
+///
+/// The children of `foreignObject`, `desc` and `title` in svg,
+/// and of the text elements `mi`, `mo`, `mn`, `ms` and `mtext` in mathml are html elements.
+pub fn children_namespace(tag: &str, namespace: Option<&str>) -> Option<&'static str> {
+    match namespace {
+        Some(SVG_NAMESPACE) if matches!(tag, "foreignObject" | "desc" | "title") => None,
+        Some(SVG_NAMESPACE) => Some(SVG_NAMESPACE),
+        Some(MATHML_NAMESPACE) if matches!(tag, "mi" | "mo" | "mn" | "ms" | "mtext") => None,
+        Some(MATHML_NAMESPACE) => Some(MATHML_NAMESPACE),
+        _ => None,
+    }
+}
+
 /// return the matching attribute
 pub fn match_attribute(att: &str) -> Option<&'static str> {
     ALL_ATTRS
@@ -23,9 +23,9 @@ pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
 /// ```
 ///
 pub fn svg_element<MSG>(
-    tag: &'static str,
+    tag: impl Into<vdom::Tag>,
     attrs: impl IntoIterator<Item = vdom::Attribute<MSG>>,
     children: impl IntoIterator<Item = vdom::Node<MSG>>,
 ) -> vdom::Node<MSG> {
-    crate::html::html_element(Some(SVG_NAMESPACE), tag, attrs, children, false)
+    crate::html::html_element(Some(SVG_NAMESPACE.into()), tag, attrs, children, false)
 }
@@ -5,7 +5,8 @@ use crate::vdom::{attr, attr_ns};
 pub use commons::*;
 pub use special::*;
 
-pub(crate) const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+/// xlink namespace const, used by the `xlink:` attributes such as `xlink:href`
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
 
 macro_rules! declare_xlink_attributes {
     ( $(
@@ -21,7 +22,7 @@ macro_rules! declare_xlink_attributes {
             pub fn $name<V, MSG>(v: V) -> crate::vdom::Attribute<MSG>
                 where V: Into<Value>,
                 {
-                    attr_ns(Some(XLINK_NAMESPACE), $attribute, AttributeValue::from(v.into()))
+                    attr_ns(Some(XLINK_NAMESPACE.into()), $attribute, AttributeValue::from(v.into()))
                 }
          )*
 
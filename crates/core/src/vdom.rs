@@ -10,14 +10,20 @@ pub use attribute::Attribute;
 pub use attribute::Callback;
 pub use attribute::GroupedAttributeValues;
 pub use element::Element;
+pub use lazy_view::LazyView;
 pub use leaf::Leaf;
+pub use resumable_diff::ResumableDiff;
+pub use selector::SelectorError;
 pub use templated_view::TemplatedView;
 
 mod attribute;
 mod element;
+mod lazy_view;
 mod leaf;
 mod map_msg;
 mod render;
+mod resumable_diff;
+mod selector;
 mod templated_view;
 
 pub use attribute::special::{
@@ -27,11 +33,22 @@ pub use attribute::special::{
 pub(crate) use attribute::special::{CHECKED, DISABLED, OPEN, VALUE};
 pub use attribute::{attr, attr_ns, AttributeName, AttributeValue, Namespace, Style, Tag, Value};
 pub use diff::{diff, diff_recursive};
+pub use diff_report::{diff_report, DiffReport};
 pub use node::{element, element_ns, fragment, leaf, node_list, Node};
+#[cfg(feature = "with-serde")]
+pub use patch::{
+    ListenerId, ListenerRegistry, SerializableAttribute, SerializableAttributeValue,
+    SerializableNode, SerializablePatch, SerializablePatchType,
+};
 pub use patch::{Patch, PatchType, TreePath};
+pub use render::{
+    AttributeOrder, FlushPoints, Newline, RenderChunks, RenderOptions, VoidElementStyle,
+    BOOLEAN_ATTRIBUTES,
+};
 
 pub mod diff;
 mod diff_lis;
+mod diff_report;
 mod node;
 pub mod patch;
 
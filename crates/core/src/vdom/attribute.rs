@@ -4,6 +4,7 @@ use crate::vdom::ComponentEventCallback;
 use crate::vdom::EventCallback;
 use derive_where::derive_where;
 use indexmap::IndexMap;
+use std::borrow::Cow;
 
 pub use attribute_value::AttributeValue;
 pub use callback::Callback;
@@ -17,13 +18,15 @@ mod style;
 mod value;
 
 /// The type of the Namspace
-pub type Namespace = &'static str;
+pub type Namespace = Cow<'static, str>;
 
-/// The type of the Tag
-pub type Tag = &'static str;
+/// The type of the Tag, which borrows the `&'static str` of the known tags
+/// and owns the names created at runtime such as the custom elements
+pub type Tag = Cow<'static, str>;
 
-/// The type of Attribute Name
-pub type AttributeName = &'static str;
+/// The type of Attribute Name, which borrows the `&'static str` of the known attributes
+/// and owns the names created at runtime such as the `data-*` attributes
+pub type AttributeName = Cow<'static, str>;
 
 /// These are the plain attributes of an element
 #[derive_where(Clone, Debug, PartialEq, Eq)]
@@ -55,11 +58,11 @@ impl<MSG> Attribute<MSG> {
     /// create a plain attribute with namespace
     pub fn new(
         namespace: Option<Namespace>,
-        name: AttributeName,
+        name: impl Into<AttributeName>,
         value: AttributeValue<MSG>,
     ) -> Self {
         Attribute {
-            name,
+            name: name.into(),
             value: vec![value],
             namespace,
         }
@@ -68,11 +71,11 @@ impl<MSG> Attribute<MSG> {
     /// create from multiple values
     pub fn with_multiple_values(
         namespace: Option<Namespace>,
-        name: AttributeName,
+        name: impl Into<AttributeName>,
         value: impl IntoIterator<Item = AttributeValue<MSG>>,
     ) -> Self {
         Attribute {
-            name,
+            name: name.into(),
             value: value.into_iter().collect(),
             namespace,
         }
@@ -161,8 +164,8 @@ impl<MSG> Attribute<MSG> {
                     merged.insert(
                         &att.name,
                         Attribute {
-                            namespace: att.namespace,
-                            name: att.name,
+                            namespace: att.namespace.clone(),
+                            name: att.name.clone(),
                             value: att.value.clone(),
                         },
                     );
@@ -197,7 +200,10 @@ impl<MSG> Attribute<MSG> {
 /// let class: Attribute<()> = attr("class", "container");
 /// ```
 #[inline]
-pub fn attr<MSG>(name: AttributeName, value: impl Into<AttributeValue<MSG>>) -> Attribute<MSG> {
+pub fn attr<MSG>(
+    name: impl Into<AttributeName>,
+    value: impl Into<AttributeValue<MSG>>,
+) -> Attribute<MSG> {
     attr_ns(None, name, value)
 }
 
@@ -206,12 +212,12 @@ pub fn attr<MSG>(name: AttributeName, value: impl Into<AttributeValue<MSG>>) ->
 /// ```rust
 /// use sauron::vdom::{Attribute,attr_ns};
 ///
-/// let href: Attribute<()> = attr_ns(Some("http://www.w3.org/1999/xlink"), "href", "cool-script.js");
+/// let href: Attribute<()> = attr_ns(Some("http://www.w3.org/1999/xlink".into()), "href", "cool-script.js");
 /// ```
 #[inline]
 pub fn attr_ns<MSG>(
     namespace: Option<Namespace>,
-    name: AttributeName,
+    name: impl Into<AttributeName>,
     value: impl Into<AttributeValue<MSG>>,
 ) -> Attribute<MSG> {
     Attribute::new(namespace, name, value.into())
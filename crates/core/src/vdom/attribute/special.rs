@@ -3,25 +3,26 @@
 //!
 use super::{attr, Attribute, Value};
 use crate::vdom::AttributeName;
+use std::borrow::Cow;
 
 /// Special Node attributes that are treated differently
 /// such as key and skip which both greatly affects the diffing algorithm
 
 /// NOTE: this is specific to sauron framework
 /// The key attribute
-pub static KEY: &AttributeName = &"key";
+pub static KEY: &AttributeName = &Cow::Borrowed("key");
 
 /// NOTE: this is specific to sauron framework
 /// The replace attribute
-pub static REPLACE: &AttributeName = &"replace";
+pub static REPLACE: &AttributeName = &Cow::Borrowed("replace");
 
 /// NOTE: this is specific to sauron framework
 /// The skip attribute
-pub static SKIP: &AttributeName = &"skip";
+pub static SKIP: &AttributeName = &Cow::Borrowed("skip");
 
 /// NOTE: this is specific to sauron framework
 /// The skip criteria attribute
-pub static SKIP_CRITERIA: &AttributeName = &"skip_criteria";
+pub static SKIP_CRITERIA: &AttributeName = &Cow::Borrowed("skip_criteria");
 
 ///
 /// NOTE: The following attributes have special behaviour in the dom, the framework
@@ -29,16 +30,16 @@ pub static SKIP_CRITERIA: &AttributeName = &"skip_criteria";
 ///
 /// the value attribute
 #[cfg(feature = "ensure-attr-set")]
-pub static VALUE: &AttributeName = &"value";
+pub static VALUE: &AttributeName = &Cow::Borrowed("value");
 /// the open attribute
 #[cfg(feature = "ensure-attr-set")]
-pub static OPEN: &AttributeName = &"open";
+pub static OPEN: &AttributeName = &Cow::Borrowed("open");
 /// the checked attribute
 #[cfg(feature = "ensure-attr-set")]
-pub static CHECKED: &AttributeName = &"checked";
+pub static CHECKED: &AttributeName = &Cow::Borrowed("checked");
 /// the disabled attribute
 #[cfg(feature = "ensure-attr-set")]
-pub static DISABLED: &AttributeName = &"disabled";
+pub static DISABLED: &AttributeName = &Cow::Borrowed("disabled");
 
 /// creates a key attribute using a formatter
 /// # Examples
@@ -63,13 +64,13 @@ pub fn key<V, MSG>(v: V) -> Attribute<MSG>
 where
     V: Into<Value>,
 {
-    attr(KEY, v)
+    attr(KEY.clone(), v)
 }
 
 /// if the value is true, then the diffing of this element
 /// and its descendants are skip entirely
 pub fn skip<MSG>(v: bool) -> Attribute<MSG> {
-    attr(SKIP, v)
+    attr(SKIP.clone(), v)
 }
 
 /// if the value of this attribute of the old element and the new element is the same
@@ -78,11 +79,11 @@ pub fn skip_criteria<V, MSG>(v: V) -> Attribute<MSG>
 where
     V: Into<Value>,
 {
-    attr(SKIP_CRITERIA, v.into())
+    attr(SKIP_CRITERIA.clone(), v.into())
 }
 
 /// if the value is true, then this node is made to replace the old
 /// node it matches
 pub fn replace<MSG>(v: bool) -> Attribute<MSG> {
-    attr(REPLACE, v)
+    attr(REPLACE.clone(), v)
 }
@@ -5,6 +5,7 @@ use std::fmt;
 /// css styles
 /// style can be converted into an attribute
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Style {
     /// style name such as border, width, etc
     pub name: Cow<'static, str>,
@@ -37,6 +38,8 @@ impl Style {
     }
 }
 
+impl Eq for Style {}
+
 impl fmt::Display for Style {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}:{}", self.name, self.value)
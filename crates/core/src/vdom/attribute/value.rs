@@ -6,6 +6,7 @@ use std::fmt;
 /// such as checked(bool), name(String), tab_index(i32)
 /// Note: memory size of Value is 32 bytes, in comparison String is 24 bytes
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     /// bool value
     Bool(bool),
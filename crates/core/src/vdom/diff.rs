@@ -1,10 +1,12 @@
 //! provides diffing algorithm which returns patches
 use super::{diff_lis, Attribute, Element, Node, Patch, TreePath};
-use super::{Tag, KEY, REPLACE, SKIP, SKIP_CRITERIA};
+use super::{AttributeName, KEY, REPLACE, SKIP, SKIP_CRITERIA};
 use crate::dom::skip_diff::SkipAttrs;
 use crate::dom::SkipPath;
 use crate::vdom::AttributeValue;
 use crate::vdom::Leaf;
+use crate::vdom::Style;
+use indexmap::{IndexMap, IndexSet};
 use std::{cmp, mem};
 
 #[cfg(feature = "use-skipdiff")]
@@ -110,11 +112,51 @@ fn should_replace<'a, MSG>(old_node: &'a Node<MSG>, new_node: &'a Node<MSG>) ->
     false
 }
 
+/// The child nodes which diffing is deferred, when the nodes are diffed one at a time
+/// by [`ResumableDiff`](super::ResumableDiff)
+#[derive(Debug)]
+pub(crate) struct Deferred {
+    /// the path of the node in the new tree, which child nodes are deferred
+    pub(crate) new_path: TreePath,
+    /// the path of the old child node and the path of the new child node
+    pub(crate) children: Vec<(SkipPath, TreePath)>,
+}
+
 /// diff the nodes recursively
 pub fn diff_recursive<'a, MSG>(
     old_node: &'a Node<MSG>,
     new_node: &'a Node<MSG>,
     path: &SkipPath,
+) -> Vec<Patch<'a, MSG>> {
+    diff_node(old_node, new_node, path, None)
+}
+
+/// diff the child nodes, or defer it to be diffed later if `deferred` is supplied
+pub(crate) fn diff_child<'a, MSG>(
+    old_child: &'a Node<MSG>,
+    new_child: &'a Node<MSG>,
+    path: &SkipPath,
+    new_index: usize,
+    deferred: Option<&mut Deferred>,
+) -> Vec<Patch<'a, MSG>> {
+    match deferred {
+        Some(deferred) => {
+            let new_path = deferred.new_path.traverse(new_index);
+            deferred.children.push((path.clone(), new_path));
+            vec![]
+        }
+        None => diff_recursive(old_child, new_child, path),
+    }
+}
+
+/// diff the nodes, the child nodes are diffed recursively unless `deferred` is supplied.
+/// The views of components and lazy views are diffed right away,
+/// since they are located at the same path as the node.
+pub(crate) fn diff_node<'a, MSG>(
+    old_node: &'a Node<MSG>,
+    new_node: &'a Node<MSG>,
+    path: &SkipPath,
+    deferred: Option<&mut Deferred>,
 ) -> Vec<Patch<'a, MSG>> {
     if let Some(skip_diff) = path.skip_diff.as_ref() {
         if USE_SKIP_DIFF && skip_diff.shall_skip_node() {
@@ -122,6 +164,16 @@ pub fn diff_recursive<'a, MSG>(
         }
     }
 
+    // lazy views built from the same view function and arguments are skipped from diffing,
+    // this is checked before anything else, so the new view is not built
+    if let (Node::Leaf(Leaf::LazyView(old_view)), Node::Leaf(Leaf::LazyView(new_view))) =
+        (old_node, new_node)
+    {
+        if new_view.reuse_view(old_view) {
+            return vec![];
+        }
+    }
+
     let skip = |old_node: &'a Node<MSG>, new_node: &'a Node<MSG>| {
         let new_skip_criteria = new_node.attribute_value(SKIP_CRITERIA);
         let old_skip_criteria = old_node.attribute_value(SKIP_CRITERIA);
@@ -158,6 +210,7 @@ pub fn diff_recursive<'a, MSG>(
             match (old_leaf, new_leaf) {
                 (Leaf::Text(_), Leaf::Text(_))
                 | (Leaf::Symbol(_), Leaf::Symbol(_))
+                | (Leaf::TrustedHtml(_), Leaf::TrustedHtml(_))
                 | (Leaf::Comment(_), Leaf::Comment(_))
                 | (Leaf::DocType(_), Leaf::DocType(_)) => {
                     if old_leaf != new_leaf {
@@ -165,15 +218,15 @@ pub fn diff_recursive<'a, MSG>(
                         patches.push(patch);
                     }
                 }
-                (Leaf::Fragment(old_nodes), Leaf::Fragment(new_nodes)) => {
-                    // we back track since Fragment is not a real node, but it would still
-                    // be traversed from the prior call
-                    let patch = diff_nodes(None, old_nodes, new_nodes, &path.backtrack());
+                // the children of fragments and node lists are located in the fragment,
+                // the same way they are tracked in the DOM.
+                // Node lists here are at the root node or inside a fragment,
+                // since node lists are unrolled into their parent element
+                (Leaf::Fragment(old_nodes), Leaf::Fragment(new_nodes))
+                | (Leaf::NodeList(old_nodes), Leaf::NodeList(new_nodes)) => {
+                    let patch = diff_nodes(None, old_nodes, new_nodes, path, deferred);
                     patches.extend(patch);
                 }
-                (Leaf::NodeList(_old_elements), Leaf::NodeList(_new_elements)) => {
-                    panic!("Node list must have already unrolled when creating an element");
-                }
                 (Leaf::StatelessComponent(old_comp), Leaf::StatelessComponent(new_comp)) => {
                     let new_path = SkipPath {
                         path: path.path.clone(),
@@ -191,26 +244,32 @@ pub fn diff_recursive<'a, MSG>(
                         !new_real_view.is_template(),
                         "new comp view should not be a template"
                     );
-                    let patch = diff_recursive(old_real_view, new_real_view, &new_path);
+                    let patch = diff_node(old_real_view, new_real_view, &new_path, deferred);
                     patches.extend(patch);
                 }
                 (Leaf::StatefulComponent(old_comp), Leaf::StatefulComponent(new_comp)) => {
                     let attr_patches = create_attribute_patches(
-                        &"component",
+                        "component",
                         &old_comp.attrs,
                         &new_comp.attrs,
                         path,
+                        false,
                     );
                     if !attr_patches.is_empty() {
                         log::info!("stateful component attr_patches: {attr_patches:#?}");
                     }
                     patches.extend(attr_patches);
-                    let patch = diff_nodes(None, &old_comp.children, &new_comp.children, path);
+                    let patch =
+                        diff_nodes(None, &old_comp.children, &new_comp.children, path, deferred);
                     if !patch.is_empty() {
                         log::info!("stateful component patch: {patch:#?}");
                     }
                     patches.extend(patch);
                 }
+                (Leaf::LazyView(old_view), Leaf::LazyView(new_view)) => {
+                    let patch = diff_node(old_view.view(), new_view.view(), path, deferred);
+                    patches.extend(patch);
+                }
                 (Leaf::TemplatedView(_old_view), _) => {
                     unreachable!("templated view should not be diffed..")
                 }
@@ -237,6 +296,7 @@ pub fn diff_recursive<'a, MSG>(
                     old_element.attributes(),
                     new_element.attributes(),
                     path,
+                    true,
                 );
                 patches.extend(attr_patches);
             }
@@ -246,6 +306,7 @@ pub fn diff_recursive<'a, MSG>(
                 old_element.children(),
                 new_element.children(),
                 path,
+                deferred,
             );
 
             patches.extend(more_patches);
@@ -259,22 +320,38 @@ pub fn diff_recursive<'a, MSG>(
 }
 
 fn diff_nodes<'a, MSG>(
-    old_tag: Option<&'a Tag>,
+    old_tag: Option<&'a str>,
     old_children: &'a [Node<MSG>],
     new_children: &'a [Node<MSG>],
     path: &SkipPath,
+    deferred: Option<&mut Deferred>,
 ) -> Vec<Patch<'a, MSG>> {
+    reuse_lazy_views(old_children, new_children);
     let diff_as_keyed = is_any_keyed(old_children) || is_any_keyed(new_children);
 
     if diff_as_keyed {
-        let keyed_patches = diff_lis::diff_keyed_nodes(old_tag, old_children, new_children, path);
+        let keyed_patches =
+            diff_lis::diff_keyed_nodes(old_tag, old_children, new_children, path, deferred);
         keyed_patches
     } else {
-        let non_keyed_patches = diff_non_keyed_nodes(old_tag, old_children, new_children, path);
+        let non_keyed_patches =
+            diff_non_keyed_nodes(old_tag, old_children, new_children, path, deferred);
         non_keyed_patches
     }
 }
 
+/// reuse the views of the lazy views which are unchanged at the same position,
+/// so they are not built when looking for the keys of the new children
+fn reuse_lazy_views<MSG>(old_children: &[Node<MSG>], new_children: &[Node<MSG>]) {
+    for (old_child, new_child) in old_children.iter().zip(new_children.iter()) {
+        if let (Node::Leaf(Leaf::LazyView(old_view)), Node::Leaf(Leaf::LazyView(new_view))) =
+            (old_child, new_child)
+        {
+            new_view.reuse_view(old_view);
+        }
+    }
+}
+
 /// In diffing non_keyed nodes,
 ///  we reuse existing DOM elements as much as possible
 ///
@@ -286,10 +363,11 @@ fn diff_nodes<'a, MSG>(
 ///  If there are more children in the new_element than the old_element
 ///  it will be all appended in the old_element.
 fn diff_non_keyed_nodes<'a, MSG>(
-    old_element_tag: Option<&'a Tag>,
+    old_element_tag: Option<&'a str>,
     old_children: &'a [Node<MSG>],
     new_children: &'a [Node<MSG>],
     path: &SkipPath,
+    mut deferred: Option<&mut Deferred>,
 ) -> Vec<Patch<'a, MSG>> {
     let mut patches = vec![];
     let old_child_count = old_children.len();
@@ -308,7 +386,13 @@ fn diff_non_keyed_nodes<'a, MSG>(
         let old_child = &old_children.get(index).expect("No old_node child node");
         let new_child = &new_children.get(index).expect("No new child node");
 
-        let more_patches = diff_recursive(old_child, new_child, &child_path);
+        let more_patches = diff_child(
+            old_child,
+            new_child,
+            &child_path,
+            index,
+            deferred.as_deref_mut(),
+        );
         patches.extend(more_patches);
     }
 
@@ -338,16 +422,20 @@ fn diff_non_keyed_nodes<'a, MSG>(
     patches
 }
 
+///
+/// If `update_partially` is true, the changes in the style attribute are diffed per style
+/// property and the changes in the class attribute are diffed per class name.
 ///
 /// Note: The performance bottlenecks
 ///     - allocating new vec
 ///     - merging attributes of the same name
 #[allow(clippy::type_complexity)]
 fn create_attribute_patches<'a, MSG>(
-    old_tag: &'a Tag,
+    old_tag: &'a str,
     old_attributes: &'a [Attribute<MSG>],
     new_attributes: &'a [Attribute<MSG>],
     path: &SkipPath,
+    update_partially: bool,
 ) -> Vec<Patch<'a, MSG>> {
     let skip_indices = if let Some(skip_diff) = &path.skip_diff {
         if let SkipAttrs::Indices(skip_indices) = &skip_diff.skip_attrs {
@@ -370,6 +458,7 @@ fn create_attribute_patches<'a, MSG>(
 
     let mut add_attributes: Vec<&Attribute<MSG>> = vec![];
     let mut remove_attributes: Vec<&Attribute<MSG>> = vec![];
+    let mut partial_patches = vec![];
 
     let new_attributes_grouped = Element::group_indexed_attributes_per_name(new_attributes);
     let old_attributes_grouped = Element::group_indexed_attributes_per_name(old_attributes);
@@ -402,8 +491,23 @@ fn create_attribute_patches<'a, MSG>(
             if USE_SKIP_DIFF && has_skip_indices && is_subset_of(&old_indices, &skip_indices) {
                 //
             } else if old_attr_values != new_attr_values {
-                for (_i, new_att) in new_attrs {
-                    add_attributes.push(new_att);
+                let partial_patch = if update_partially {
+                    create_partial_attribute_patch(
+                        old_tag,
+                        new_attr_name,
+                        &old_attr_values,
+                        &new_attr_values,
+                        path,
+                    )
+                } else {
+                    None
+                };
+                if let Some(partial_patch) = partial_patch {
+                    partial_patches.extend(partial_patch);
+                } else {
+                    for (_i, new_att) in new_attrs {
+                        add_attributes.push(new_att);
+                    }
                 }
             }
         } else {
@@ -422,7 +526,19 @@ fn create_attribute_patches<'a, MSG>(
         if USE_SKIP_DIFF && has_skip_indices && is_subset_of(&old_indices, &skip_indices) {
             //
         } else if !new_attributes_grouped.contains_key(old_attr_name) {
-            remove_attributes.extend(old_attrs.clone());
+            // only the old class names are removed, so the class names which are added
+            // to the element outside of the view are kept
+            let partial_patch = if update_partially && old_attr_name == "class" {
+                let old_attr_values: Vec<_> = old_attrs.iter().map(|attr| &attr.value).collect();
+                create_partial_attribute_patch(old_tag, old_attr_name, &old_attr_values, &[], path)
+            } else {
+                None
+            };
+            if let Some(partial_patch) = partial_patch {
+                partial_patches.extend(partial_patch);
+            } else {
+                remove_attributes.extend(old_attrs.clone());
+            }
         }
     }
 
@@ -440,9 +556,103 @@ fn create_attribute_patches<'a, MSG>(
             remove_attributes,
         ));
     }
+    patches.extend(partial_patches);
     patches
 }
 
+/// create a patch which only updates the changed style properties or class names of the
+/// style and class attribute.
+/// This returns `Some(None)` when there is no effective change,
+/// and returns None if the attribute can not be updated partially
+fn create_partial_attribute_patch<'a, MSG>(
+    old_tag: &'a str,
+    attr_name: &AttributeName,
+    old_values: &[&'a Vec<AttributeValue<MSG>>],
+    new_values: &[&'a Vec<AttributeValue<MSG>>],
+    path: &SkipPath,
+) -> Option<Option<Patch<'a, MSG>>> {
+    match attr_name.as_ref() {
+        "style" => {
+            let (set, remove) = diff_style_properties(old_values, new_values)?;
+            let has_changes = !set.is_empty() || !remove.is_empty();
+            Some(has_changes.then(|| Patch::update_styles(old_tag, path.path.clone(), set, remove)))
+        }
+        "class" => {
+            let (add, remove) = diff_class_names(old_values, new_values)?;
+            let has_changes = !add.is_empty() || !remove.is_empty();
+            Some(
+                has_changes.then(|| Patch::update_classes(old_tag, path.path.clone(), add, remove)),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// return the style properties that are set and removed,
+/// this returns None if the attribute values are not all styles
+#[allow(clippy::type_complexity)]
+fn diff_style_properties<'a, MSG>(
+    old_values: &[&'a Vec<AttributeValue<MSG>>],
+    new_values: &[&'a Vec<AttributeValue<MSG>>],
+) -> Option<(Vec<&'a Style>, Vec<&'a Style>)> {
+    let old_styles = style_properties(old_values)?;
+    let new_styles = style_properties(new_values)?;
+    let set = new_styles
+        .values()
+        .filter(|style| {
+            old_styles
+                .get(style.name.as_ref())
+                .map(|old_style| old_style.value != style.value)
+                .unwrap_or(true)
+        })
+        .copied()
+        .collect();
+    let remove = old_styles
+        .values()
+        .filter(|style| !new_styles.contains_key(style.name.as_ref()))
+        .copied()
+        .collect();
+    Some((set, remove))
+}
+
+/// the style properties keyed by their name, where the later style overrides the earlier one.
+/// This returns None if there is a value which is not a style
+fn style_properties<'a, MSG>(
+    values: &[&'a Vec<AttributeValue<MSG>>],
+) -> Option<IndexMap<&'a str, &'a Style>> {
+    let mut styles = IndexMap::new();
+    for value in values.iter().flat_map(|values| values.iter()) {
+        for style in value.as_style()? {
+            styles.insert(style.name.as_ref(), style);
+        }
+    }
+    Some(styles)
+}
+
+/// return the class names that are added and removed,
+/// this returns None if the attribute values are not all simple values
+fn diff_class_names<MSG>(
+    old_values: &[&Vec<AttributeValue<MSG>>],
+    new_values: &[&Vec<AttributeValue<MSG>>],
+) -> Option<(Vec<String>, Vec<String>)> {
+    let old_names = class_names(old_values)?;
+    let new_names = class_names(new_values)?;
+    let add = new_names.difference(&old_names).cloned().collect();
+    let remove = old_names.difference(&new_names).cloned().collect();
+    Some((add, remove))
+}
+
+/// the unique class names in the class attribute values.
+/// This returns None if there is a value which is not a simple value
+fn class_names<MSG>(values: &[&Vec<AttributeValue<MSG>>]) -> Option<IndexSet<String>> {
+    let mut names = IndexSet::new();
+    for value in values.iter().flat_map(|values| values.iter()) {
+        let value = value.get_simple()?.to_string();
+        names.extend(value.split_whitespace().map(|name| name.to_string()));
+    }
+    Some(names)
+}
+
 /// returns true if all the elements in subset is in big_set
 /// This also returns the indices of big_set that are not found in the subset
 fn is_subset_of<T: PartialEq>(subset: &[T], big_set: &[T]) -> bool {
@@ -1,22 +1,29 @@
 //! diff with longest increasing subsequence
 
-use super::diff::diff_recursive;
-use super::{AttributeValue, Tag, KEY};
+use super::diff::{diff_child, Deferred};
 use super::{Node, Patch};
+use super::{TreePath, KEY};
 use crate::dom::SkipPath;
-use indexmap::IndexMap;
+use std::ops::Range;
 
 pub fn diff_keyed_nodes<'a, MSG>(
-    old_tag: Option<&'a Tag>,
+    old_tag: Option<&'a str>,
     old_children: &'a [Node<MSG>],
     new_children: &'a [Node<MSG>],
     path: &SkipPath,
+    mut deferred: Option<&mut Deferred>,
 ) -> Vec<Patch<'a, MSG>> {
     if !old_children.is_empty() && new_children.is_empty() {
         return vec![Patch::clear_children(old_tag, path.path.clone())];
     }
 
-    let (patches, offsets) = diff_keyed_ends(old_tag, old_children, new_children, path);
+    let (patches, offsets) = diff_keyed_ends(
+        old_tag,
+        old_children,
+        new_children,
+        path,
+        deferred.as_deref_mut(),
+    );
 
     let (left_offset, right_offset) = match offsets {
         Some(offsets) => offsets,
@@ -88,17 +95,18 @@ pub fn diff_keyed_nodes<'a, MSG>(
             all_patches.push(patch);
         }
     } else {
-        let patches = diff_keyed_middle(old_middle, new_middle, left_offset, path);
+        let patches = diff_keyed_middle(old_middle, new_middle, left_offset, path, deferred);
         all_patches.extend(patches);
     }
     all_patches
 }
 
 fn diff_keyed_ends<'a, MSG>(
-    old_tag: Option<&'a Tag>,
+    old_tag: Option<&'a str>,
     old_children: &'a [Node<MSG>],
     new_children: &'a [Node<MSG>],
     path: &SkipPath,
+    mut deferred: Option<&mut Deferred>,
 ) -> (Vec<Patch<'a, MSG>>, Option<(usize, usize)>) {
     // keep track of the old index that has been matched already
     let mut old_index_matched = vec![];
@@ -112,7 +120,7 @@ fn diff_keyed_ends<'a, MSG>(
         }
         let child_path = path.traverse(index);
         // diff the children and add to patches
-        let patches = diff_recursive(old, new, &child_path);
+        let patches = diff_child(old, new, &child_path, index, deferred.as_deref_mut());
         all_patches.extend(patches);
         old_index_matched.push(index);
         left_offset += 1;
@@ -158,7 +166,8 @@ fn diff_keyed_ends<'a, MSG>(
             break;
         }
         let child_path = path.traverse(old_index);
-        let patches = diff_recursive(old, new, &child_path);
+        let new_index = new_children.len() - index - 1;
+        let patches = diff_child(old, new, &child_path, new_index, deferred.as_deref_mut());
         all_patches.extend(patches);
         right_offset += 1;
     }
@@ -166,106 +175,90 @@ fn diff_keyed_ends<'a, MSG>(
     (all_patches, Some((left_offset, right_offset)))
 }
 
+/// A run of consecutive new children which are either moved from the old children or created
+enum Run<'a, MSG> {
+    /// the paths of the old children which are moved
+    Moved(Vec<TreePath>),
+    /// the new children which are created
+    Created(Vec<&'a Node<MSG>>),
+}
+
 /// derived from dioxus core/src/diff.rs
+///
+/// The old children that are in the longest increasing subsequence stay in place,
+/// the rest of the new children are moved or created in between them.
+/// All the patch paths refers to the location of the nodes in the old children,
+/// since the target nodes are looked up before any of the patches is applied.
 fn diff_keyed_middle<'a, MSG>(
     old_children: &'a [Node<MSG>],
     new_children: &'a [Node<MSG>],
     left_offset: usize,
     path: &SkipPath,
+    mut deferred: Option<&mut Deferred>,
 ) -> Vec<Patch<'a, MSG>> {
     let mut all_patches = vec![];
+    let old_path = |old_index: usize| path.traverse(left_offset + old_index);
 
     let old_children_keys: Vec<_> = old_children
         .iter()
         .map(|c| c.attribute_value(KEY))
         .collect();
 
-    let new_children_keys: Vec<_> = new_children
-        .iter()
-        .map(|c| c.attribute_value(KEY))
-        .collect();
-
-    debug_assert_ne!(new_children_keys.first(), old_children_keys.first());
-    debug_assert_ne!(new_children_keys.last(), old_children_keys.last());
-
-    // make a map of old_index -> old_key
-    let old_key_to_old_index: IndexMap<usize, &Vec<&AttributeValue<MSG>>> =
-        IndexMap::from_iter(old_children_keys.iter().enumerate().filter_map(
-            |(old_index, old_key)| old_key.as_ref().map(|old_key| (old_index, old_key)),
-        ));
-
-    let mut shared_keys: Vec<Vec<&AttributeValue<MSG>>> = vec![];
-
-    // map each new key to the old key, carrying over the old index
-    let new_index_to_old_index: Vec<usize> = new_children
+    // map each new child to the old child with the same key,
+    // an old child is only reused once even if there are duplicate keys
+    let mut old_reused = vec![false; old_children.len()];
+    let new_index_to_old_index: Vec<Option<usize>> = new_children
         .iter()
         .map(|new| {
-            if let Some(new_key) = new.attribute_value(KEY) {
-                let index = old_key_to_old_index
+            let new_key = new.attribute_value(KEY)?;
+            let old_index =
+                old_children_keys
                     .iter()
-                    .find_map(|(old_index, old_key)| {
-                        if new_key == **old_key {
-                            Some(*old_index)
-                        } else {
-                            None
-                        }
-                    });
-                if let Some(index) = index {
-                    shared_keys.push(new_key);
-                    index
-                } else {
-                    u32::MAX as usize
-                }
-            } else {
-                u32::MAX as usize
-            }
+                    .enumerate()
+                    .position(|(old_index, old_key)| {
+                        !old_reused[old_index] && old_key.as_ref() == Some(&new_key)
+                    })?;
+            old_reused[old_index] = true;
+            Some(old_index)
         })
         .collect();
 
     // if none of the old keys are reused by the new children,
     // then we remove all the remaining old children and create the new children afresh.
-    if shared_keys.is_empty() && old_children.first().is_some() {
+    if !old_reused.contains(&true) {
         // skip the first one, so we can use it as our foothold for inserting the new children
-        for (index, old) in old_children.iter().skip(1).enumerate() {
-            let patch = Patch::remove_node(old.tag(), path.traverse(index + 1).path);
-            all_patches.push(patch);
+        for (index, old) in old_children.iter().enumerate().skip(1) {
+            all_patches.push(Patch::remove_node(old.tag(), old_path(index).path));
         }
-
-        let first = 0;
-
-        let patch = Patch::replace_node(
-            old_children[left_offset + first].tag(),
-            path.traverse(left_offset + first).path,
-            new_children.iter().collect::<Vec<_>>(),
-        );
-        all_patches.push(patch);
+        all_patches.push(Patch::replace_node(
+            old_children[0].tag(),
+            old_path(0).path,
+            new_children.iter(),
+        ));
         return all_patches;
     }
 
-    // remove any old children that are not shared
+    // remove any old children that are not reused, including the nodes that has no key
     for (index, old_child) in old_children.iter().enumerate() {
-        if let Some(old_key) = old_child.attribute_value(KEY) {
-            if !shared_keys.contains(&old_key) {
-                let patch =
-                    Patch::remove_node(old_child.tag(), path.traverse(left_offset + index).path);
-                all_patches.push(patch);
-            }
-        } else {
-            // also remove the node that has no key
-            let patch =
-                Patch::remove_node(old_child.tag(), path.traverse(left_offset + index).path);
-            all_patches.push(patch);
+        if !old_reused[index] {
+            all_patches.push(Patch::remove_node(old_child.tag(), old_path(index).path));
         }
     }
 
-    // Compute the LIS of this list
-    let mut lis_sequence = Vec::with_capacity(new_index_to_old_index.len());
+    // Compute the LIS of the old indices of the reused children
+    let shared: Vec<(usize, usize)> = new_index_to_old_index
+        .iter()
+        .enumerate()
+        .filter_map(|(new_index, old_index)| old_index.map(|old_index| (new_index, old_index)))
+        .collect();
+    let shared_old_indices: Vec<usize> = shared.iter().map(|(_, old_index)| *old_index).collect();
 
-    let mut predecessors = vec![0; new_index_to_old_index.len()];
-    let mut starts = vec![0; new_index_to_old_index.len()];
+    let mut lis_sequence = Vec::with_capacity(shared.len());
+    let mut predecessors = vec![0; shared.len()];
+    let mut starts = vec![0; shared.len()];
 
     longest_increasing_subsequence::lis_with(
-        &new_index_to_old_index,
+        &shared_old_indices,
         &mut lis_sequence,
         |a, b| a < b,
         &mut predecessors,
@@ -275,122 +268,113 @@ fn diff_keyed_middle<'a, MSG>(
     // the list_sequence came out from high to low, so we just reverse it back to arrange from low to high
     lis_sequence.reverse();
 
-    // if a new node gets u32 max and is at the end, then it might be part of our LIS (because u32 max is a valid LIS)
-    if lis_sequence.last().map(|f| new_index_to_old_index[*f]) == Some(u32::MAX as usize) {
-        lis_sequence.pop();
-    }
+    // the (new_index, old_index) of the children which stay in place
+    let stable: Vec<(usize, usize)> = lis_sequence.iter().map(|i| shared[*i]).collect();
 
-    for idx in lis_sequence.iter() {
-        let patches = diff_recursive(
-            &old_children[new_index_to_old_index[*idx]],
-            &new_children[*idx],
-            path,
+    for (new_index, old_index) in stable.iter() {
+        let patches = diff_child(
+            &old_children[*old_index],
+            &new_children[*new_index],
+            &old_path(*old_index),
+            left_offset + new_index,
+            deferred.as_deref_mut(),
         );
         all_patches.extend(patches);
     }
 
-    // add mount instruction for the first items not covered by the lis
-    let mut move_after_nodes = vec![];
-    let last = *lis_sequence.last().unwrap();
-    if last < (new_children.len() - 1) {
-        let mut new_nodes = vec![];
-        let mut node_paths = vec![];
-        for (idx, new_node) in new_children[(last + 1)..].iter().enumerate() {
-            let new_idx = idx + last + 1;
-            let old_index = new_index_to_old_index[new_idx];
-            if old_index == u32::MAX as usize {
-                new_nodes.push(new_node);
-            } else {
-                let patches = diff_recursive(&old_children[old_index], new_node, path);
-                all_patches.extend(patches);
-
-                node_paths.push(path.traverse(left_offset + old_index).path);
-            }
-        }
-        if !node_paths.is_empty() {
-            let patch = Patch::move_after_node(
-                old_children[left_offset + last].tag(),
-                path.traverse(left_offset + last).path, //target element
-                node_paths,
-            );
-            move_after_nodes.push(patch);
-        }
-        let old_index = new_index_to_old_index[last];
-        let tag = old_children[old_index].tag();
-        if !new_nodes.is_empty() {
-            let patch = Patch::insert_after_node(
-                tag,
-                path.traverse(left_offset + old_index).path,
-                new_nodes,
-            );
-            all_patches.push(patch);
-        }
+    // the children before the first stable child are placed before the first old child,
+    // or before the first stable child if the first old child is removed.
+    // The children after each stable child are placed after it.
+    let (first_new_index, first_old_index) = stable[0];
+    let leading_old_index = if old_reused[0] { 0 } else { first_old_index };
+    let tag = old_children[leading_old_index].tag();
+    let anchor_path = old_path(leading_old_index).path;
+    let (runs, patches) = runs_of_new_children(
+        old_children,
+        new_children,
+        &new_index_to_old_index,
+        0..first_new_index,
+        left_offset,
+        path,
+        deferred.as_deref_mut(),
+    );
+    all_patches.extend(patches);
+    for run in runs {
+        all_patches.push(match run {
+            Run::Moved(paths) => Patch::move_before_node(tag, anchor_path.clone(), paths),
+            Run::Created(nodes) => Patch::insert_before_node(tag, anchor_path.clone(), nodes),
+        });
     }
 
-    // for each spacing, generate a mount instruction
-    let mut lis_iter = lis_sequence.iter().rev();
-    let last = *lis_iter.next().unwrap();
-    let lowest = lis_iter.min();
-    if let Some(next) = lowest {
-        let mut new_nodes = vec![];
-        for (idx, new_node) in new_children[(next + 1)..last].iter().enumerate() {
-            let new_idx = idx + next + 1;
-            let old_index = new_index_to_old_index[new_idx];
-            if old_index == u32::MAX as usize {
-                new_nodes.push(new_node)
-            } else {
-                let patches = diff_recursive(&old_children[old_index], new_node, path);
-                all_patches.extend(patches);
-            }
-        }
-
-        if !new_nodes.is_empty() {
-            let tag = old_children[last].tag();
-            let patch =
-                Patch::insert_before_node(tag, path.traverse(left_offset + last).path, new_nodes);
-            all_patches.push(patch);
+    for (i, (new_index, old_index)) in stable.iter().enumerate() {
+        let end = stable
+            .get(i + 1)
+            .map(|(next_new_index, _)| *next_new_index)
+            .unwrap_or(new_children.len());
+        let tag = old_children[*old_index].tag();
+        let anchor_path = old_path(*old_index).path;
+        let (runs, patches) = runs_of_new_children(
+            old_children,
+            new_children,
+            &new_index_to_old_index,
+            (new_index + 1)..end,
+            left_offset,
+            path,
+            deferred.as_deref_mut(),
+        );
+        all_patches.extend(patches);
+        // inserting after the same anchor places the nodes right after it,
+        // so the runs are placed from the last
+        for run in runs.into_iter().rev() {
+            all_patches.push(match run {
+                Run::Moved(paths) => Patch::move_after_node(tag, anchor_path.clone(), paths),
+                Run::Created(nodes) => Patch::insert_after_node(tag, anchor_path.clone(), nodes),
+            });
         }
     }
+    all_patches
+}
 
-    // add mount instruction for the last items not covered by the list
-    let mut move_before_nodes = vec![];
-    let first_lis = *lis_sequence.first().unwrap();
-    if first_lis > 0 {
-        let mut new_nodes = vec![];
-        let mut node_paths = vec![];
-        for (idx, new_node) in new_children[..first_lis].iter().enumerate() {
-            let old_index = new_index_to_old_index[idx];
-            if old_index == u32::MAX as usize {
-                new_nodes.push(new_node);
-            } else {
-                let patches = diff_recursive(&old_children[old_index], new_node, path);
+/// group the new children in `range` into runs of moved and created nodes,
+/// the moved nodes are also diffed with their old node, which patches are returned with the runs
+fn runs_of_new_children<'a, MSG>(
+    old_children: &'a [Node<MSG>],
+    new_children: &'a [Node<MSG>],
+    new_index_to_old_index: &[Option<usize>],
+    range: Range<usize>,
+    left_offset: usize,
+    path: &SkipPath,
+    mut deferred: Option<&mut Deferred>,
+) -> (Vec<Run<'a, MSG>>, Vec<Patch<'a, MSG>>) {
+    let mut runs: Vec<Run<MSG>> = vec![];
+    let mut all_patches = vec![];
+    for new_index in range {
+        let new_child = &new_children[new_index];
+        match new_index_to_old_index[new_index] {
+            Some(old_index) => {
+                let old_path = path.traverse(left_offset + old_index);
+                let patches = diff_child(
+                    &old_children[old_index],
+                    new_child,
+                    &old_path,
+                    left_offset + new_index,
+                    deferred.as_deref_mut(),
+                );
                 all_patches.extend(patches);
-                node_paths.push(path.traverse(left_offset + old_index).path);
+                if let Some(Run::Moved(paths)) = runs.last_mut() {
+                    paths.push(old_path.path);
+                } else {
+                    runs.push(Run::Moved(vec![old_path.path]));
+                }
+            }
+            None => {
+                if let Some(Run::Created(nodes)) = runs.last_mut() {
+                    nodes.push(new_child);
+                } else {
+                    runs.push(Run::Created(vec![new_child]));
+                }
             }
-        }
-        if !node_paths.is_empty() {
-            let first = 0;
-            let patch = Patch::move_before_node(
-                old_children[left_offset + first].tag(),
-                path.traverse(left_offset + first).path, //target_element
-                node_paths,                              //to be move after the target_element
-            );
-            move_before_nodes.push(patch);
-        }
-
-        if !new_nodes.is_empty() {
-            let old_index = new_index_to_old_index[first_lis];
-            let tag = old_children[old_index].tag();
-            let patch = Patch::insert_before_node(
-                tag,
-                path.traverse(left_offset + old_index).path,
-                new_nodes,
-            );
-            all_patches.push(patch);
         }
     }
-
-    all_patches.extend(move_before_nodes);
-    all_patches.extend(move_after_nodes);
-    all_patches
+    (runs, all_patches)
 }
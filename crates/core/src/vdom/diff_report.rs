@@ -0,0 +1,338 @@
+//! A human readable report of the differences in between 2 virtual dom trees,
+//! useful in test assertions and when debugging the patches.
+use super::{Element, Leaf, Node, RenderOptions, TreePath, KEY, REPLACE};
+use std::fmt;
+
+/// ANSI escape codes used in the colored report
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// Return a report of the differences of the `old` and `new` node, in a unified diff format.
+///
+/// Each node is written on its own line, indented by its depth in the tree.
+/// Lines prefixed with `-` are removed from the old node, lines prefixed with `+` are added
+/// in the new node, and lines prefixed with `~` are keyed nodes which are moved.
+/// Moved nodes are annotated with their old and new path.
+/// The child nodes of a node which has no changes are collapsed into `...`.
+///
+/// # Example
+/// ```rust
+/// use sauron::{*, vdom::diff_report};
+///
+/// let old: Node<()> = ul([], [li([key(1)], [text("one")]), li([key(2)], [text("two")])]);
+/// let new: Node<()> = ul([], [li([key(2)], [text("two")]), li([key(1)], [text("uno")])]);
+///
+/// let report = diff_report(&old, &new);
+/// assert!(report.has_changes());
+/// assert_eq!(
+///     report.to_string(),
+///     [
+///         "  <ul>",
+///         "~   <li key=\"2\"> ...  (moved from [1] to [0])",
+///         "    <li key=\"1\">",
+///         "-     \"one\"",
+///         "+     \"uno\"",
+///     ]
+///     .join("\n")
+/// );
+/// ```
+pub fn diff_report<MSG>(old: &Node<MSG>, new: &Node<MSG>) -> DiffReport {
+    let mut lines = vec![];
+    report_node(
+        &mut lines,
+        old,
+        new,
+        &TreePath::root(),
+        &TreePath::root(),
+        false,
+        0,
+    );
+    DiffReport { lines }
+}
+
+/// The report of the differences of 2 nodes, created with [`diff_report`].
+///
+/// This is displayed without colors, use [`DiffReport::to_colored_string`]
+/// to highlight the changes with ANSI colors when written into a terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffReport {
+    lines: Vec<Line>,
+}
+
+/// A line in the report
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Line {
+    change: Change,
+    /// the depth of the node in the tree
+    depth: usize,
+    /// the node without its children
+    content: String,
+    /// the old and new path of a moved node
+    moved: Option<(TreePath, TreePath)>,
+}
+
+/// How the node in a line is changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    /// the node is the same in the old and new tree
+    Same,
+    /// the node is the same, but moved to a different location
+    Moved,
+    /// the node is removed from the old tree
+    Removed,
+    /// the node is added in the new tree
+    Added,
+}
+
+impl DiffReport {
+    /// returns true if the old and new node are different
+    pub fn has_changes(&self) -> bool {
+        self.lines.iter().any(|line| line.change != Change::Same)
+    }
+
+    /// the report where the removed lines are in red, the added lines are in green
+    /// and the moved lines are in yellow
+    pub fn to_colored_string(&self) -> String {
+        let mut buffer = String::new();
+        self.write(&mut buffer, true).expect("must write");
+        buffer
+    }
+
+    fn write(&self, buffer: &mut dyn fmt::Write, colored: bool) -> fmt::Result {
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                writeln!(buffer)?;
+            }
+            let (marker, color) = match line.change {
+                Change::Same => (' ', None),
+                Change::Moved => ('~', Some(YELLOW)),
+                Change::Removed => ('-', Some(RED)),
+                Change::Added => ('+', Some(GREEN)),
+            };
+            let color = color.filter(|_| colored);
+            if let Some(color) = color {
+                write!(buffer, "{color}")?;
+            }
+            write!(
+                buffer,
+                "{marker} {:indent$}{}",
+                "",
+                line.content,
+                indent = line.depth * 2
+            )?;
+            if let Some((old_path, new_path)) = &line.moved {
+                write!(
+                    buffer,
+                    "  (moved from {:?} to {:?})",
+                    old_path.path, new_path.path
+                )?;
+            }
+            if color.is_some() {
+                write!(buffer, "{RESET}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+/// report the old and new node which are diffed against each other
+fn report_node<MSG>(
+    lines: &mut Vec<Line>,
+    old: &Node<MSG>,
+    new: &Node<MSG>,
+    old_path: &TreePath,
+    new_path: &TreePath,
+    is_moved: bool,
+    depth: usize,
+) {
+    let old = old.unwrap_view();
+    let new = new.unwrap_view();
+    if is_replaced(old, new) {
+        report_subtree(lines, old, Change::Removed, depth);
+        report_subtree(lines, new, Change::Added, depth);
+        return;
+    }
+    let old_content = content(old);
+    let new_content = content(new);
+    let moved = is_moved.then(|| (old_path.clone(), new_path.clone()));
+    let start = lines.len();
+    if old_content == new_content {
+        let change = if is_moved {
+            Change::Moved
+        } else {
+            Change::Same
+        };
+        lines.push(Line {
+            change,
+            depth,
+            content: new_content,
+            moved,
+        });
+    } else {
+        lines.push(Line {
+            change: Change::Removed,
+            depth,
+            content: old_content,
+            moved: None,
+        });
+        lines.push(Line {
+            change: Change::Added,
+            depth,
+            content: new_content,
+            moved,
+        });
+    }
+    report_children(
+        lines,
+        old.children(),
+        new.children(),
+        old_path,
+        new_path,
+        depth + 1,
+    );
+    // collapse the child nodes if this node is the same or only moved
+    let has_changes = lines[start + 1..]
+        .iter()
+        .any(|line| line.change != Change::Same);
+    if !has_changes && lines.len() > start + 1 {
+        lines.truncate(start + 1);
+        lines[start].content.push_str(" ...");
+    }
+}
+
+/// report the child nodes, where the keyed nodes are matched by their key
+/// and the other nodes are matched by their index, the same way they are diffed.
+fn report_children<MSG>(
+    lines: &mut Vec<Line>,
+    old_children: &[Node<MSG>],
+    new_children: &[Node<MSG>],
+    old_path: &TreePath,
+    new_path: &TreePath,
+    depth: usize,
+) {
+    let is_keyed = old_children
+        .iter()
+        .chain(new_children.iter())
+        .any(|child| child.attribute_value(KEY).is_some());
+
+    // the old index of each new child, an old child is only matched once
+    let mut old_matched = vec![false; old_children.len()];
+    let new_index_to_old_index: Vec<Option<usize>> = new_children
+        .iter()
+        .enumerate()
+        .map(|(new_index, new_child)| {
+            let old_index = if is_keyed {
+                let new_key = new_child.attribute_value(KEY)?;
+                old_children.iter().enumerate().position(|(i, old_child)| {
+                    !old_matched[i] && old_child.attribute_value(KEY).as_ref() == Some(&new_key)
+                })?
+            } else {
+                (new_index < old_children.len()).then_some(new_index)?
+            };
+            old_matched[old_index] = true;
+            Some(old_index)
+        })
+        .collect();
+
+    // the matched nodes which are not in the longest increasing subsequence are moved
+    let matched_old_indices: Vec<usize> =
+        new_index_to_old_index.iter().flatten().copied().collect();
+    let stable_old_indices: Vec<usize> = longest_increasing_subsequence::lis(&matched_old_indices)
+        .into_iter()
+        .map(|i| matched_old_indices[i])
+        .collect();
+
+    // the removed old nodes are reported before the new node which comes after them
+    let mut next_old_index = 0;
+    let mut report_removed_until = |lines: &mut Vec<Line>, end: usize| {
+        while next_old_index < end {
+            if !old_matched[next_old_index] {
+                report_subtree(lines, &old_children[next_old_index], Change::Removed, depth);
+            }
+            next_old_index += 1;
+        }
+    };
+
+    for (new_index, new_child) in new_children.iter().enumerate() {
+        match new_index_to_old_index[new_index] {
+            Some(old_index) => {
+                let is_moved = !stable_old_indices.contains(&old_index);
+                if !is_moved {
+                    report_removed_until(lines, old_index);
+                }
+                report_node(
+                    lines,
+                    &old_children[old_index],
+                    new_child,
+                    &old_path.traverse(old_index),
+                    &new_path.traverse(new_index),
+                    is_moved,
+                    depth,
+                );
+            }
+            None => report_subtree(lines, new_child, Change::Added, depth),
+        }
+    }
+    report_removed_until(lines, old_children.len());
+}
+
+/// report the node and all of its child nodes with the same change
+fn report_subtree<MSG>(lines: &mut Vec<Line>, node: &Node<MSG>, change: Change, depth: usize) {
+    let node = node.unwrap_view();
+    lines.push(Line {
+        change,
+        depth,
+        content: content(node),
+        moved: None,
+    });
+    for child in node.children() {
+        report_subtree(lines, child, change, depth + 1);
+    }
+}
+
+/// returns true if the old node is replaced with the new node instead of being diffed
+fn is_replaced<MSG>(old: &Node<MSG>, new: &Node<MSG>) -> bool {
+    if std::mem::discriminant(old) != std::mem::discriminant(new) {
+        return true;
+    }
+    if let (Node::Leaf(old_leaf), Node::Leaf(new_leaf)) = (old, new) {
+        if std::mem::discriminant(old_leaf) != std::mem::discriminant(new_leaf) {
+            return true;
+        }
+    }
+    let is_explicit_replace = new
+        .first_value(REPLACE)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let old_tag = old.element_ref().map(Element::tag);
+    let new_tag = new.element_ref().map(Element::tag);
+    is_explicit_replace
+        || old_tag != new_tag
+        || old.attribute_value(KEY) != new.attribute_value(KEY)
+}
+
+/// the node written without its children
+fn content<MSG>(node: &Node<MSG>) -> String {
+    match node {
+        Node::Element(elm) => {
+            let mut buffer = String::new();
+            elm.render_start_tag(&mut buffer, &RenderOptions::default())
+                .expect("must render");
+            buffer
+        }
+        Node::Leaf(Leaf::Text(text)) => format!("{:?}", text.as_ref()),
+        Node::Leaf(Leaf::NodeList(_)) => "node list".to_string(),
+        Node::Leaf(Leaf::Fragment(_)) => "fragment".to_string(),
+        #[cfg(feature = "with-dom")]
+        Node::Leaf(Leaf::StatefulComponent(_)) => "stateful component".to_string(),
+        node => node.render_to_string(),
+    }
+}
@@ -1,8 +1,8 @@
 use super::attribute::{AttributeName, Namespace, Tag};
+use super::node::unroll_node_lists;
 use super::{Attribute, Node};
 
 use crate::vdom::AttributeValue;
-use crate::vdom::Leaf;
 use crate::vdom::Value;
 use derive_where::derive_where;
 use indexmap::IndexMap;
@@ -39,24 +39,16 @@ impl<MSG> Element<MSG> {
     /// create a new instance of an element
     pub fn new(
         namespace: Option<Namespace>,
-        tag: Tag,
+        tag: impl Into<Tag>,
         attrs: impl IntoIterator<Item = Attribute<MSG>>,
         children: impl IntoIterator<Item = Node<MSG>>,
         self_closing: bool,
     ) -> Self {
-        //unroll the nodelist
-        let children = children
-            .into_iter()
-            .flat_map(|child| match child {
-                Node::Leaf(Leaf::NodeList(node_list)) => node_list,
-                _ => vec![child],
-            })
-            .collect();
         Self {
             namespace,
-            tag,
+            tag: tag.into(),
             attrs: attrs.into_iter().collect(),
-            children,
+            children: unroll_node_lists(children),
             self_closing,
         }
     }
@@ -126,7 +118,7 @@ impl<MSG> Element<MSG> {
     }
 
     /// return the tag of this element
-    pub fn tag(&self) -> &Tag {
+    pub fn tag(&self) -> &str {
         &self.tag
     }
 
@@ -136,13 +128,13 @@ impl<MSG> Element<MSG> {
     }
 
     /// change the tag of this element
-    pub fn set_tag(&mut self, tag: Tag) {
-        self.tag = tag;
+    pub fn set_tag(&mut self, tag: impl Into<Tag>) {
+        self.tag = tag.into();
     }
 
     /// remove the attributes with this key
-    pub fn remove_attribute(&mut self, name: &AttributeName) {
-        self.attrs.retain(|att| att.name != *name)
+    pub fn remove_attribute(&mut self, name: &str) {
+        self.attrs.retain(|att| att.name != name)
     }
 
     /// remove the existing values of this attribute
@@ -167,11 +159,11 @@ impl<MSG> Element<MSG> {
     }
 
     /// return all the attribute values which the name &AttributeName
-    pub fn attribute_value(&self, name: &AttributeName) -> Option<Vec<&AttributeValue<MSG>>> {
+    pub fn attribute_value(&self, name: &str) -> Option<Vec<&AttributeValue<MSG>>> {
         let result: Vec<&AttributeValue<MSG>> = self
             .attrs
             .iter()
-            .filter(|att| att.name == *name)
+            .filter(|att| att.name == name)
             .flat_map(|att| att.value())
             .collect();
 
@@ -183,7 +175,7 @@ impl<MSG> Element<MSG> {
     }
 
     /// get the first value of the attribute which has the name `att_name` of this element
-    pub fn first_value(&self, att_name: &AttributeName) -> Option<&Value> {
+    pub fn first_value(&self, att_name: &str) -> Option<&Value> {
         self.attribute_value(att_name)
             .and_then(|att_values| att_values.first().and_then(|v| v.get_simple()))
     }
@@ -0,0 +1,133 @@
+use crate::vdom::Node;
+use std::any::{Any, TypeId};
+use std::cell::OnceCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// build the view from the arguments of the lazy view
+type ViewFn<MSG> = Rc<dyn Fn(&dyn Any) -> Node<MSG>>;
+
+/// A view which is only built when it is needed.
+///
+/// The view is built from the `view_fn` and its `args`, when diffed against a lazy view
+/// which has the same `view_fn` and equal `args`, the view of the old lazy view is reused
+/// and the whole subtree is skipped from diffing.
+pub struct LazyView<MSG> {
+    /// the type of the view function, used to identify the lazy views built from the same function
+    type_id: TypeId,
+    /// the arguments passed to the view function
+    args: Rc<dyn Any>,
+    /// compare the arguments of 2 lazy views with the same `type_id`
+    args_eq: fn(&dyn Any, &dyn Any) -> bool,
+    /// build the view from the arguments
+    view_fn: ViewFn<MSG>,
+    /// the view, built once when it is first needed
+    view: OnceCell<Rc<Node<MSG>>>,
+}
+
+impl<MSG> LazyView<MSG> {
+    /// create a lazy view which calls `view_fn` with `args` when the view is needed
+    pub fn new<ARGS, F>(args: ARGS, view_fn: F) -> Self
+    where
+        ARGS: PartialEq + 'static,
+        F: Fn(&ARGS) -> Node<MSG> + 'static,
+    {
+        Self {
+            type_id: TypeId::of::<F>(),
+            args: Rc::new(args),
+            args_eq: |this, other| match (this.downcast_ref::<ARGS>(), other.downcast_ref::<ARGS>())
+            {
+                (Some(this), Some(other)) => this == other,
+                _ => false,
+            },
+            view_fn: Rc::new(move |args| {
+                view_fn(
+                    args.downcast_ref::<ARGS>()
+                        .expect("must be the args of this view function"),
+                )
+            }),
+            view: OnceCell::new(),
+        }
+    }
+
+    /// return the view, building it if it is not yet built
+    pub fn view(&self) -> &Node<MSG> {
+        self.view
+            .get_or_init(|| Rc::new((self.view_fn)(self.args.as_ref())))
+    }
+
+    /// return a mutable reference to the view, building it if it is not yet built
+    pub fn view_mut(&mut self) -> &mut Node<MSG> {
+        self.view();
+        Rc::make_mut(self.view.get_mut().expect("must be built"))
+    }
+
+    /// returns true if the view is already built
+    pub fn is_built(&self) -> bool {
+        self.view.get().is_some()
+    }
+
+    /// returns true if both lazy views are built from the same view function with equal arguments,
+    /// in which case they have the same view
+    pub fn is_same(&self, other: &Self) -> bool {
+        self.type_id == other.type_id && (self.args_eq)(self.args.as_ref(), other.args.as_ref())
+    }
+
+    /// take the view of `old` if both are the same lazy view, without building the view again.
+    /// Returns true if the view is reused.
+    pub(crate) fn reuse_view(&self, old: &Self) -> bool {
+        if self.is_same(old) {
+            if !self.is_built() {
+                old.view();
+                let old_view = old.view.get().expect("must be built");
+                let _ = self.view.set(Rc::clone(old_view));
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// map the msg of the view of this lazy view, the view is mapped when it is built
+    pub fn map_msg<F, MSG2>(self, cb: F) -> LazyView<MSG2>
+    where
+        F: Fn(MSG) -> MSG2 + Clone + 'static,
+        MSG2: 'static,
+        MSG: 'static,
+    {
+        let view_fn = self.view_fn;
+        LazyView {
+            type_id: self.type_id,
+            args: self.args,
+            args_eq: self.args_eq,
+            view_fn: Rc::new(move |args| view_fn(args).map_msg(cb.clone())),
+            view: OnceCell::new(),
+        }
+    }
+}
+
+impl<MSG> Clone for LazyView<MSG> {
+    fn clone(&self) -> Self {
+        Self {
+            type_id: self.type_id,
+            args: Rc::clone(&self.args),
+            args_eq: self.args_eq,
+            view_fn: Rc::clone(&self.view_fn),
+            view: self.view.clone(),
+        }
+    }
+}
+
+impl<MSG> PartialEq for LazyView<MSG> {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_same(other)
+    }
+}
+
+impl<MSG> fmt::Debug for LazyView<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyView")
+            .field("view", &self.view.get())
+            .finish()
+    }
+}
@@ -2,8 +2,8 @@
 use crate::dom::StatefulModel;
 use crate::dom::StatelessModel;
 use crate::vdom::Attribute;
-use crate::vdom::AttributeName;
 use crate::vdom::AttributeValue;
+use crate::vdom::LazyView;
 use crate::vdom::Node;
 use crate::vdom::TemplatedView;
 use derive_where::derive_where;
@@ -16,6 +16,9 @@ pub enum Leaf<MSG> {
     Text(Cow<'static, str>),
     /// Html entities such as &nbsp; &gt;
     Symbol(Cow<'static, str>),
+    /// Trusted html markup which is rendered as is, without escaping.
+    /// Only use this for markup that doesn't come from user input.
+    TrustedHtml(Cow<'static, str>),
     /// A comment node
     Comment(Cow<'static, str>),
     /// doctype: html, math, svg
@@ -33,6 +36,9 @@ pub enum Leaf<MSG> {
     StatelessComponent(StatelessModel<MSG>),
     /// a view where a template and skip diff is provided
     TemplatedView(TemplatedView<MSG>),
+    /// a view which is only built when needed, and is skipped from diffing when its arguments
+    /// didn't change
+    LazyView(LazyView<MSG>),
 }
 
 impl<MSG> PartialEq for Leaf<MSG> {
@@ -40,6 +46,7 @@ impl<MSG> PartialEq for Leaf<MSG> {
         match (self, other) {
             (Self::Text(v), Self::Text(o)) => v == o,
             (Self::Symbol(v), Self::Symbol(o)) => v == o,
+            (Self::TrustedHtml(v), Self::TrustedHtml(o)) => v == o,
             (Self::Comment(v), Self::Comment(o)) => v == o,
             (Self::DocType(v), Self::DocType(o)) => v == o,
             (Self::NodeList(v), Self::NodeList(o)) => v == o,
@@ -47,6 +54,7 @@ impl<MSG> PartialEq for Leaf<MSG> {
             #[cfg(feature = "with-dom")]
             (Self::StatefulComponent(v), Self::StatefulComponent(o)) => v == o,
             (Self::StatelessComponent(v), Self::StatelessComponent(o)) => v == o,
+            (Self::LazyView(v), Self::LazyView(o)) => v == o,
             _ => false,
         }
     }
@@ -69,10 +77,11 @@ impl<MSG> Leaf<MSG> {
     }
 
     /// return the attribute value of this leaf
-    pub fn attribute_value(&self, name: &AttributeName) -> Option<Vec<&AttributeValue<MSG>>> {
+    pub fn attribute_value(&self, name: &str) -> Option<Vec<&AttributeValue<MSG>>> {
         match self {
             Self::StatelessComponent(comp) => comp.attribute_value(name),
             Self::TemplatedView(templated_view) => templated_view.view.attribute_value(name),
+            Self::LazyView(lazy_view) => lazy_view.view().attribute_value(name),
             _ => None,
         }
     }
@@ -83,6 +92,7 @@ impl<MSG> Leaf<MSG> {
         match self {
             Self::StatelessComponent(comp) => comp.attributes(),
             Self::TemplatedView(templated_view) => templated_view.view.attributes(),
+            Self::LazyView(lazy_view) => lazy_view.view().attributes(),
             _ => None,
         }
     }
@@ -98,6 +98,7 @@ impl<MSG> Leaf<MSG> {
         match self {
             Self::Text(v) => Leaf::Text(v),
             Self::Symbol(v) => Leaf::Symbol(v),
+            Self::TrustedHtml(v) => Leaf::TrustedHtml(v),
             Self::Comment(v) => Leaf::Comment(v),
             Self::DocType(v) => Leaf::DocType(v),
             Self::Fragment(nodes) => Leaf::Fragment(
@@ -115,6 +116,7 @@ impl<MSG> Leaf<MSG> {
             Self::StatefulComponent(v) => Leaf::StatefulComponent(v.map_msg(cb)),
             Self::StatelessComponent(v) => Leaf::StatelessComponent(v.map_msg(cb)),
             Self::TemplatedView(v) => Leaf::TemplatedView(v.map_msg(cb)),
+            Self::LazyView(v) => Leaf::LazyView(v.map_msg(cb)),
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::{AttributeName, Namespace, Tag};
+use super::{Namespace, Tag};
 use crate::dom::SkipDiff;
 use crate::vdom::Attribute;
 use crate::vdom::AttributeValue;
@@ -15,11 +15,11 @@ use std::fmt::{Debug, Formatter};
 ///
 /// Much of the types are Generics
 ///
-/// Namespace - is the type for the namespace, this will be &'static str when used in html based virtual dom implementation
-/// Tag - is the type for the element tag, this will be &'static str when used in html based virtual
-/// dom impmenentation
-/// AttributeName - is the type for the attribute name, this will be &'static str when used in html based
-/// virtual dom implementation
+/// Namespace - is the type for the namespace, this is a `Cow<'static, str>` which borrows the static namespaces
+/// Tag - is the type for the element tag, this is a `Cow<'static, str>` which borrows the static html tags
+/// and owns the tags created at runtime
+/// AttributeName - is the type for the attribute name, this is a `Cow<'static, str>` which borrows the static
+/// html attributes and owns the attribute names created at runtime
 /// AttributeValue - is the type for the value of the attribute, this will be String, f64, or just another
 /// generics that suits the implementing library which used mt-dom for just dom-diffing purposes
 #[derive_where(Clone, Debug, PartialEq, Eq)]
@@ -183,19 +183,20 @@ impl<MSG> Node<MSG> {
 
     /// returns the tag of this node if it is an element
     /// otherwise None if it is a text node
-    pub fn tag(&self) -> Option<&Tag> {
+    pub fn tag(&self) -> Option<&str> {
         if let Some(e) = self.element_ref() {
-            Some(&e.tag)
+            Some(e.tag())
         } else {
             None
         }
     }
 
-    /// return the children of this node if it is an element
-    /// returns None if it is a text node
+    /// return the children of this node if it is an element, a node list or a fragment
+    /// returns an empty slice if it is a text node
     pub fn children(&self) -> &[Node<MSG>] {
         match self {
             Self::Element(elm) => elm.children(),
+            Self::Leaf(Leaf::NodeList(nodes)) | Self::Leaf(Leaf::Fragment(nodes)) => nodes,
             Self::Leaf(Leaf::StatefulComponent(comp)) => &comp.children,
             _ => &[],
         }
@@ -288,7 +289,7 @@ impl<MSG> Node<MSG> {
     }
 
     /// return the attribute values of this node which match the attribute name `name`
-    pub fn attribute_value(&self, name: &AttributeName) -> Option<Vec<&AttributeValue<MSG>>> {
+    pub fn attribute_value(&self, name: &str) -> Option<Vec<&AttributeValue<MSG>>> {
         match self {
             Self::Element(elm) => elm.attribute_value(name),
             Self::Leaf(leaf) => leaf.attribute_value(name),
@@ -296,7 +297,7 @@ impl<MSG> Node<MSG> {
     }
 
     /// get the first value of the attribute which has the name `att_name` of this node
-    pub fn first_value(&self, att_name: &AttributeName) -> Option<&Value> {
+    pub fn first_value(&self, att_name: &str) -> Option<&Value> {
         self.attribute_value(att_name)
             .and_then(|att_values| att_values.first().and_then(|v| v.get_simple()))
     }
@@ -328,6 +329,30 @@ impl<MSG> Node<MSG> {
     pub fn is_template(&self) -> bool {
         matches!(self, Self::Leaf(Leaf::TemplatedView(_)))
     }
+
+    /// the view of components, templated views and lazy views, which is where their
+    /// child nodes are located with the same path. Other nodes return themselves.
+    pub(crate) fn unwrap_view(&self) -> &Node<MSG> {
+        match self {
+            Self::Leaf(Leaf::StatelessComponent(comp)) => comp.view.unwrap_view(),
+            Self::Leaf(Leaf::TemplatedView(view)) => view.view.unwrap_view(),
+            Self::Leaf(Leaf::LazyView(view)) => view.view().unwrap_view(),
+            _ => self,
+        }
+    }
+
+    /// find the node at this path, the same way the patch paths are located,
+    /// where the child nodes of components and lazy views are the child nodes of their view
+    pub(crate) fn find_view_node(&self, path: &[usize]) -> Option<&Node<MSG>> {
+        match path.split_first() {
+            Some((index, rest)) => self
+                .unwrap_view()
+                .children()
+                .get(*index)?
+                .find_view_node(rest),
+            None => Some(self),
+        }
+    }
 }
 
 /// create a virtual node with tag, attrs and children
@@ -343,7 +368,7 @@ impl<MSG> Node<MSG> {
 /// ```
 #[inline]
 pub fn element<MSG>(
-    tag: Tag,
+    tag: impl Into<Tag>,
     attrs: impl IntoIterator<Item = Attribute<MSG>>,
     children: impl IntoIterator<Item = Node<MSG>>,
 ) -> Node<MSG> {
@@ -356,7 +381,7 @@ pub fn element<MSG>(
 /// use sauron::{Node, vdom::element_ns,attr};
 ///
 /// let svg: Node<()> = element_ns(
-///         Some("http://www.w3.org/2000/svg"),
+///         Some("http://www.w3.org/2000/svg".into()),
 ///          "svg",
 ///          vec![attr("width","400"), attr("height","400")],
 ///          vec![],
@@ -365,7 +390,7 @@ pub fn element<MSG>(
 /// ```
 pub fn element_ns<MSG>(
     namespace: Option<Namespace>,
-    tag: Tag,
+    tag: impl Into<Tag>,
     attrs: impl IntoIterator<Item = Attribute<MSG>>,
     children: impl IntoIterator<Item = Node<MSG>>,
     self_closing: bool,
@@ -378,12 +403,26 @@ pub fn leaf<MSG>(leaf: impl Into<Leaf<MSG>>) -> Node<MSG> {
     Node::Leaf(leaf.into())
 }
 
-/// create a node list
+/// create a node list, the node lists in `nodes` are unrolled into this node list
 pub fn node_list<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Node<MSG> {
-    Node::Leaf(Leaf::NodeList(nodes.into_iter().collect()))
+    Node::Leaf(Leaf::NodeList(unroll_node_lists(nodes)))
 }
 
-/// create fragment node
+/// create fragment node, the node lists in `nodes` are unrolled into this fragment
 pub fn fragment<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Node<MSG> {
-    Node::Leaf(Leaf::Fragment(nodes.into_iter().collect()))
+    Node::Leaf(Leaf::Fragment(unroll_node_lists(nodes)))
+}
+
+/// unroll the node lists, such that the nodes of the node lists are placed together with the
+/// rest of the nodes.
+/// This allows the nodes in the node lists to be diffed together with their siblings,
+/// ie: keyed nodes created in a loop.
+pub(crate) fn unroll_node_lists<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Vec<Node<MSG>> {
+    nodes
+        .into_iter()
+        .flat_map(|node| match node {
+            Node::Leaf(Leaf::NodeList(node_list)) => node_list,
+            _ => vec![node],
+        })
+        .collect()
 }
@@ -1,13 +1,20 @@
 //! patch module
 
-use super::Tag;
-use super::{Attribute, Node};
+use super::{Attribute, Node, Style};
 use std::borrow::Cow;
 
 use derive_where::derive_where;
 
+#[cfg(feature = "with-serde")]
+pub use serializable_patch::{
+    ListenerId, ListenerRegistry, SerializableAttribute, SerializableAttributeValue,
+    SerializableNode, SerializablePatch, SerializablePatchType,
+};
 pub use tree_path::TreePath;
 
+mod apply;
+#[cfg(feature = "with-serde")]
+mod serializable_patch;
 mod tree_path;
 
 /// A Patch encodes an operation that modifies a real DOM element or native UI element
@@ -63,7 +70,7 @@ mod tree_path;
 #[derive_where(Clone, Debug, PartialEq, Eq)]
 pub struct Patch<'a, MSG> {
     /// the tag of the node at patch_path
-    pub tag: Option<&'a Tag>,
+    pub tag: Option<&'a str>,
     /// the path to traverse to get to the target element
     pub patch_path: TreePath,
     /// the type of patch we are going to apply
@@ -125,6 +132,22 @@ pub enum PatchType<'a, MSG> {
         /// attributes that are to be removed from this target node
         attrs: Vec<&'a Attribute<MSG>>,
     },
+    /// Update only the style properties that are changed in the target element,
+    /// instead of replacing the whole style attribute
+    UpdateStyles {
+        /// the style properties that are added or changed
+        set: Vec<&'a Style>,
+        /// the style properties that are removed
+        remove: Vec<&'a Style>,
+    },
+    /// Add and remove only the class names that are changed in the target element,
+    /// instead of replacing the whole class attribute
+    UpdateClasses {
+        /// the class names to be added
+        add: Vec<String>,
+        /// the class names to be removed
+        remove: Vec<String>,
+    },
 }
 
 impl<'a, MSG> Patch<'a, MSG> {
@@ -143,13 +166,13 @@ impl<'a, MSG> Patch<'a, MSG> {
     }
 
     /// return the tag of this patch
-    pub fn tag(&self) -> Option<&Tag> {
+    pub fn tag(&self) -> Option<&str> {
         self.tag
     }
 
     /// create an InsertBeforeNode patch
     pub fn insert_before_node(
-        tag: Option<&'a Tag>,
+        tag: Option<&'a str>,
         patch_path: TreePath,
         nodes: impl IntoIterator<Item = &'a Node<MSG>>,
     ) -> Patch<'a, MSG> {
@@ -164,7 +187,7 @@ impl<'a, MSG> Patch<'a, MSG> {
 
     /// create an InsertAfterNode patch
     pub fn insert_after_node(
-        tag: Option<&'a Tag>,
+        tag: Option<&'a str>,
         patch_path: TreePath,
         nodes: Vec<&'a Node<MSG>>,
     ) -> Patch<'a, MSG> {
@@ -177,7 +200,7 @@ impl<'a, MSG> Patch<'a, MSG> {
 
     /// create a patch where we add children to the target node
     pub fn append_children(
-        tag: Option<&'a Tag>,
+        tag: Option<&'a str>,
         patch_path: TreePath,
         children: Vec<&'a Node<MSG>>,
     ) -> Patch<'a, MSG> {
@@ -190,7 +213,7 @@ impl<'a, MSG> Patch<'a, MSG> {
 
     /// create a patch where the target element that can be traverse
     /// using the patch path will be remove
-    pub fn remove_node(tag: Option<&'a Tag>, patch_path: TreePath) -> Patch<'a, MSG> {
+    pub fn remove_node(tag: Option<&'a str>, patch_path: TreePath) -> Patch<'a, MSG> {
         Patch {
             tag,
             patch_path,
@@ -199,7 +222,7 @@ impl<'a, MSG> Patch<'a, MSG> {
     }
 
     /// create a patch where the target element has to clear its children nodes
-    pub fn clear_children(tag: Option<&'a Tag>, patch_path: TreePath) -> Patch<'a, MSG> {
+    pub fn clear_children(tag: Option<&'a str>, patch_path: TreePath) -> Patch<'a, MSG> {
         Patch {
             tag,
             patch_path,
@@ -210,7 +233,7 @@ impl<'a, MSG> Patch<'a, MSG> {
     /// remove the nodes pointed at the `nodes_path` and insert them before the target element
     /// pointed at patch_path
     pub fn move_before_node(
-        tag: Option<&'a Tag>,
+        tag: Option<&'a str>,
         patch_path: TreePath,
         nodes_path: impl IntoIterator<Item = TreePath>,
     ) -> Patch<'a, MSG> {
@@ -226,7 +249,7 @@ impl<'a, MSG> Patch<'a, MSG> {
     /// remove the nodes pointed at the `nodes_path` and insert them after the target element
     /// pointed at patch_path
     pub fn move_after_node(
-        tag: Option<&'a Tag>,
+        tag: Option<&'a str>,
         patch_path: TreePath,
         nodes_path: impl IntoIterator<Item = TreePath>,
     ) -> Patch<'a, MSG> {
@@ -242,7 +265,7 @@ impl<'a, MSG> Patch<'a, MSG> {
     /// create a patch where a node is replaced by the `replacement` node.
     /// The target node to be replace is traverse using the `patch_path`
     pub fn replace_node(
-        tag: Option<&'a Tag>,
+        tag: Option<&'a str>,
         patch_path: TreePath,
         replacement: impl IntoIterator<Item = &'a Node<MSG>>,
     ) -> Patch<'a, MSG> {
@@ -257,7 +280,7 @@ impl<'a, MSG> Patch<'a, MSG> {
 
     /// create a patch where a new attribute is added to the target element
     pub fn add_attributes(
-        tag: &'a Tag,
+        tag: &'a str,
         patch_path: TreePath,
         attrs: impl IntoIterator<Item = &'a Attribute<MSG>>,
     ) -> Patch<'a, MSG> {
@@ -273,7 +296,7 @@ impl<'a, MSG> Patch<'a, MSG> {
     /// create patch where it remove attributes of the target element that can be traversed by the
     /// patch_path.
     pub fn remove_attributes(
-        tag: &'a Tag,
+        tag: &'a str,
         patch_path: TreePath,
         attrs: Vec<&'a Attribute<MSG>>,
     ) -> Patch<'a, MSG> {
@@ -284,6 +307,34 @@ impl<'a, MSG> Patch<'a, MSG> {
         }
     }
 
+    /// create a patch where the style properties of the target element are set or removed
+    pub fn update_styles(
+        tag: &'a str,
+        patch_path: TreePath,
+        set: Vec<&'a Style>,
+        remove: Vec<&'a Style>,
+    ) -> Patch<'a, MSG> {
+        Patch {
+            tag: Some(tag),
+            patch_path,
+            patch_type: PatchType::UpdateStyles { set, remove },
+        }
+    }
+
+    /// create a patch where class names are added to or removed from the target element
+    pub fn update_classes(
+        tag: &'a str,
+        patch_path: TreePath,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Patch<'a, MSG> {
+        Patch {
+            tag: Some(tag),
+            patch_path,
+            patch_type: PatchType::UpdateClasses { add, remove },
+        }
+    }
+
     /// map the msg of this patch such that `Patch<MSG>` becomes `Patch<MSG2>`
     pub fn map_msg<F, MSG2>(self, cb: F) -> Patch<'a, MSG2>
     where
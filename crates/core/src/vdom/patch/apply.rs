@@ -0,0 +1,422 @@
+//! Apply the patches into a virtual node, the same way the patches are applied into the DOM.
+//!
+//! The patch paths are relative to the original tree, so all the target nodes are looked up
+//! before any of the patches is applied. To do this, the nodes are stored in an arena where
+//! each node can be referred to with an index while its location in the tree changes.
+use super::{Patch, PatchType, TreePath};
+use crate::vdom::{Attribute, AttributeValue, Leaf, Node, Style, Value};
+
+impl<MSG> Node<MSG> {
+    /// Apply the patches into this node, as if the patches are applied into the DOM
+    /// which is rendered from this node.
+    ///
+    /// Applying the patches from diffing this node with a new node turns this node into the
+    /// new node, which can be used to keep a mirror of the DOM or to test the diffing natively.
+    /// ```rust
+    /// use sauron::{diff, html::*, html::attributes::*, Node};
+    ///
+    /// let mut old: Node<()> = ul([], [li([class("a")], [text("1")])]);
+    /// let new: Node<()> = ul([], [li([class("a")], [text("2")]), li([], [text("3")])]);
+    /// old.apply_patches(&diff(&old.clone(), &new));
+    /// assert_eq!(old, new);
+    /// ```
+    /// The style and class attributes which are updated per style property and class name
+    /// are regrouped into a single attribute, which renders the same as the new node.
+    /// Patches which target nodes that can not be found are skipped.
+    pub fn apply_patches(&mut self, patches: &[Patch<MSG>]) {
+        let patches = patches.iter().map(|patch| {
+            let arena_patch = match &patch.patch_type {
+                PatchType::InsertBeforeNode { nodes } => ArenaPatch::InsertBefore(
+                    nodes.iter().map(|node| node.as_ref().clone()).collect(),
+                ),
+                PatchType::InsertAfterNode { nodes } => {
+                    ArenaPatch::InsertAfter(nodes.iter().map(|node| (*node).clone()).collect())
+                }
+                PatchType::AppendChildren { children } => {
+                    ArenaPatch::Append(children.iter().map(|node| (*node).clone()).collect())
+                }
+                PatchType::ClearChildren => ArenaPatch::ClearChildren,
+                PatchType::RemoveNode => ArenaPatch::RemoveNode,
+                PatchType::MoveBeforeNode { nodes_path } => ArenaPatch::MoveBefore(nodes_path),
+                PatchType::MoveAfterNode { nodes_path } => ArenaPatch::MoveAfter(nodes_path),
+                PatchType::ReplaceNode { replacement } => {
+                    ArenaPatch::Replace(replacement.iter().map(|node| (*node).clone()).collect())
+                }
+                patch_type => ArenaPatch::Update(patch_type),
+            };
+            (&patch.patch_path, arena_patch)
+        });
+        apply_arena_patches(self, patches, update_attributes);
+    }
+
+    /// the node which children and attributes are patched, components are patched in their view
+    fn patch_target_mut(&mut self) -> &mut Node<MSG> {
+        match self {
+            Node::Leaf(Leaf::StatelessComponent(comp)) => comp.view.patch_target_mut(),
+            Node::Leaf(Leaf::TemplatedView(view)) => view.view.patch_target_mut(),
+            Node::Leaf(Leaf::LazyView(view)) => view.view_mut().patch_target_mut(),
+            node => node,
+        }
+    }
+}
+
+impl<MSG> PatchableNode for Node<MSG> {
+    fn placeholder() -> Self {
+        Node::Leaf(Leaf::Text("".into()))
+    }
+
+    fn take_children(&mut self) -> Vec<Self> {
+        match self.patch_target_mut() {
+            Node::Element(elm) => std::mem::take(&mut elm.children),
+            Node::Leaf(Leaf::NodeList(nodes)) | Node::Leaf(Leaf::Fragment(nodes)) => {
+                std::mem::take(nodes)
+            }
+            #[cfg(feature = "with-dom")]
+            Node::Leaf(Leaf::StatefulComponent(comp)) => std::mem::take(&mut comp.children),
+            _ => vec![],
+        }
+    }
+
+    fn set_children(&mut self, children: Vec<Self>) {
+        match self.patch_target_mut() {
+            Node::Element(elm) => elm.children = children,
+            Node::Leaf(Leaf::NodeList(nodes)) | Node::Leaf(Leaf::Fragment(nodes)) => {
+                *nodes = children
+            }
+            #[cfg(feature = "with-dom")]
+            Node::Leaf(Leaf::StatefulComponent(comp)) => comp.children = children,
+            _ => (),
+        }
+    }
+}
+
+/// apply the attribute patches into the node
+fn update_attributes<MSG>(node: &mut Node<MSG>, patch_type: &PatchType<MSG>) {
+    let attrs = match node.patch_target_mut() {
+        Node::Element(elm) => &mut elm.attrs,
+        #[cfg(feature = "with-dom")]
+        Node::Leaf(Leaf::StatefulComponent(comp)) => &mut comp.attrs,
+        _ => return,
+    };
+    match patch_type {
+        PatchType::AddAttributes { attrs: new_attrs } => {
+            let mut names = vec![];
+            for attr in new_attrs.iter() {
+                if !names.contains(&attr.name.as_ref()) {
+                    names.push(attr.name.as_ref());
+                }
+            }
+            for name in names {
+                let values = new_attrs
+                    .iter()
+                    .filter(|attr| attr.name == name)
+                    .map(|attr| (*attr).clone());
+                replace_attribute(attrs, name, values);
+            }
+        }
+        PatchType::RemoveAttributes { attrs: removed } => {
+            attrs.retain(|attr| !removed.iter().any(|removed| removed.name == attr.name));
+        }
+        PatchType::UpdateStyles { set, remove } => {
+            let styles: Vec<Style> = attrs
+                .iter()
+                .filter(|attr| attr.name == "style")
+                .flat_map(|attr| attr.value.iter())
+                .flat_map(|value| match value {
+                    AttributeValue::Style(styles) => styles.clone(),
+                    _ => vec![],
+                })
+                .collect();
+            let styles = update_styles(styles, set.iter().copied(), remove.iter().copied());
+            let style_attr = Attribute::new(None, "style", AttributeValue::Style(styles));
+            replace_attribute(attrs, "style", [style_attr]);
+        }
+        PatchType::UpdateClasses { add, remove } => {
+            let class_names: Vec<String> = attrs
+                .iter()
+                .filter(|attr| attr.name == "class")
+                .flat_map(|attr| attr.value.iter())
+                .filter_map(|value| value.get_simple())
+                .map(|value| value.to_string())
+                .collect();
+            let class_names = update_class_names(class_names, add, remove);
+            // the class attribute is dropped when there are no class names left,
+            // the same way it is when the class attribute is removed from the view
+            let class_attr = (!class_names.is_empty()).then(|| {
+                Attribute::new(
+                    None,
+                    "class",
+                    AttributeValue::Simple(Value::from(class_names.join(" "))),
+                )
+            });
+            replace_attribute(attrs, "class", class_attr);
+        }
+        _ => unreachable!("only attribute patches are applied here"),
+    }
+}
+
+/// replace the attributes with this name, the new attributes are placed
+/// at the location of the first replaced attribute
+fn replace_attribute<MSG>(
+    attrs: &mut Vec<Attribute<MSG>>,
+    name: &str,
+    new_attrs: impl IntoIterator<Item = Attribute<MSG>>,
+) {
+    let idx = attrs
+        .iter()
+        .position(|attr| attr.name == name)
+        .unwrap_or(attrs.len());
+    attrs.retain(|attr| attr.name != name);
+    let idx = idx.min(attrs.len());
+    attrs.splice(idx..idx, new_attrs);
+}
+
+/// set and remove the style properties, the changed properties are kept in place
+pub(crate) fn update_styles<'a>(
+    mut styles: Vec<Style>,
+    set: impl IntoIterator<Item = &'a Style>,
+    remove: impl IntoIterator<Item = &'a Style>,
+) -> Vec<Style> {
+    for removed in remove {
+        styles.retain(|style| style.name != removed.name);
+    }
+    for new_style in set {
+        if let Some(style) = styles.iter_mut().find(|style| style.name == new_style.name) {
+            style.value = new_style.value.clone();
+        } else {
+            styles.push(new_style.clone());
+        }
+    }
+    styles
+}
+
+/// add and remove the class names of a class attribute with these values
+pub(crate) fn update_class_names(
+    values: Vec<String>,
+    add: &[String],
+    remove: &[String],
+) -> Vec<String> {
+    let mut class_names: Vec<String> = vec![];
+    for class_name in values.iter().flat_map(|value| value.split_whitespace()) {
+        if !remove.iter().any(|removed| removed == class_name)
+            && !class_names.iter().any(|existing| existing == class_name)
+        {
+            class_names.push(class_name.to_string());
+        }
+    }
+    for class_name in add {
+        if !class_names.contains(class_name) {
+            class_names.push(class_name.clone());
+        }
+    }
+    class_names
+}
+
+/// A node tree which can be patched in the arena
+pub(crate) trait PatchableNode: Sized {
+    /// an empty node, used in place of the nodes moved into the arena
+    fn placeholder() -> Self;
+    /// take out the children of this node
+    fn take_children(&mut self) -> Vec<Self>;
+    /// put back the children of this node
+    fn set_children(&mut self, children: Vec<Self>);
+}
+
+/// The patches on the tree structure, the other patches are applied with the update function
+pub(crate) enum ArenaPatch<'p, N, U> {
+    InsertBefore(Vec<N>),
+    InsertAfter(Vec<N>),
+    Append(Vec<N>),
+    ClearChildren,
+    RemoveNode,
+    Replace(Vec<N>),
+    MoveBefore(&'p [TreePath]),
+    MoveAfter(&'p [TreePath]),
+    Update(U),
+}
+
+/// apply the patches into the tree with `root` as the root node
+pub(crate) fn apply_arena_patches<'p, N, U>(
+    root: &mut N,
+    patches: impl IntoIterator<Item = (&'p TreePath, ArenaPatch<'p, N, U>)>,
+    mut update: impl FnMut(&mut N, U),
+) where
+    N: PatchableNode,
+{
+    let mut arena = NodeArena::new(std::mem::replace(root, N::placeholder()));
+    // all the target nodes are looked up before any of the patches is applied,
+    // since the patch paths are relative to the original tree
+    let patches: Vec<_> = patches
+        .into_iter()
+        .map(|(path, patch)| {
+            let target = arena.find_node(path);
+            let for_moving: Vec<usize> = match &patch {
+                ArenaPatch::MoveBefore(nodes_path) | ArenaPatch::MoveAfter(nodes_path) => {
+                    nodes_path
+                        .iter()
+                        .filter_map(|path| arena.find_node(path))
+                        .collect()
+                }
+                _ => vec![],
+            };
+            (path, target, patch, for_moving)
+        })
+        .collect();
+
+    for (path, target, patch, for_moving) in patches {
+        let Some(target) = target else {
+            log::warn!("can not find the target node at: {path:?}");
+            continue;
+        };
+        match patch {
+            ArenaPatch::InsertBefore(nodes) => {
+                let nodes = arena.insert_all(nodes);
+                arena.insert_sibling(target, nodes, 0);
+            }
+            ArenaPatch::InsertAfter(nodes) => {
+                let nodes = arena.insert_all(nodes);
+                arena.insert_sibling(target, nodes, 1);
+            }
+            ArenaPatch::Append(children) => {
+                let children = arena.insert_all(children);
+                arena.append_children(target, children);
+            }
+            ArenaPatch::ClearChildren => {
+                for child in std::mem::take(&mut arena.slots[target].children) {
+                    arena.slots[child].parent = None;
+                }
+            }
+            ArenaPatch::RemoveNode => arena.detach(target),
+            ArenaPatch::Replace(replacement) => {
+                let replacement = arena.insert_all(replacement);
+                if target == arena.root {
+                    if let Some(first) = replacement.first() {
+                        arena.root = *first;
+                    }
+                } else {
+                    arena.insert_sibling(target, replacement, 0);
+                    arena.detach(target);
+                }
+            }
+            ArenaPatch::MoveBefore(_) | ArenaPatch::MoveAfter(_) => {
+                let offset = if let ArenaPatch::MoveBefore(_) = patch {
+                    0
+                } else {
+                    1
+                };
+                for id in for_moving.iter() {
+                    arena.detach(*id);
+                }
+                arena.insert_sibling(target, for_moving, offset);
+            }
+            ArenaPatch::Update(patch) => update(&mut arena.slots[target].node, patch),
+        }
+    }
+    *root = arena.into_root();
+}
+
+struct NodeArena<N> {
+    slots: Vec<NodeSlot<N>>,
+    root: usize,
+}
+
+struct NodeSlot<N> {
+    /// the node, with its children moved into `children`
+    node: N,
+    children: Vec<usize>,
+    parent: Option<usize>,
+}
+
+impl<N> NodeArena<N>
+where
+    N: PatchableNode,
+{
+    fn new(root: N) -> Self {
+        let mut arena = NodeArena {
+            slots: vec![],
+            root: 0,
+        };
+        arena.root = arena.insert(root, None);
+        arena
+    }
+
+    fn insert(&mut self, mut node: N, parent: Option<usize>) -> usize {
+        let children = node.take_children();
+        let id = self.slots.len();
+        self.slots.push(NodeSlot {
+            node,
+            children: vec![],
+            parent,
+        });
+        let children = children
+            .into_iter()
+            .map(|child| self.insert(child, Some(id)))
+            .collect();
+        self.slots[id].children = children;
+        id
+    }
+
+    fn insert_all(&mut self, nodes: Vec<N>) -> Vec<usize> {
+        nodes
+            .into_iter()
+            .map(|node| self.insert(node, None))
+            .collect()
+    }
+
+    fn find_node(&self, path: &TreePath) -> Option<usize> {
+        let mut id = self.root;
+        for idx in path.path.iter() {
+            id = *self.slots[id].children.get(*idx)?;
+        }
+        Some(id)
+    }
+
+    /// the parent of this node and the index of this node in the parent's children
+    fn position(&self, id: usize) -> Option<(usize, usize)> {
+        let parent = self.slots[id].parent?;
+        let idx = self.slots[parent].children.iter().position(|c| *c == id)?;
+        Some((parent, idx))
+    }
+
+    fn detach(&mut self, id: usize) {
+        if let Some((parent, idx)) = self.position(id) {
+            self.slots[parent].children.remove(idx);
+            self.slots[id].parent = None;
+        }
+    }
+
+    fn append_children(&mut self, parent: usize, children: Vec<usize>) {
+        for id in children.iter() {
+            self.slots[*id].parent = Some(parent);
+        }
+        self.slots[parent].children.extend(children);
+    }
+
+    /// insert the nodes into the parent of target at an offset from the target
+    fn insert_sibling(&mut self, target: usize, nodes: Vec<usize>, offset: usize) {
+        if let Some((parent, idx)) = self.position(target) {
+            for id in nodes.iter() {
+                self.slots[*id].parent = Some(parent);
+            }
+            let at = idx + offset;
+            self.slots[parent].children.splice(at..at, nodes);
+        } else {
+            log::warn!("can not insert a sibling to a node without a parent");
+        }
+    }
+
+    /// rebuild the node tree from the root
+    fn into_root(mut self) -> N {
+        self.build(self.root)
+    }
+
+    fn build(&mut self, id: usize) -> N {
+        let mut node = std::mem::replace(&mut self.slots[id].node, N::placeholder());
+        let children = std::mem::take(&mut self.slots[id].children)
+            .into_iter()
+            .map(|child| self.build(child))
+            .collect();
+        node.set_children(children);
+        node
+    }
+}
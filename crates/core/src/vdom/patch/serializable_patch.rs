@@ -0,0 +1,507 @@
+//! An owned version of [`Patch`] which can be serialized.
+//!
+//! Unlike [`Patch`] which borrows the nodes from the old and new view, a [`SerializablePatch`]
+//! owns all of its data, so it can be stored, logged or sent to another process to be replayed.
+//! The event listeners can not be serialized, these are replaced with a [`ListenerId`]
+//! which can be used to look up the listener in the [`ListenerRegistry`].
+use super::apply::{
+    apply_arena_patches, update_class_names, update_styles, ArenaPatch, PatchableNode,
+};
+use crate::vdom::{
+    Attribute, AttributeValue, EventCallback, Leaf, Node, Patch, PatchType, Style, TreePath, Value,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The id of an event listener which is registered in a [`ListenerRegistry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ListenerId(pub usize);
+
+/// Contains the event listeners of the converted patches, so the listener can be looked up
+/// with its id when the event is triggered on the receiving side.
+pub struct ListenerRegistry<MSG> {
+    listeners: HashMap<ListenerId, EventCallback<MSG>>,
+    /// the id of the next registered event listener,
+    /// the ids are not reused so the ids of the removed event listeners stay invalid
+    next_id: usize,
+}
+
+impl<MSG> ListenerRegistry<MSG> {
+    /// create an empty registry
+    pub fn new() -> Self {
+        Self {
+            listeners: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// register the event listener and return its id
+    pub fn register(&mut self, listener: &EventCallback<MSG>) -> ListenerId {
+        let id = ListenerId(self.next_id);
+        self.next_id += 1;
+        self.listeners.insert(id, listener.clone());
+        id
+    }
+
+    /// return the event listener with this id
+    pub fn get(&self, id: ListenerId) -> Option<&EventCallback<MSG>> {
+        self.listeners.get(&id)
+    }
+
+    /// remove the event listeners which are no longer used in the `node`,
+    /// ie: the event listeners of the nodes which are removed or replaced by the patches
+    /// which are applied into the `node`
+    pub fn retain_used(&mut self, node: &SerializableNode) {
+        let mut used = HashSet::new();
+        collect_listener_ids(node, &mut used);
+        self.listeners.retain(|id, _| used.contains(id));
+    }
+
+    /// the number of registered event listeners
+    pub fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    /// returns true if there is no registered event listener
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    /// remove all the event listeners, the ids that are given out are no longer valid
+    pub fn clear(&mut self) {
+        self.listeners.clear();
+        self.next_id = 0;
+    }
+}
+
+impl<MSG> Default for ListenerRegistry<MSG> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<MSG> fmt::Debug for ListenerRegistry<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ListenerRegistry")
+            .field("len", &self.listeners.len())
+            .finish()
+    }
+}
+
+/// An owned and serializable version of [`Patch`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializablePatch {
+    /// the tag of the node at patch_path
+    pub tag: Option<String>,
+    /// the path to traverse to get to the target element
+    pub patch_path: TreePath,
+    /// the type of patch we are going to apply
+    pub patch_type: SerializablePatchType,
+}
+
+/// An owned and serializable version of [`PatchType`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SerializablePatchType {
+    /// insert the nodes before the node at patch_path
+    InsertBeforeNode {
+        /// the nodes to be inserted before patch_path
+        nodes: Vec<SerializableNode>,
+    },
+    /// insert the nodes after the node at patch_path
+    InsertAfterNode {
+        /// the nodes to be inserted after the patch_path
+        nodes: Vec<SerializableNode>,
+    },
+    /// Append a vector of child nodes to a parent node id at patch_path
+    AppendChildren {
+        /// children nodes to be appended
+        children: Vec<SerializableNode>,
+    },
+    /// clear the chilren of this node,
+    ClearChildren,
+    /// remove the target node
+    RemoveNode,
+    /// move the nodes at `nodes_path` before the target node
+    MoveBeforeNode {
+        /// before this target location
+        nodes_path: Vec<TreePath>,
+    },
+    /// move the nodes at `nodes_path` after the target node
+    MoveAfterNode {
+        /// after this target location
+        nodes_path: Vec<TreePath>,
+    },
+    /// replace the target node with the replacement nodes
+    ReplaceNode {
+        /// the nodes that will replace the target node
+        replacement: Vec<SerializableNode>,
+    },
+    /// add or replace the attributes of the target node
+    AddAttributes {
+        /// the attributes to be patched into the target node
+        attrs: Vec<SerializableAttribute>,
+    },
+    /// remove the attributes of the target node
+    RemoveAttributes {
+        /// attributes that are to be removed from this target node
+        attrs: Vec<SerializableAttribute>,
+    },
+    /// set and remove the style properties of the target node
+    UpdateStyles {
+        /// the style properties that are added or changed
+        set: Vec<Style>,
+        /// the style properties that are removed
+        remove: Vec<Style>,
+    },
+    /// add and remove the class names of the target node
+    UpdateClasses {
+        /// the class names to be added
+        add: Vec<String>,
+        /// the class names to be removed
+        remove: Vec<String>,
+    },
+}
+
+/// An owned and serializable node.
+/// Components and templated views are converted into their views, while node lists and
+/// fragments are converted into fragments, the same way they are tracked in the DOM
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SerializableNode {
+    /// an element
+    Element {
+        /// the namespace of the element, ie: svg
+        namespace: Option<String>,
+        /// the tag of the element
+        tag: String,
+        /// the attributes of the element
+        attrs: Vec<SerializableAttribute>,
+        /// the child nodes of the element
+        children: Vec<SerializableNode>,
+        /// whether the element is self closing
+        self_closing: bool,
+    },
+    /// a text node
+    Text(String),
+    /// a comment node
+    Comment(String),
+    /// html markup from symbols and trusted html, which is inserted as is
+    Html(String),
+    /// a doctype
+    DocType(String),
+    /// the nodes of a fragment or a node list
+    Fragment(Vec<SerializableNode>),
+}
+
+/// An owned and serializable attribute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializableAttribute {
+    /// the namespace of the attribute
+    pub namespace: Option<String>,
+    /// the attribute name
+    pub name: String,
+    /// the values of the attribute
+    pub value: Vec<SerializableAttributeValue>,
+}
+
+/// An owned and serializable attribute value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SerializableAttributeValue {
+    /// a simple value
+    Simple(Value),
+    /// style values
+    Style(Vec<Style>),
+    /// an event listener, which is registered in the [`ListenerRegistry`] with this id
+    EventListener(ListenerId),
+    /// no value
+    Empty,
+}
+
+impl SerializablePatch {
+    /// convert the patch, registering its event listeners into `listeners`.
+    /// The event listeners can only be looked up by their id in the same registry
+    pub fn from_patch<MSG>(patch: &Patch<MSG>, listeners: &mut ListenerRegistry<MSG>) -> Self {
+        let patch_type = match &patch.patch_type {
+            PatchType::InsertBeforeNode { nodes } => SerializablePatchType::InsertBeforeNode {
+                nodes: convert_nodes(nodes.iter().map(|node| node.as_ref()), listeners),
+            },
+            PatchType::InsertAfterNode { nodes } => SerializablePatchType::InsertAfterNode {
+                nodes: convert_nodes(nodes.iter().copied(), listeners),
+            },
+            PatchType::AppendChildren { children } => SerializablePatchType::AppendChildren {
+                children: convert_nodes(children.iter().copied(), listeners),
+            },
+            PatchType::ClearChildren => SerializablePatchType::ClearChildren,
+            PatchType::RemoveNode => SerializablePatchType::RemoveNode,
+            PatchType::MoveBeforeNode { nodes_path } => SerializablePatchType::MoveBeforeNode {
+                nodes_path: nodes_path.clone(),
+            },
+            PatchType::MoveAfterNode { nodes_path } => SerializablePatchType::MoveAfterNode {
+                nodes_path: nodes_path.clone(),
+            },
+            PatchType::ReplaceNode { replacement } => SerializablePatchType::ReplaceNode {
+                replacement: convert_nodes(replacement.iter().copied(), listeners),
+            },
+            PatchType::AddAttributes { attrs } => SerializablePatchType::AddAttributes {
+                attrs: attrs
+                    .iter()
+                    .map(|attr| SerializableAttribute::from_attribute(attr, listeners))
+                    .collect(),
+            },
+            PatchType::RemoveAttributes { attrs } => SerializablePatchType::RemoveAttributes {
+                attrs: attrs
+                    .iter()
+                    .map(|attr| SerializableAttribute::from_attribute(attr, listeners))
+                    .collect(),
+            },
+            PatchType::UpdateStyles { set, remove } => SerializablePatchType::UpdateStyles {
+                set: set.iter().map(|style| (*style).clone()).collect(),
+                remove: remove.iter().map(|style| (*style).clone()).collect(),
+            },
+            PatchType::UpdateClasses { add, remove } => SerializablePatchType::UpdateClasses {
+                add: add.clone(),
+                remove: remove.clone(),
+            },
+        };
+        SerializablePatch {
+            tag: patch.tag.map(|tag| tag.to_string()),
+            patch_path: patch.patch_path.clone(),
+            patch_type,
+        }
+    }
+}
+
+impl SerializableNode {
+    /// convert the node, registering its event listeners into `listeners`
+    pub fn from_node<MSG>(node: &Node<MSG>, listeners: &mut ListenerRegistry<MSG>) -> Self {
+        match node {
+            Node::Element(elm) => SerializableNode::Element {
+                namespace: elm.namespace().map(|namespace| namespace.to_string()),
+                tag: elm.tag().to_string(),
+                attrs: elm
+                    .attributes()
+                    .iter()
+                    .map(|attr| SerializableAttribute::from_attribute(attr, listeners))
+                    .collect(),
+                children: convert_nodes(elm.children(), listeners),
+                self_closing: elm.self_closing,
+            },
+            Node::Leaf(leaf) => match leaf {
+                Leaf::Text(v) => SerializableNode::Text(v.to_string()),
+                Leaf::Comment(v) => SerializableNode::Comment(v.to_string()),
+                Leaf::Symbol(v) | Leaf::TrustedHtml(v) => SerializableNode::Html(v.to_string()),
+                Leaf::DocType(v) => SerializableNode::DocType(v.to_string()),
+                Leaf::NodeList(children) | Leaf::Fragment(children) => {
+                    SerializableNode::Fragment(convert_nodes(children, listeners))
+                }
+                #[cfg(feature = "with-dom")]
+                Leaf::StatefulComponent(comp) => Self::from_node(&comp.view(), listeners),
+                Leaf::StatelessComponent(comp) => Self::from_node(&comp.view, listeners),
+                Leaf::TemplatedView(view) => Self::from_node(&view.view, listeners),
+                Leaf::LazyView(view) => Self::from_node(view.view(), listeners),
+            },
+        }
+    }
+
+    /// apply the patches into this node, the same way the patches are applied into the DOM,
+    /// where the patch paths are relative to this node.
+    ///
+    /// This is used to keep a mirror of the DOM which the patches are sent to.
+    /// Patches which target nodes that can not be found are skipped.
+    pub fn apply_patches(&mut self, patches: &[SerializablePatch]) {
+        let patches = patches.iter().map(|patch| {
+            let arena_patch = match &patch.patch_type {
+                SerializablePatchType::InsertBeforeNode { nodes } => {
+                    ArenaPatch::InsertBefore(nodes.clone())
+                }
+                SerializablePatchType::InsertAfterNode { nodes } => {
+                    ArenaPatch::InsertAfter(nodes.clone())
+                }
+                SerializablePatchType::AppendChildren { children } => {
+                    ArenaPatch::Append(children.clone())
+                }
+                SerializablePatchType::ClearChildren => ArenaPatch::ClearChildren,
+                SerializablePatchType::RemoveNode => ArenaPatch::RemoveNode,
+                SerializablePatchType::MoveBeforeNode { nodes_path } => {
+                    ArenaPatch::MoveBefore(nodes_path)
+                }
+                SerializablePatchType::MoveAfterNode { nodes_path } => {
+                    ArenaPatch::MoveAfter(nodes_path)
+                }
+                SerializablePatchType::ReplaceNode { replacement } => {
+                    ArenaPatch::Replace(replacement.clone())
+                }
+                patch_type => ArenaPatch::Update(patch_type),
+            };
+            (&patch.patch_path, arena_patch)
+        });
+        apply_arena_patches(self, patches, update_attributes);
+    }
+}
+
+impl PatchableNode for SerializableNode {
+    fn placeholder() -> Self {
+        SerializableNode::Text(String::new())
+    }
+
+    fn take_children(&mut self) -> Vec<Self> {
+        match self {
+            SerializableNode::Element { children, .. } | SerializableNode::Fragment(children) => {
+                std::mem::take(children)
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_children(&mut self, new_children: Vec<Self>) {
+        if let SerializableNode::Element { children, .. } | SerializableNode::Fragment(children) =
+            self
+        {
+            *children = new_children;
+        }
+    }
+}
+
+/// apply the attribute patches into the node
+fn update_attributes(node: &mut SerializableNode, patch_type: &SerializablePatchType) {
+    let SerializableNode::Element { attrs, .. } = node else {
+        return;
+    };
+    match patch_type {
+        SerializablePatchType::AddAttributes { attrs: new_attrs } => {
+            for new_attr in new_attrs {
+                if let Some(attr) = attrs
+                    .iter_mut()
+                    .find(|attr| attr.name == new_attr.name && attr.namespace == new_attr.namespace)
+                {
+                    attr.value.clone_from(&new_attr.value);
+                } else {
+                    attrs.push(new_attr.clone());
+                }
+            }
+        }
+        SerializablePatchType::RemoveAttributes { attrs: removed } => {
+            attrs.retain(|attr| !removed.iter().any(|removed| removed.name == attr.name));
+        }
+        SerializablePatchType::UpdateStyles { set, remove } => {
+            let attr = attribute_entry(attrs, "style");
+            let styles = attr
+                .value
+                .iter()
+                .flat_map(|value| match value {
+                    SerializableAttributeValue::Style(styles) => styles.clone(),
+                    _ => vec![],
+                })
+                .collect();
+            let styles = update_styles(styles, set, remove);
+            attr.value = vec![SerializableAttributeValue::Style(styles)];
+        }
+        SerializablePatchType::UpdateClasses { add, remove } => {
+            let attr = attribute_entry(attrs, "class");
+            let values = attr
+                .value
+                .iter()
+                .filter_map(|value| match value {
+                    SerializableAttributeValue::Simple(v) => Some(v.to_string()),
+                    _ => None,
+                })
+                .collect();
+            let class_names = update_class_names(values, add, remove);
+            attr.value = vec![SerializableAttributeValue::Simple(Value::from(
+                class_names.join(" "),
+            ))];
+            // the class attribute is dropped when there are no class names left
+            if class_names.is_empty() {
+                attrs.retain(|attr| attr.name != "class");
+            }
+        }
+        _ => unreachable!("only attribute patches are applied here"),
+    }
+}
+
+impl SerializableAttribute {
+    /// convert the attribute, registering its event listeners into `listeners`.
+    /// Event listeners of stateful components are not included
+    pub fn from_attribute<MSG>(
+        attr: &Attribute<MSG>,
+        listeners: &mut ListenerRegistry<MSG>,
+    ) -> Self {
+        SerializableAttribute {
+            namespace: attr
+                .namespace
+                .as_ref()
+                .map(|namespace| namespace.to_string()),
+            name: attr.name.to_string(),
+            value: attr
+                .value
+                .iter()
+                .filter_map(|value| match value {
+                    AttributeValue::Simple(v) => {
+                        Some(SerializableAttributeValue::Simple(v.clone()))
+                    }
+                    AttributeValue::Style(v) => Some(SerializableAttributeValue::Style(v.clone())),
+                    AttributeValue::EventListener(listener) => Some(
+                        SerializableAttributeValue::EventListener(listeners.register(listener)),
+                    ),
+                    AttributeValue::ComponentEventListener(_) => None,
+                    AttributeValue::Empty => Some(SerializableAttributeValue::Empty),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// collect the ids of the event listeners in the node and its descendants
+fn collect_listener_ids(node: &SerializableNode, ids: &mut HashSet<ListenerId>) {
+    match node {
+        SerializableNode::Element {
+            attrs, children, ..
+        } => {
+            ids.extend(
+                attrs
+                    .iter()
+                    .flat_map(|attr| attr.value.iter())
+                    .filter_map(|value| match value {
+                        SerializableAttributeValue::EventListener(id) => Some(*id),
+                        _ => None,
+                    }),
+            );
+            children
+                .iter()
+                .for_each(|child| collect_listener_ids(child, ids));
+        }
+        SerializableNode::Fragment(children) => children
+            .iter()
+            .for_each(|child| collect_listener_ids(child, ids)),
+        _ => (),
+    }
+}
+
+fn convert_nodes<'a, MSG: 'a>(
+    nodes: impl IntoIterator<Item = &'a Node<MSG>>,
+    listeners: &mut ListenerRegistry<MSG>,
+) -> Vec<SerializableNode> {
+    nodes
+        .into_iter()
+        .map(|node| SerializableNode::from_node(node, listeners))
+        .collect()
+}
+
+/// return the attribute with this name, adding it if there is none
+fn attribute_entry<'a>(
+    attrs: &'a mut Vec<SerializableAttribute>,
+    name: &str,
+) -> &'a mut SerializableAttribute {
+    let idx = match attrs.iter().position(|attr| attr.name == name) {
+        Some(idx) => idx,
+        None => {
+            attrs.push(SerializableAttribute {
+                namespace: None,
+                name: name.to_string(),
+                value: vec![],
+            });
+            attrs.len() - 1
+        }
+    };
+    &mut attrs[idx]
+}
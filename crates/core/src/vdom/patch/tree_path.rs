@@ -58,6 +58,7 @@ use std::fmt::Debug;
 ///    7 = [1,2]
 /// ```
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TreePath {
     /// An array of child index at each level of the dom tree.
     /// The children of the nodes at each child index is traverse
@@ -234,8 +235,8 @@ mod tests {
 
     // index is the index of this code with respect to it's sibling
     fn assert_traverse_match(node: &Node<()>, node_idx: &mut usize, path: Vec<usize>) {
-        let id = node.attribute_value(&"id").unwrap()[0];
-        let class = node.attribute_value(&"class").unwrap()[0];
+        let id = node.attribute_value("id").unwrap()[0];
+        let class = node.attribute_value("class").unwrap()[0];
         assert_eq!(id.as_str(), Some(node_idx.to_string()).as_deref());
         assert_eq!(class.as_str(), Some(format_vec(&path)).as_deref());
         for (i, child) in node.children().iter().enumerate() {
@@ -247,8 +248,8 @@ mod tests {
     }
 
     fn traverse_tree_path(node: &Node<()>, path: &TreePath, node_idx: &mut usize) {
-        let id = node.attribute_value(&"id").unwrap()[0];
-        let class = node.attribute_value(&"class").unwrap()[0];
+        let id = node.attribute_value("id").unwrap()[0];
+        let class = node.attribute_value("class").unwrap()[0];
         assert_eq!(id.as_str(), Some(node_idx.to_string()).as_deref());
         assert_eq!(class.as_str(), Some(format_vec(&path.path)).as_deref());
         for (i, child) in node.children().iter().enumerate() {
@@ -9,37 +9,175 @@ use crate::{
 };
 use std::fmt;
 
-const DEFAULT_INDENT_SIZE: usize = 2;
+pub use options::{AttributeOrder, Newline, RenderOptions, VoidElementStyle, BOOLEAN_ATTRIBUTES};
+pub use stream::{FlushPoints, RenderChunks};
 
-/// add an indent if applicable
-fn maybe_indent(buffer: &mut dyn fmt::Write, indent: usize, compressed: bool) -> fmt::Result {
-    if !compressed {
-        write!(
-            buffer,
-            "\n{}",
-            " ".repeat(DEFAULT_INDENT_SIZE).repeat(indent)
-        )?;
+mod options;
+mod stream;
+
+/// The content of these elements are raw text, which the browser doesn't unescape.
+/// <https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements>
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// The void elements, which have no end tag in html, including the legacy elements
+/// which are still parsed as void elements by the browser.
+/// <https://html.spec.whatwg.org/multipage/syntax.html#void-elements>
+/// <https://html.spec.whatwg.org/multipage/parsing.html#serialising-html-fragments>
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "basefont", "bgsound", "br", "col", "embed", "frame", "hr", "img", "input",
+    "keygen", "link", "meta", "param", "source", "track", "wbr",
+];
+
+/// Elements which whitespace is preserved, as specified in the user agent stylesheet.
+/// <https://html.spec.whatwg.org/multipage/rendering.html#flow-content-3>
+const PREFORMATTED_ELEMENTS: [&str; 4] = ["pre", "textarea", "listing", "plaintext"];
+
+/// Elements which are displayed as blocks or are not displayed at all in the user agent stylesheet.
+/// Whitespace in between these elements are collapsed by the browser, while whitespace in between
+/// inline elements such as `span` are displayed.
+///
+/// Unknown elements such as custom elements are displayed inline, so they are not included here.
+/// <https://html.spec.whatwg.org/multipage/rendering.html#the-css-user-agent-style-sheet-and-presentational-hints>
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "base",
+    "blockquote",
+    "body",
+    "caption",
+    "col",
+    "colgroup",
+    "datalist",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "menu",
+    "meta",
+    "nav",
+    "noscript",
+    "ol",
+    "optgroup",
+    "option",
+    "p",
+    "pre",
+    "script",
+    "search",
+    "section",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "ul",
+];
+
+/// Elements in the svg namespace which content is text, whitespace in between their child nodes
+/// is displayed.
+const FOREIGN_TEXT_ELEMENTS: [&str; 3] = ["text", "tspan", "textPath"];
+
+/// whether whitespace around this node is collapsed by the browser.
+/// `in_foreign` indicates the node is a child of an element in foreign namespace such as svg,
+/// where whitespace in between elements doesn't matter
+fn is_block_level<MSG>(node: &Node<MSG>, in_foreign: bool) -> bool {
+    match node {
+        Node::Element(_) if in_foreign => true,
+        Node::Element(element) => BLOCK_ELEMENTS.contains(&element.tag()),
+        Node::Leaf(Leaf::Comment(_)) | Node::Leaf(Leaf::DocType(_)) => true,
+        // stateful components are rendered in a div
+        Node::Leaf(Leaf::StatefulComponent(_)) => true,
+        Node::Leaf(Leaf::StatelessComponent(comp)) => is_block_level(&comp.view, in_foreign),
+        Node::Leaf(Leaf::TemplatedView(view)) => is_block_level(&view.view, in_foreign),
+        Node::Leaf(Leaf::LazyView(view)) => is_block_level(view.view(), in_foreign),
+        _ => false,
+    }
+}
+
+/// write the text into the buffer, escaping the characters that would
+/// otherwise be interpreted as html markup
+fn write_escaped_text(buffer: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+    write_escaped(buffer, text, false)
+}
+
+/// write the attribute value into the buffer, escaping the characters that would
+/// otherwise terminate the double quoted attribute value
+fn write_escaped_attribute_value(buffer: &mut dyn fmt::Write, value: &str) -> fmt::Result {
+    write_escaped(buffer, value, true)
+}
+
+/// write the content of a raw text element such as `script` and `style` as is,
+/// except for `</` which is written as `<\/`, so the text can't close the element early
+fn write_raw_text(buffer: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+    let mut parts = text.split("</");
+    if let Some(first) = parts.next() {
+        buffer.write_str(first)?;
+    }
+    for part in parts {
+        buffer.write_str("<\\/")?;
+        buffer.write_str(part)?;
     }
     Ok(())
 }
 
+fn write_escaped(buffer: &mut dyn fmt::Write, s: &str, in_attribute: bool) -> fmt::Result {
+    let mut last = 0;
+    for (i, ch) in s.char_indices() {
+        let escaped = match ch {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' if in_attribute => "&quot;",
+            _ => continue,
+        };
+        buffer.write_str(&s[last..i])?;
+        buffer.write_str(escaped)?;
+        last = i + ch.len_utf8();
+    }
+    buffer.write_str(&s[last..])
+}
+
 impl<MSG> Node<MSG> {
-    // ISSUE: sublte difference in `render` and `render_to_string`:
-    //  - flow content element such as span will treat the whitespace in between them as html text
-    //  node
-    //  Example:
-    //  in `render`
+    // Pretty rendering only breaks the lines in between nodes where the added whitespace
+    // will not change what the browser displays, so the output of `render` and `render_to_string`
+    // are displayed the same.
+    // Example: the whitespace in between these inline elements is significant
     //  ```html
     //     <span>hello</span>
     //     <span> world</span>
     //  ```
-    //     will displayed as "hello  world"
-    //
-    //  where us `render_to_string`
+    //     will displayed as "hello  world", therefore they are kept in the same line
     //  ```html
     //  <span>hello</span><span> world</span>
     //  ```
-    //  will result to a desirable output: "hello world"
     //
     /// render the node to a writable buffer
     pub fn render_with_indent(
@@ -47,10 +185,40 @@ impl<MSG> Node<MSG> {
         buffer: &mut dyn fmt::Write,
         indent: usize,
         compressed: bool,
+    ) -> fmt::Result {
+        let options = RenderOptions {
+            compressed,
+            ..Default::default()
+        };
+        self.render_indented(buffer, indent, &options)
+    }
+
+    /// render the node to a writable buffer using the supplied options
+    pub fn render_with_options(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        self.render_indented(buffer, 0, options)
+    }
+
+    /// render the node to string using the supplied options
+    pub fn render_to_string_with_options(&self, options: &RenderOptions) -> String {
+        let mut buffer = String::new();
+        self.render_with_options(&mut buffer, options)
+            .expect("must render");
+        buffer
+    }
+
+    fn render_indented(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+        options: &RenderOptions,
     ) -> fmt::Result {
         match self {
-            Node::Element(element) => element.render_with_indent(buffer, indent, compressed),
-            Node::Leaf(leaf) => leaf.render_with_indent(buffer, indent, compressed),
+            Node::Element(element) => element.render_indented(buffer, indent, options),
+            Node::Leaf(leaf) => leaf.render_indented(buffer, indent, options),
         }
     }
 
@@ -86,14 +254,28 @@ impl<MSG> Leaf<MSG> {
         buffer: &mut dyn fmt::Write,
         indent: usize,
         compressed: bool,
+    ) -> fmt::Result {
+        let options = RenderOptions {
+            compressed,
+            ..Default::default()
+        };
+        self.render_indented(buffer, indent, &options)
+    }
+
+    fn render_indented(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+        options: &RenderOptions,
     ) -> fmt::Result {
         match self {
-            Leaf::Text(text) => {
-                write!(buffer, "{text}")
-            }
+            Leaf::Text(text) => write_escaped_text(buffer, text),
             Leaf::Symbol(symbol) => {
                 write!(buffer, "{symbol}")
             }
+            Leaf::TrustedHtml(html) => {
+                write!(buffer, "{html}")
+            }
             Leaf::Comment(comment) => {
                 write!(buffer, "<!--{comment}-->")
             }
@@ -102,21 +284,20 @@ impl<MSG> Leaf<MSG> {
             }
             Leaf::Fragment(nodes) => {
                 for node in nodes {
-                    node.render_with_indent(buffer, indent, compressed)?;
+                    node.render_indented(buffer, indent, options)?;
                 }
                 Ok(())
             }
             Leaf::NodeList(node_list) => {
                 for node in node_list {
-                    node.render_with_indent(buffer, indent, compressed)?;
+                    node.render_indented(buffer, indent, options)?;
                 }
                 Ok(())
             }
-            Leaf::StatefulComponent(_comp) => {
-                write!(buffer, "<!-- stateful component -->")
-            }
-            Leaf::StatelessComponent(comp) => comp.view.render(buffer),
-            Leaf::TemplatedView(view) => view.view.render(buffer),
+            Leaf::StatefulComponent(comp) => comp.view().render_indented(buffer, indent, options),
+            Leaf::StatelessComponent(comp) => comp.view.render_indented(buffer, indent, options),
+            Leaf::TemplatedView(view) => view.view.render_indented(buffer, indent, options),
+            Leaf::LazyView(view) => view.view().render_indented(buffer, indent, options),
         }
     }
 }
@@ -129,56 +310,122 @@ impl<MSG> Element<MSG> {
         indent: usize,
         compressed: bool,
     ) -> fmt::Result {
-        write!(buffer, "<{}", self.tag())?;
+        let options = RenderOptions {
+            compressed,
+            ..Default::default()
+        };
+        self.render_indented(buffer, indent, &options)
+    }
 
-        let merged_attributes: Vec<Attribute<MSG>> =
-            Attribute::merge_attributes_of_same_name(self.attributes().iter());
+    fn render_indented(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        self.render_start_tag(buffer, options)?;
 
-        for attr in &merged_attributes {
-            write!(buffer, " ")?;
-            attr.render(buffer)?;
-        }
+        let children = self.children();
+
+        // text inside script and style are not escaped, since they are not unescaped by the browser
+        let is_raw_text = RAW_TEXT_ELEMENTS.contains(&self.tag());
 
-        if self.self_closing {
-            write!(buffer, "/>")?;
+        // whitespace is significant in the whole subtree of preformatted elements
+        let options = &if PREFORMATTED_ELEMENTS.contains(&self.tag()) {
+            RenderOptions {
+                compressed: true,
+                ..*options
+            }
         } else {
-            write!(buffer, ">")?;
+            *options
+        };
+
+        // only put each child node in its own line if the added whitespace
+        // doesn't change how the browser displays them
+        let break_lines = !children.is_empty() && self.can_break_children();
+
+        for child in children {
+            if break_lines {
+                options.write_indent(buffer, indent + 1)?;
+            }
+            match child.as_text() {
+                Some(text) if is_raw_text => write_raw_text(buffer, text)?,
+                _ => child.render_indented(buffer, indent + 1, options)?,
+            }
         }
 
-        let children = self.children();
-        let first_child = children.first();
-        let is_first_child_text_node = first_child.map(|node| node.is_text()).unwrap_or(false);
+        if break_lines {
+            options.write_indent(buffer, indent)?;
+        }
+
+        self.render_end_tag(buffer)
+    }
 
-        let is_lone_child_text_node = children.len() == 1 && is_first_child_text_node;
+    /// whether whitespace can be inserted in between the child nodes of this element
+    /// without affecting how they are displayed
+    fn can_break_children(&self) -> bool {
+        let in_foreign = self.namespace().is_some();
+        if in_foreign && FOREIGN_TEXT_ELEMENTS.contains(&self.tag()) {
+            return false;
+        }
+        self.children()
+            .iter()
+            .all(|child| is_block_level(child, in_foreign))
+    }
 
-        // do not indent if it is only text child node
-        if is_lone_child_text_node {
-            first_child
-                .unwrap()
-                .render_with_indent(buffer, indent, compressed)?;
-        } else {
-            // otherwise print all child nodes with each line and indented
-            for child in self.children() {
-                maybe_indent(buffer, indent + 1, compressed)?;
-                child.render_with_indent(buffer, indent + 1, compressed)?;
+    /// render the opening tag of this element together with its attributes
+    pub(crate) fn render_start_tag(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        write!(buffer, "<{}", self.tag())?;
+
+        let mut merged_attributes: Vec<Attribute<MSG>> =
+            Attribute::merge_attributes_of_same_name(self.attributes().iter());
+
+        if options.attribute_order == AttributeOrder::Alphabetical {
+            merged_attributes.sort_by(|a, b| a.name().cmp(b.name()));
+        }
+
+        for attr in &merged_attributes {
+            for value in attr.rendered_values(options) {
+                write!(buffer, " ")?;
+                attr.write_name_value(buffer, &value)?;
             }
         }
 
-        // do not make a new line it if is only a text child node or it has no child nodes
-        if !is_lone_child_text_node && !children.is_empty() {
-            maybe_indent(buffer, indent, compressed)?;
+        // elements from foreign namespace such as svg needs to be explicitly closed
+        let is_html5_void =
+            options.void_element_style == VoidElementStyle::Html5 && self.namespace().is_none();
+
+        if self.has_no_end_tag() && !is_html5_void {
+            write!(buffer, "/>")
+        } else {
+            write!(buffer, ">")
         }
+    }
 
-        if !self.self_closing {
+    /// render the closing tag of this element, if it has one
+    fn render_end_tag(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
+        if !self.has_no_end_tag() {
             write!(buffer, "</{}>", self.tag())?;
         }
         Ok(())
     }
+
+    /// whether this element is closed in its start tag.
+    /// Elements from foreign namespace such as svg can be self closing, while in html
+    /// only the void elements have no end tag, ie: `<my-el/>` is not closed by the browser
+    fn has_no_end_tag(&self) -> bool {
+        self.self_closing && (self.namespace().is_some() || VOID_ELEMENTS.contains(&self.tag()))
+    }
 }
 
 impl<MSG> Attribute<MSG> {
-    /// render attributes
-    fn render(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
+    /// the merged values of this attribute, which are each rendered as `name="value"`.
+    /// There are no values if the attribute is skipped
+    fn rendered_values(&self, options: &RenderOptions) -> Vec<String> {
         let GroupedAttributeValues {
             plain_values,
             styles,
@@ -192,31 +439,42 @@ impl<MSG> Attribute<MSG> {
         // of the render which then satisfies our intent to the the browser behavior.
         //
         // https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fe-disabled
-        let boolean_attributes = ["open", "checked", "disabled"];
-
-        let bool_value: bool = plain_values
-            .first()
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let is_false = plain_values.first().and_then(|v| v.as_bool()) == Some(false);
 
         // skip this attribute if the boolean attributes evaluates to false
-        let should_skip_attribute = boolean_attributes.contains(self.name()) && !bool_value;
+        let should_skip_attribute =
+            options.boolean_attributes.contains(&self.name().as_ref()) && is_false;
 
-        if !should_skip_attribute {
-            if let Some(merged_plain_values) = Value::merge_to_string(plain_values) {
-                write!(buffer, "{}=\"{}\"", self.name(), merged_plain_values)?;
-            }
-            if let Some(merged_styles) = Style::merge_to_string(styles) {
-                write!(buffer, "{}=\"{}\"", self.name(), merged_styles)?;
-            }
+        if should_skip_attribute {
+            return vec![];
         }
-        Ok(())
+        Value::merge_to_string(plain_values)
+            .into_iter()
+            .chain(Style::merge_to_string(styles))
+            .collect()
+    }
+
+    /// write the attribute name together with its escaped value
+    fn write_name_value(&self, buffer: &mut dyn fmt::Write, value: &str) -> fmt::Result {
+        write!(buffer, "{}=\"", self.name())?;
+        write_escaped_attribute_value(buffer, value)?;
+        write!(buffer, "\"")
     }
 
     /// render compressed html to string
     pub fn render_to_string(&self) -> String {
         let mut buffer = String::new();
-        self.render(&mut buffer).expect("must render");
+        for (i, value) in self
+            .rendered_values(&RenderOptions::default())
+            .iter()
+            .enumerate()
+        {
+            if i > 0 {
+                buffer.push(' ');
+            }
+            self.write_name_value(&mut buffer, value)
+                .expect("must render");
+        }
         buffer
     }
 }
@@ -225,6 +483,8 @@ impl<MSG> Attribute<MSG> {
 mod test {
     use super::*;
     use crate::html::{attributes::*, *};
+    use crate::svg::SVG_NAMESPACE;
+    use crate::vdom::element_ns;
 
     #[test]
     fn test_render_comments() {
@@ -246,6 +506,71 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_render_escaped_text() {
+        let view: Node<()> = div(vec![], vec![text("<script>alert('xss')</script> & more")]);
+
+        assert_eq!(
+            view.render_to_string(),
+            "<div>&lt;script&gt;alert('xss')&lt;/script&gt; &amp; more</div>"
+        );
+    }
+
+    #[test]
+    fn test_render_escaped_attribute_value() {
+        let view: Node<()> = div(vec![attr("title", "\"><script>alert(1)</script>")], vec![]);
+
+        assert_eq!(
+            view.render_to_string(),
+            r#"<div title="&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_render_raw_text_elements_unescaped() {
+        let view: Node<()> = div(
+            vec![],
+            vec![
+                script(vec![], vec![text("if (a < b && c > d) {}")]),
+                crate::html::tags::style(vec![], vec![text("div > p { content: \"&\"; }")]),
+            ],
+        );
+
+        assert_eq!(
+            view.render_to_string(),
+            "<div><script>if (a < b && c > d) {}</script><style>div > p { content: \"&\"; }</style></div>"
+        );
+    }
+
+    #[test]
+    fn test_render_raw_text_can_not_close_the_element() {
+        let view: Node<()> = div(
+            vec![],
+            vec![
+                script(
+                    vec![],
+                    vec![text("let a = \"</script><script>alert(1)</SCRIPT>\";")],
+                ),
+                crate::html::tags::style(
+                    vec![],
+                    vec![text("</style><img src=x onerror=alert(1)>")],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            view.render_to_string(),
+            r#"<div><script>let a = "<\/script><script>alert(1)<\/SCRIPT>";</script><style><\/style><img src=x onerror=alert(1)></style></div>"#
+        );
+    }
+
+    #[test]
+    fn test_render_trusted_html() {
+        let view: Node<()> = div(vec![], vec![trusted_html("<b>bold</b>")]);
+
+        assert_eq!(view.render_to_string(), "<div><b>bold</b></div>");
+    }
+
     #[test]
     fn test_render_classes() {
         let view: Node<()> = div(vec![class("frame"), class("component")], vec![]);
@@ -269,4 +594,180 @@ mod test {
         view.render(&mut buffer).expect("must render");
         assert_eq!(expected, buffer);
     }
+
+    #[test]
+    fn test_render_html5_void_elements() {
+        let view: Node<()> = div(
+            vec![],
+            vec![
+                br(vec![], vec![]),
+                input(vec![r#type("text")], vec![]),
+                element_ns(
+                    Some(SVG_NAMESPACE.into()),
+                    "circle",
+                    vec![attr("r", 1)],
+                    vec![],
+                    true,
+                ),
+            ],
+        );
+        let options = RenderOptions {
+            void_element_style: VoidElementStyle::Html5,
+            ..RenderOptions::compressed()
+        };
+        assert_eq!(
+            view.render_to_string_with_options(&options),
+            r#"<div><br><input type="text"><circle r="1"/></div>"#
+        );
+    }
+
+    #[test]
+    fn test_render_self_closing_non_void_elements_with_end_tag() {
+        let view: Node<()> = div(
+            vec![],
+            vec![element_ns(
+                None,
+                "my-el",
+                vec![attr("size", 1)],
+                vec![],
+                true,
+            )],
+        );
+        let expected = r#"<div><my-el size="1"></my-el></div>"#;
+        assert_eq!(view.render_to_string(), expected);
+        let options = RenderOptions {
+            void_element_style: VoidElementStyle::Html5,
+            ..RenderOptions::compressed()
+        };
+        assert_eq!(view.render_to_string_with_options(&options), expected);
+    }
+
+    #[test]
+    fn test_render_attribute_to_string() {
+        let attr: Attribute<()> = class("frame");
+        assert_eq!(attr.render_to_string(), r#"class="frame""#);
+    }
+
+    #[test]
+    fn test_render_boolean_attributes() {
+        let view: Node<()> = select(
+            vec![multiple(false), required(true)],
+            vec![
+                option(vec![selected(false), hidden(false)], vec![]),
+                option(vec![selected(true), attr("readonly", "readonly")], vec![]),
+            ],
+        );
+        assert_eq!(
+            view.render_to_string(),
+            r#"<select required="true"><option></option><option selected="true" readonly="readonly"></option></select>"#
+        );
+    }
+
+    #[test]
+    fn test_render_alphabetical_attributes() {
+        let view: Node<()> = div(vec![id("main"), class("frame"), attr("data-x", 1)], vec![]);
+        let options = RenderOptions {
+            attribute_order: AttributeOrder::Alphabetical,
+            ..RenderOptions::compressed()
+        };
+        assert_eq!(
+            view.render_to_string_with_options(&options),
+            r#"<div class="frame" data-x="1" id="main"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_render_tab_indent_and_crlf() {
+        let view: Node<()> = ul(vec![], vec![li(vec![], vec![text("a")])]);
+        let options = RenderOptions {
+            indent_char: '\t',
+            indent_width: 1,
+            newline: Newline::CrLf,
+            ..Default::default()
+        };
+        assert_eq!(
+            view.render_to_string_with_options(&options),
+            "<ul>\r\n\t<li>a</li>\r\n</ul>"
+        );
+    }
+
+    #[test]
+    fn test_default_options_matches_render() {
+        let view: Node<()> = div(
+            vec![class("a")],
+            vec![p(vec![], vec![text("1")]), input(vec![], vec![])],
+        );
+        assert_eq!(
+            view.render_to_string_with_options(&RenderOptions::default()),
+            view.render_to_string_pretty()
+        );
+        assert_eq!(
+            view.render_to_string_with_options(&RenderOptions::compressed()),
+            view.render_to_string()
+        );
+    }
+
+    #[test]
+    fn test_pretty_render_keeps_inline_elements_in_one_line() {
+        let view: Node<()> = p(
+            vec![],
+            vec![
+                span(vec![], vec![text("hello")]),
+                span(vec![], vec![text(" world")]),
+            ],
+        );
+        assert_eq!(
+            view.render_to_string_pretty(),
+            "<p><span>hello</span><span> world</span></p>"
+        );
+    }
+
+    #[test]
+    fn test_pretty_render_breaks_lines_in_between_blocks() {
+        let view: Node<()> = div(
+            vec![],
+            vec![
+                h1(vec![], vec![text("title")]),
+                p(
+                    vec![],
+                    vec![text("a "), b(vec![], vec![text("bold")]), text(" word")],
+                ),
+                ul(vec![], vec![li(vec![], vec![text("item")])]),
+            ],
+        );
+        assert_eq!(
+            view.render_to_string_pretty(),
+            "<div>\n  <h1>title</h1>\n  <p>a <b>bold</b> word</p>\n  <ul>\n    <li>item</li>\n  </ul>\n</div>"
+        );
+    }
+
+    #[test]
+    fn test_pretty_render_preserves_preformatted_content() {
+        let view: Node<()> = div(
+            vec![],
+            vec![pre(
+                vec![],
+                vec![div(vec![], vec![p(vec![], vec![text("  code")])])],
+            )],
+        );
+        assert_eq!(
+            view.render_to_string_pretty(),
+            "<div>\n  <pre><div><p>  code</p></div></pre>\n</div>"
+        );
+    }
+
+    #[test]
+    fn test_pretty_render_svg_children() {
+        let view: Node<()> = crate::svg::svg(
+            vec![],
+            vec![
+                crate::svg::rect(vec![], vec![]),
+                crate::svg::tags::text(vec![], vec![text("label")]),
+            ],
+        );
+        assert_eq!(
+            view.render_to_string_pretty(),
+            "<svg>\n  <rect></rect>\n  <text>label</text>\n</svg>"
+        );
+    }
 }
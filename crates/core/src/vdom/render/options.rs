@@ -0,0 +1,139 @@
+//! Options to configure how the virtual dom is rendered into html
+use std::fmt;
+
+/// The boolean attributes as defined in the html spec.
+/// These attributes represent a true value by their presence, so they are omitted from the
+/// rendered html when their value is `false`.
+///
+/// <https://html.spec.whatwg.org/multipage/indices.html#attributes-3>
+pub const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+    "shadowrootclonable",
+    "shadowrootdelegatesfocus",
+    "shadowrootserializable",
+];
+
+/// How void elements such as `<br>`, `<img>` and `<input>` are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoidElementStyle {
+    /// close the void elements with `/>`, ie: `<br/>`
+    Xhtml,
+    /// leave the void elements unclosed, ie: `<br>`.
+    /// Elements in a foreign namespace such as svg are still closed with `/>`
+    Html5,
+}
+
+/// The order in which the attributes of an element are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOrder {
+    /// in the order the attributes are specified in the view
+    Source,
+    /// sorted alphabetically by the attribute name, useful for stable snapshot tests
+    Alphabetical,
+}
+
+/// The line break used in pretty printed html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Newline {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl Newline {
+    /// return the characters of this line break
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+impl fmt::Display for Newline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Options to configure how a node is rendered into html.
+///
+/// The default renders the same output as [`Node::render`](crate::vdom::Node::render)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// render everything in one line, without any newlines and indentation
+    pub compressed: bool,
+    /// the character used for indenting the nested nodes
+    pub indent_char: char,
+    /// the number of `indent_char` for each level of nesting
+    pub indent_width: usize,
+    /// the line break inserted in between nodes
+    pub newline: Newline,
+    /// how void elements are written
+    pub void_element_style: VoidElementStyle,
+    /// the attributes which are omitted when their value is `false`
+    pub boolean_attributes: &'static [&'static str],
+    /// the order in which the attributes of an element are rendered
+    pub attribute_order: AttributeOrder,
+}
+
+impl RenderOptions {
+    /// no newlines and no indents, this is what [`Node::render_to_string`](crate::vdom::Node::render_to_string) uses
+    pub fn compressed() -> Self {
+        Self {
+            compressed: true,
+            ..Default::default()
+        }
+    }
+
+    /// write the newline followed by the indentation for the nesting level,
+    /// if the output is not compressed
+    pub(crate) fn write_indent(&self, buffer: &mut dyn fmt::Write, indent: usize) -> fmt::Result {
+        if !self.compressed {
+            buffer.write_str(self.newline.as_str())?;
+            for _ in 0..indent * self.indent_width {
+                buffer.write_char(self.indent_char)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            compressed: false,
+            indent_char: ' ',
+            indent_width: 2,
+            newline: Newline::Lf,
+            void_element_style: VoidElementStyle::Xhtml,
+            boolean_attributes: BOOLEAN_ATTRIBUTES,
+            attribute_order: AttributeOrder::Source,
+        }
+    }
+}
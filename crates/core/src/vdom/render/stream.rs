@@ -0,0 +1,197 @@
+//! Streaming server side rendering of the virtual dom.
+//!
+//! The html is rendered incrementally into chunks, which are yielded at the configured
+//! [`FlushPoints`], so the server can send the first bytes of the document to the browser
+//! before the whole page is rendered.
+use super::{RenderOptions, RAW_TEXT_ELEMENTS};
+use crate::vdom::{Element, Leaf, Node};
+use futures::Stream;
+use std::fmt;
+use std::io;
+
+/// Specifies at which points of the document the streaming renderer yields
+/// the html that has been rendered so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlushPoints {
+    /// flush right after the closing `</head>` of a html document,
+    /// so the browser can start loading the stylesheets and scripts early
+    pub after_head: bool,
+    /// flush after each child of the `<body>`, or after each child of the root element
+    /// if the node is not a html document
+    pub after_each_child: bool,
+}
+
+impl FlushPoints {
+    /// render the whole node into a single chunk
+    pub fn none() -> Self {
+        Self {
+            after_head: false,
+            after_each_child: false,
+        }
+    }
+}
+
+impl Default for FlushPoints {
+    fn default() -> Self {
+        Self {
+            after_head: true,
+            after_each_child: true,
+        }
+    }
+}
+
+/// A part of the node tree that is rendered in one go
+enum Segment<'a, MSG> {
+    StartTag(&'a Element<MSG>),
+    EndTag(&'a Element<MSG>),
+    Element(&'a Element<MSG>),
+    Node(&'a Node<MSG>),
+    Flush,
+}
+
+/// An iterator over the rendered html chunks of a node.
+/// Each chunk is only rendered when it is requested.
+pub struct RenderChunks<'a, MSG> {
+    segments: std::vec::IntoIter<Segment<'a, MSG>>,
+    options: RenderOptions,
+}
+
+impl<'a, MSG> RenderChunks<'a, MSG> {
+    fn new(node: &'a Node<MSG>, flush_points: FlushPoints) -> Self {
+        let mut segments = vec![];
+        push_document_segments(&mut segments, node, flush_points);
+        Self {
+            segments: segments.into_iter(),
+            options: RenderOptions::compressed(),
+        }
+    }
+
+    fn render_segment(
+        segment: &Segment<'a, MSG>,
+        buffer: &mut String,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        match segment {
+            Segment::StartTag(element) => element.render_start_tag(buffer, options),
+            Segment::EndTag(element) => element.render_end_tag(buffer),
+            Segment::Element(element) => element.render_indented(buffer, 0, options),
+            Segment::Node(node) => node.render_indented(buffer, 0, options),
+            Segment::Flush => Ok(()),
+        }
+    }
+}
+
+impl<'a, MSG> Iterator for RenderChunks<'a, MSG> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let mut buffer = String::new();
+        for segment in self.segments.by_ref() {
+            if let Segment::Flush = segment {
+                if !buffer.is_empty() {
+                    return Some(buffer);
+                }
+            } else {
+                Self::render_segment(&segment, &mut buffer, &self.options).expect("must render");
+            }
+        }
+        if buffer.is_empty() {
+            None
+        } else {
+            Some(buffer)
+        }
+    }
+}
+
+/// split the top-level of the node into segments, placing the flush points in between them
+fn push_document_segments<'a, MSG>(
+    segments: &mut Vec<Segment<'a, MSG>>,
+    node: &'a Node<MSG>,
+    flush_points: FlushPoints,
+) {
+    match node {
+        Node::Leaf(Leaf::NodeList(nodes)) | Node::Leaf(Leaf::Fragment(nodes)) => {
+            for node in nodes {
+                push_document_segments(segments, node, flush_points);
+            }
+        }
+        Node::Leaf(Leaf::StatelessComponent(comp)) => {
+            push_document_segments(segments, &comp.view, flush_points)
+        }
+        Node::Leaf(Leaf::TemplatedView(view)) => {
+            push_document_segments(segments, &view.view, flush_points)
+        }
+        Node::Leaf(Leaf::LazyView(view)) => {
+            push_document_segments(segments, view.view(), flush_points)
+        }
+        Node::Element(element) if element.tag() == "html" => {
+            segments.push(Segment::StartTag(element));
+            for child in element.children() {
+                match child.tag() {
+                    Some("head") => {
+                        segments.push(Segment::Node(child));
+                        if flush_points.after_head {
+                            segments.push(Segment::Flush);
+                        }
+                    }
+                    Some("body") => {
+                        let body = child.element_ref().expect("must be an element");
+                        push_children_segments(segments, body, flush_points);
+                    }
+                    _ => segments.push(Segment::Node(child)),
+                }
+            }
+            segments.push(Segment::EndTag(element));
+        }
+        Node::Element(element) => push_children_segments(segments, element, flush_points),
+        Node::Leaf(_) => segments.push(Segment::Node(node)),
+    }
+}
+
+/// split the element into its start tag, each of its child nodes and its end tag
+fn push_children_segments<'a, MSG>(
+    segments: &mut Vec<Segment<'a, MSG>>,
+    element: &'a Element<MSG>,
+    flush_points: FlushPoints,
+) {
+    // the content of raw text elements is rendered differently, so it is not split
+    if element.self_closing || RAW_TEXT_ELEMENTS.contains(&element.tag()) {
+        segments.push(Segment::Element(element));
+        return;
+    }
+    segments.push(Segment::StartTag(element));
+    for (i, child) in element.children().iter().enumerate() {
+        // the closing tags are sent together with the last child
+        if i > 0 && flush_points.after_each_child {
+            segments.push(Segment::Flush);
+        }
+        segments.push(Segment::Node(child));
+    }
+    segments.push(Segment::EndTag(element));
+}
+
+impl<MSG> Node<MSG> {
+    /// render the node into compressed html chunks, which are split at the `flush_points`.
+    /// Concatenating all the chunks yields the same html as [`Node::render_to_string`].
+    pub fn render_chunks(&self, flush_points: FlushPoints) -> RenderChunks<'_, MSG> {
+        RenderChunks::new(self, flush_points)
+    }
+
+    /// render the node as a stream of compressed html chunks, which are split at the `flush_points`
+    pub fn render_to_stream(&self, flush_points: FlushPoints) -> impl Stream<Item = String> + '_ {
+        futures::stream::iter(self.render_chunks(flush_points))
+    }
+
+    /// render the node as compressed html into the writer, which is flushed at each of the `flush_points`
+    pub fn render_to_writer(
+        &self,
+        writer: &mut dyn io::Write,
+        flush_points: FlushPoints,
+    ) -> io::Result<()> {
+        for chunk in self.render_chunks(flush_points) {
+            writer.write_all(chunk.as_bytes())?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
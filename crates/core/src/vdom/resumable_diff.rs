@@ -0,0 +1,102 @@
+//! Diffing which can be paused and resumed later,
+//! so diffing a very large view doesn't block the browser from handling the user input.
+use super::diff::{diff_node, Deferred};
+use super::{Node, Patch, TreePath};
+use crate::dom::{SkipDiff, SkipPath};
+
+/// A diff of an old node and a new node which is done one node at a time,
+/// and can be paused in between the nodes and resumed later.
+///
+/// The old and new nodes must be the same on every call to [`ResumableDiff::resume`],
+/// if either of them changed, the unfinished diff has to be discarded and a new one started.
+/// The patches of all the calls combined are the same patches returned by [`diff`](super::diff),
+/// although in a different order. Since the patch paths refer to the nodes in the old tree,
+/// the patches have to be applied together after the diff is complete.
+///
+/// # Example
+/// ```rust
+/// use sauron::{*, vdom::ResumableDiff};
+///
+/// let old: Node<()> = ul([], [li([], [text("one")]), li([], [text("two")])]);
+/// let new: Node<()> = ul([], [li([], [text("uno")]), li([], [text("dos")])]);
+///
+/// let mut resumable = ResumableDiff::new();
+/// let mut patches = vec![];
+/// while !resumable.is_complete() {
+///     // yield after every node
+///     patches.extend(resumable.resume(&old, &new, || true));
+/// }
+/// assert_eq!(patches.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct ResumableDiff {
+    /// the path of the old node and the path of the new node which are yet to be diffed,
+    /// the last one is diffed next
+    pending: Vec<(SkipPath, TreePath)>,
+}
+
+impl ResumableDiff {
+    /// create a diff which starts at the root nodes
+    pub fn new() -> Self {
+        Self {
+            pending: vec![(
+                SkipPath {
+                    path: TreePath::root(),
+                    skip_diff: None,
+                },
+                TreePath::root(),
+            )],
+        }
+    }
+
+    /// create a diff which starts at the root nodes, skipping the nodes and attributes
+    /// that can not change according to the `skip_diff` of a templated view
+    pub fn with_skip_diff(skip_diff: SkipDiff) -> Self {
+        Self {
+            pending: vec![(SkipPath::new(TreePath::root(), skip_diff), TreePath::root())],
+        }
+    }
+
+    /// returns true if all the nodes are diffed
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// continue diffing the nodes, until `should_yield` returns true or the diff is complete.
+    /// `should_yield` is checked after each node, so at least one node is diffed on every call.
+    ///
+    /// Returns the patches of the nodes diffed in this call.
+    pub fn resume<'a, MSG>(
+        &mut self,
+        old_node: &'a Node<MSG>,
+        new_node: &'a Node<MSG>,
+        mut should_yield: impl FnMut() -> bool,
+    ) -> Vec<Patch<'a, MSG>> {
+        let mut patches = vec![];
+        while let Some((path, new_path)) = self.pending.pop() {
+            let old = old_node
+                .find_view_node(&path.path.path)
+                .expect("must find the old node");
+            let new = new_node
+                .find_view_node(&new_path.path)
+                .expect("must find the new node");
+            let mut deferred = Deferred {
+                new_path,
+                children: vec![],
+            };
+            patches.extend(diff_node(old, new, &path, Some(&mut deferred)));
+            // the children are diffed in the order they are found
+            self.pending.extend(deferred.children.into_iter().rev());
+            if should_yield() {
+                break;
+            }
+        }
+        patches
+    }
+}
+
+impl Default for ResumableDiff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,499 @@
+//! Find the elements in a virtual dom tree with css selectors,
+//! the same way `querySelector` and `querySelectorAll` does in the browser.
+//!
+//! The supported selectors are:
+//! - type selector `div` and the universal selector `*`
+//! - id selector `#id` and class selector `.class`
+//! - attribute selectors `[attr]` and `[attr=value]`, the value can be quoted
+//! - the `:nth-child(an+b)` pseudo class, including `odd` and `even`
+//! - descendant combinator `div p` and child combinator `div > p`
+//! - a list of selectors separated by comma `h1, h2`
+use super::{Attribute, Element, Node, Style, TreePath, Value, BOOLEAN_ATTRIBUTES};
+
+/// An error when parsing a css selector
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SelectorError {
+    /// the selector or a selector in the list is empty
+    #[error("empty selector")]
+    Empty,
+    /// a character which is not expected at this position of the selector
+    #[error("unexpected `{found}` at position {position} of the selector")]
+    UnexpectedChar {
+        /// the character found
+        found: char,
+        /// the character position in the selector
+        position: usize,
+    },
+    /// the selector ends before it is complete
+    #[error("unexpected end of the selector")]
+    UnexpectedEnd,
+    /// a pseudo class other than `:nth-child`
+    #[error("unsupported pseudo class `:{0}`")]
+    UnsupportedPseudoClass(String),
+    /// the argument of `:nth-child` is not `odd`, `even` or in the form of `an+b`
+    #[error("invalid `:nth-child` argument `{0}`")]
+    InvalidNth(String),
+}
+
+impl<MSG> Node<MSG> {
+    /// return the first element matching the css `selector` together with its path,
+    /// in the order of the elements in the tree.
+    ///
+    /// This node itself is also matched, as if this node is in a document.
+    /// The path of the element is located the same way the patch paths are.
+    ///
+    /// # Example
+    /// ```rust
+    /// use sauron::*;
+    ///
+    /// let view: Node<()> = main([], [ul([class("list")], [li([], [text("one")]), li([], [text("two")])])]);
+    /// let (node, path) = view.query_selector(".list > li:nth-child(2)").unwrap().unwrap();
+    /// assert_eq!(node.render_to_string(), "<li>two</li>");
+    /// assert_eq!(path, TreePath::new([0, 1]));
+    /// ```
+    pub fn query_selector(
+        &self,
+        selector: &str,
+    ) -> Result<Option<(&Node<MSG>, TreePath)>, SelectorError> {
+        Ok(self.query_selector_all(selector)?.into_iter().next())
+    }
+
+    /// return all the elements matching the css `selector` together with their path,
+    /// in the order of the elements in the tree. See [`Node::query_selector`]
+    pub fn query_selector_all(
+        &self,
+        selector: &str,
+    ) -> Result<Vec<(&Node<MSG>, TreePath)>, SelectorError> {
+        let selectors = Parser::new(selector).parse_selector_list()?;
+        let mut found = vec![];
+        let mut position = 0;
+        find_matches(
+            self,
+            TreePath::root(),
+            &selectors,
+            &mut vec![],
+            &mut position,
+            &mut found,
+        );
+        Ok(found)
+    }
+}
+
+/// An element in the tree, with its position among the sibling elements
+struct Scope<'a, MSG> {
+    element: &'a Element<MSG>,
+    /// the 1-based position, as counted in `:nth-child`
+    position: usize,
+}
+
+/// find the elements matching any of the selectors in this node and its descendants.
+/// Fragments, node lists and components are not elements, their child nodes are located in
+/// their parent element, the same way they are mounted in the DOM.
+fn find_matches<'a, MSG>(
+    node: &'a Node<MSG>,
+    path: TreePath,
+    selectors: &[ComplexSelector],
+    ancestors: &mut Vec<Scope<'a, MSG>>,
+    position: &mut usize,
+    found: &mut Vec<(&'a Node<MSG>, TreePath)>,
+) {
+    let node = node.unwrap_view();
+    match node {
+        Node::Element(element) => {
+            *position += 1;
+            let scope = Scope {
+                element,
+                position: *position,
+            };
+            if selectors
+                .iter()
+                .any(|selector| selector.matches(&scope, ancestors))
+            {
+                found.push((node, path.clone()));
+            }
+            ancestors.push(scope);
+            let mut child_position = 0;
+            for (index, child) in element.children().iter().enumerate() {
+                find_matches(
+                    child,
+                    path.traverse(index),
+                    selectors,
+                    ancestors,
+                    &mut child_position,
+                    found,
+                );
+            }
+            ancestors.pop();
+        }
+        Node::Leaf(_) => {
+            for (index, child) in node.children().iter().enumerate() {
+                find_matches(
+                    child,
+                    path.traverse(index),
+                    selectors,
+                    ancestors,
+                    position,
+                    found,
+                );
+            }
+        }
+    }
+}
+
+/// compound selectors joined with combinators, ie: `ul.list > li`
+#[derive(Debug)]
+struct ComplexSelector {
+    compounds: Vec<CompoundSelector>,
+    /// the combinator in between `compounds[i]` and `compounds[i + 1]`
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Combinator {
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+}
+
+/// the selectors which all apply to the same element, ie: `li.item[title=one]`
+#[derive(Debug, Default)]
+struct CompoundSelector {
+    /// the element tag, `None` matches any element
+    tag: Option<String>,
+    ids: Vec<String>,
+    classes: Vec<String>,
+    /// the attribute name and the value to be equal to, if there is
+    attributes: Vec<(String, Option<String>)>,
+    /// the `a` and `b` of `:nth-child(an+b)`
+    nth_children: Vec<(i64, i64)>,
+}
+
+impl ComplexSelector {
+    fn matches<MSG>(&self, scope: &Scope<MSG>, ancestors: &[Scope<MSG>]) -> bool {
+        self.matches_at(self.compounds.len() - 1, scope, ancestors)
+    }
+
+    /// match the compound selector at `index` with the element,
+    /// and the compound selectors before it with its ancestors
+    fn matches_at<MSG>(&self, index: usize, scope: &Scope<MSG>, ancestors: &[Scope<MSG>]) -> bool {
+        if !self.compounds[index].matches(scope) {
+            return false;
+        }
+        if index == 0 {
+            return true;
+        }
+        match self.combinators[index - 1] {
+            Combinator::Child => match ancestors.split_last() {
+                Some((parent, rest)) => self.matches_at(index - 1, parent, rest),
+                None => false,
+            },
+            Combinator::Descendant => (0..ancestors.len())
+                .rev()
+                .any(|i| self.matches_at(index - 1, &ancestors[i], &ancestors[..i])),
+        }
+    }
+}
+
+impl CompoundSelector {
+    fn is_empty(&self) -> bool {
+        self.tag.is_none()
+            && self.ids.is_empty()
+            && self.classes.is_empty()
+            && self.attributes.is_empty()
+            && self.nth_children.is_empty()
+    }
+
+    fn matches<MSG>(&self, scope: &Scope<MSG>) -> bool {
+        let element = scope.element;
+        if let Some(tag) = &self.tag {
+            let is_same_tag = if element.namespace().is_some() {
+                element.tag() == tag
+            } else {
+                element.tag().eq_ignore_ascii_case(tag)
+            };
+            if !is_same_tag {
+                return false;
+            }
+        }
+        let id = attribute_string(element, "id");
+        if !self
+            .ids
+            .iter()
+            .all(|expected| id.as_ref() == Some(expected))
+        {
+            return false;
+        }
+        if !self.classes.is_empty() {
+            let class = attribute_string(element, "class").unwrap_or_default();
+            let class_names: Vec<&str> = class.split_whitespace().collect();
+            if !self
+                .classes
+                .iter()
+                .all(|expected| class_names.contains(&expected.as_str()))
+            {
+                return false;
+            }
+        }
+        let has_attributes = self.attributes.iter().all(|(name, expected)| {
+            match (attribute_string(element, name), expected) {
+                (Some(value), Some(expected)) => value == *expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            }
+        });
+        if !has_attributes {
+            return false;
+        }
+        self.nth_children
+            .iter()
+            .all(|(a, b)| is_nth(*a, *b, scope.position as i64))
+    }
+}
+
+/// returns true if `position` is equal to `a*n + b` for some `n >= 0`
+fn is_nth(a: i64, b: i64, position: i64) -> bool {
+    if a == 0 {
+        position == b
+    } else {
+        let diff = position - b;
+        diff % a == 0 && diff / a >= 0
+    }
+}
+
+/// the value of the attribute as it is rendered into html,
+/// this is None if the element has no such attribute
+fn attribute_string<MSG>(element: &Element<MSG>, name: &str) -> Option<String> {
+    let mut plain_values = vec![];
+    let mut styles = vec![];
+    for attr in element
+        .attributes()
+        .iter()
+        .filter(|attr| *attr.name() == name)
+    {
+        let grouped = Attribute::group_values(attr);
+        plain_values.extend(grouped.plain_values);
+        styles.extend(grouped.styles);
+    }
+    // boolean attributes which are false are not set in the element
+    let is_false = plain_values.first().and_then(|v| v.as_bool()) == Some(false);
+    if BOOLEAN_ATTRIBUTES.contains(&name) && is_false {
+        return None;
+    }
+    Value::merge_to_string(plain_values).or_else(|| Style::merge_to_string(styles))
+}
+
+/// parse the css selector
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Parser {
+    fn new(selector: &str) -> Self {
+        Self {
+            chars: selector.chars().collect(),
+            index: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn unexpected(&self) -> SelectorError {
+        match self.peek() {
+            Some(found) => SelectorError::UnexpectedChar {
+                found,
+                position: self.index,
+            },
+            None => SelectorError::UnexpectedEnd,
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SelectorError> {
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    /// skip the whitespace, returns true if there is any
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.index;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+        self.index > start
+    }
+
+    fn parse_selector_list(&mut self) -> Result<Vec<ComplexSelector>, SelectorError> {
+        let mut selectors = vec![self.parse_complex()?];
+        while self.peek() == Some(',') {
+            self.index += 1;
+            selectors.push(self.parse_complex()?);
+        }
+        match self.peek() {
+            None => Ok(selectors),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_complex(&mut self) -> Result<ComplexSelector, SelectorError> {
+        self.skip_whitespace();
+        if matches!(self.peek(), None | Some(',')) {
+            return Err(SelectorError::Empty);
+        }
+        let mut compounds = vec![self.parse_compound()?];
+        let mut combinators = vec![];
+        loop {
+            let has_whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                None | Some(',') => break,
+                Some('>') => {
+                    self.index += 1;
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some(_) if has_whitespace => Combinator::Descendant,
+                Some(_) => return Err(self.unexpected()),
+            };
+            combinators.push(combinator);
+            compounds.push(self.parse_compound()?);
+        }
+        Ok(ComplexSelector {
+            compounds,
+            combinators,
+        })
+    }
+
+    fn parse_compound(&mut self) -> Result<CompoundSelector, SelectorError> {
+        let mut compound = CompoundSelector::default();
+        let is_universal = self.peek() == Some('*');
+        if is_universal {
+            self.index += 1;
+        } else if self.peek().is_some_and(is_ident_char) {
+            compound.tag = Some(self.parse_ident()?);
+        }
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.index += 1;
+                    compound.ids.push(self.parse_ident()?);
+                }
+                Some('.') => {
+                    self.index += 1;
+                    compound.classes.push(self.parse_ident()?);
+                }
+                Some('[') => {
+                    self.index += 1;
+                    compound.attributes.push(self.parse_attribute()?);
+                }
+                Some(':') => {
+                    self.index += 1;
+                    compound.nth_children.push(self.parse_nth_child()?);
+                }
+                _ => break,
+            }
+        }
+        if compound.is_empty() && !is_universal {
+            return Err(self.unexpected());
+        }
+        Ok(compound)
+    }
+
+    fn parse_ident(&mut self) -> Result<String, SelectorError> {
+        let start = self.index;
+        while self.peek().is_some_and(is_ident_char) {
+            self.index += 1;
+        }
+        if self.index == start {
+            return Err(self.unexpected());
+        }
+        Ok(self.chars[start..self.index].iter().collect())
+    }
+
+    /// parse the attribute selector after the `[`
+    fn parse_attribute(&mut self) -> Result<(String, Option<String>), SelectorError> {
+        self.skip_whitespace();
+        let name = self.parse_ident()?;
+        self.skip_whitespace();
+        let value = if self.peek() == Some('=') {
+            self.index += 1;
+            self.skip_whitespace();
+            let value = match self.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    self.index += 1;
+                    let start = self.index;
+                    while self.peek().is_some_and(|c| c != quote) {
+                        self.index += 1;
+                    }
+                    let value = self.chars[start..self.index].iter().collect();
+                    self.expect(quote)?;
+                    value
+                }
+                _ => self.parse_ident()?,
+            };
+            self.skip_whitespace();
+            Some(value)
+        } else {
+            None
+        };
+        self.expect(']')?;
+        Ok((name, value))
+    }
+
+    /// parse the pseudo class after the `:`, only `:nth-child` is supported
+    fn parse_nth_child(&mut self) -> Result<(i64, i64), SelectorError> {
+        let name = self.parse_ident()?;
+        if name != "nth-child" {
+            return Err(SelectorError::UnsupportedPseudoClass(name));
+        }
+        self.expect('(')?;
+        let start = self.index;
+        while self.peek().is_some_and(|c| c != ')') {
+            self.index += 1;
+        }
+        let argument: String = self.chars[start..self.index].iter().collect();
+        self.expect(')')?;
+        parse_nth(&argument).ok_or(SelectorError::InvalidNth(argument))
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// parse the `an+b` argument of `:nth-child`
+fn parse_nth(argument: &str) -> Option<(i64, i64)> {
+    let argument: String = argument
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match argument.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => (),
+    }
+    match argument.split_once('n') {
+        Some((a, b)) => {
+            let a = match a {
+                "" | "+" => 1,
+                "-" => -1,
+                a => a.parse().ok()?,
+            };
+            let b = if b.is_empty() {
+                0
+            } else if b.starts_with(['+', '-']) {
+                b.parse().ok()?
+            } else {
+                return None;
+            };
+            Some((a, b))
+        }
+        None => Some((0, argument.parse().ok()?)),
+    }
+}
@@ -1,17 +1,24 @@
 #![deny(warnings)]
 use rphtml::config::ParseOptions;
 use rphtml::parser::Doc;
+use rphtml::parser::DocHolder;
 use rphtml::parser::NodeType;
 use rphtml::types::BoxDynError;
 use sauron_core::{
     html::{attributes::*, lookup, *},
+    svg::attributes::XLINK_NAMESPACE,
     vdom::AttributeValue,
+    vdom::Leaf,
     vdom::Node,
     vdom::Value,
+    vdom::{AttributeName, Namespace, Tag},
 };
 use std::fmt;
 use std::io;
 use std::ops::Deref;
+pub use well_formed::{Location, Malformed};
+
+mod well_formed;
 
 /// all the possible error when parsing html string
 #[derive(Debug, thiserror::Error)]
@@ -23,25 +30,137 @@ pub enum ParseError {
     #[error("{0}")]
     FmtError(#[from] fmt::Error),
     /// rphtml specific error
-    #[error("{0}")]
-    RpHtmlError(#[from] BoxDynError),
+    #[error("{source}")]
+    RpHtmlError {
+        /// the error returned by rphtml
+        source: BoxDynError,
+        /// the location where rphtml stopped parsing,
+        /// this is `None` when the error is not a parse error of the html
+        location: Option<Location>,
+    },
     /// the tag is not a valid html
-    #[error("Invalid tag: {0}")]
-    InvalidTag(String),
+    #[error("Invalid tag: `{tag}` at {location}")]
+    InvalidTag {
+        /// the name of the tag
+        tag: String,
+        /// the location of the start tag
+        location: Location,
+    },
+    /// the tags are not properly closed or nested, only returned by [`parse_html_strict`]
+    #[error("{0}")]
+    Malformed(#[from] Malformed),
 }
 
-/// parse the html string and build a node tree
+/// parse the html string and build a node tree.
+/// When the html can not be parsed, the error is logged and the html is displayed as text
 pub fn raw_html<MSG>(html: &str) -> Node<MSG> {
-    // decode html entitiesd back since it will be safely converted into text
-    let html = html_escape::decode_html_entities(html);
-    parse_html(&html)
-        .expect("must be ok")
-        .expect("must have a node")
+    match parse_html(html) {
+        Ok(node) => node.unwrap_or_else(|| node_list([])),
+        Err(err) => {
+            log::error!("unable to parse html: {err}");
+            text(html)
+        }
+    }
 }
 
 /// the document is not wrapped with html
 pub fn parse_html<MSG>(html: &str) -> Result<Option<Node<MSG>>, ParseError> {
-    let doc = Doc::parse(
+    let doc = parse_doc(html)?;
+    process_node(doc.get_root_node().borrow().deref(), Context::new(html))
+}
+
+/// parse the html, rejecting the unclosed and misnested tags instead of fixing them.
+/// The error contains the line and column of the first offending tag.
+/// All the elements need an end tag, except the void elements such as `<br>` and the self closing tags.
+///
+/// # Example
+/// ```rust
+/// use sauron_html_parser::{parse_html_strict, Malformed, ParseError};
+/// use sauron_core::vdom::Node;
+///
+/// let result: Result<Option<Node<()>>, _> = parse_html_strict("<div>\n  <p>hello</div>");
+/// let Err(ParseError::Malformed(Malformed::UnclosedTag { tag, location })) = result else {
+///     panic!("must be an unclosed tag");
+/// };
+/// assert_eq!(tag, "p");
+/// assert_eq!((location.line, location.column), (2, 3));
+/// assert_eq!(location.snippet, "<p>");
+/// ```
+pub fn parse_html_strict<MSG>(html: &str) -> Result<Option<Node<MSG>>, ParseError> {
+    if let Some(malformed) = well_formed::check(html).into_iter().next() {
+        return Err(malformed.into());
+    }
+    parse_html(html)
+}
+
+/// parse the html, fixing the unclosed and misnested tags the same way as [`parse_html`],
+/// while returning them as warnings together with the node tree
+pub fn parse_html_lenient<MSG>(
+    html: &str,
+) -> Result<(Option<Node<MSG>>, Vec<Malformed>), ParseError> {
+    let warnings = well_formed::check(html);
+    Ok((parse_html(html)?, warnings))
+}
+
+/// parse a complete html document, returning its doctype and the whole `<html>` element
+/// in a node list, the same way a document is created in `document_view`.
+/// When the document has no `<html>` element, its `<head>` and the rest of its nodes
+/// are wrapped into one.
+///
+/// # Example
+/// ```rust
+/// use sauron_html_parser::parse_document;
+/// use sauron_core::vdom::Node;
+///
+/// let document: Node<()> = parse_document(
+///     "<!doctype html><html><head><title>Hi</title></head><body>Hello</body></html>",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     document.render_to_string(),
+///     "<!doctype html><html><head><title>Hi</title></head><body>Hello</body></html>"
+/// );
+/// ```
+pub fn parse_document<MSG>(document: &str) -> Result<Node<MSG>, ParseError> {
+    let doc = parse_doc(document)?;
+    let root_node = doc.get_root_node();
+    let root_node = root_node.borrow();
+    let mut prolog = vec![];
+    let mut html_element = None;
+    let mut head_element = None;
+    let mut body_nodes = vec![];
+    for child in root_node.childs.iter().flatten() {
+        let Some(node) = process_node(child.borrow().deref(), Context::new(document))? else {
+            continue;
+        };
+        match node.tag() {
+            Some("html") if html_element.is_none() => html_element = Some(node),
+            Some("head") if head_element.is_none() => head_element = Some(node),
+            Some(_) => body_nodes.push(node),
+            None => match node {
+                Node::Leaf(Leaf::DocType(_)) | Node::Leaf(Leaf::Comment(_)) => prolog.push(node),
+                Node::Leaf(Leaf::Text(ref txt)) if txt.trim().is_empty() => (),
+                _ => body_nodes.push(node),
+            },
+        }
+    }
+    let html_element = html_element.unwrap_or_else(|| {
+        let body_element = if body_nodes.len() == 1 && body_nodes[0].tag() == Some("body") {
+            body_nodes.remove(0)
+        } else {
+            body([], body_nodes)
+        };
+        html(
+            [],
+            [head_element.unwrap_or_else(|| head([], [])), body_element],
+        )
+    });
+    prolog.push(html_element);
+    Ok(node_list(prolog))
+}
+
+fn parse_doc(html: &str) -> Result<DocHolder, ParseError> {
+    Doc::parse(
         html,
         ParseOptions {
             case_sensitive_tagname: false,
@@ -50,76 +169,129 @@ pub fn parse_html<MSG>(html: &str) -> Result<Option<Node<MSG>>, ParseError> {
             auto_fix_unexpected_endtag: true,
             auto_fix_unescaped_lt: true,
         },
-    )?;
-    process_node(doc.get_root_node().borrow().deref())
+    )
+    .map_err(|source| ParseError::RpHtmlError {
+        location: rphtml_error_index(&source).map(|index| Location::at_char_index(html, index)),
+        source,
+    })
 }
 
-//TODO: This is not dealing with html symbols such as
-//   `&#9650;`
-//   `&#9660;`
-fn process_node<MSG>(node: &rphtml::parser::Node) -> Result<Option<Node<MSG>>, ParseError> {
-    let content = if let Some(content) = &node.content {
-        let content = String::from_iter(content.iter());
-        Some(content)
-    } else {
-        None
-    };
+/// the char index where rphtml stopped parsing.
+/// rphtml doesn't expose the fields of the region of the error, only its display,
+/// ie: `[line:1,col:5,index:4]`
+fn rphtml_error_index(err: &BoxDynError) -> Option<usize> {
+    let err = err.downcast_ref::<rphtml::error::ParseError>()?;
+    let region = err.region.to_string();
+    let (_, index) = region.rsplit_once("index:")?;
+    index.trim_end_matches(']').parse().ok()
+}
 
-    let mut child_nodes = if let Some(childs) = &node.childs {
-        childs
-            .iter()
-            .flat_map(|child| process_node(child.borrow().deref()).ok().flatten())
-            .collect()
-    } else {
-        vec![]
-    };
+/// the context of the parent element of the node being converted
+#[derive(Debug, Clone, Copy)]
+struct Context<'a> {
+    /// the html being parsed, for locating the invalid tags
+    html: &'a str,
+    /// the namespace of the child elements, ie: the svg namespace inside of `<svg>`
+    namespace: Option<&'static str>,
+}
+
+impl<'a> Context<'a> {
+    fn new(html: &'a str) -> Self {
+        Self {
+            html,
+            namespace: None,
+        }
+    }
+}
+
+/// convert the rphtml node into a vdom node.
+/// The html entities such as `&amp;`, `&nbsp;` and `&#9650;` are decoded in the text and attribute values,
+/// except in the text of html `script` and `style`.
+fn process_node<MSG>(
+    node: &rphtml::parser::Node,
+    context: Context,
+) -> Result<Option<Node<MSG>>, ParseError> {
+    let content = node
+        .content
+        .as_ref()
+        .map(|content| String::from_iter(content.iter()));
 
     match node.node_type {
         NodeType::Tag => {
             let tag = &node.meta.as_ref().expect("must have a tag");
             let tag_name = String::from_iter(tag.borrow().name.iter());
-            if let Some(html_tag) = lookup::match_tag(&tag_name) {
-                let is_self_closing = HTML_SC_TAGS.contains(&html_tag);
-                let attributes: Vec<Attribute<MSG>> = tag
-                    .borrow()
-                    .attrs
-                    .iter()
-                    .filter_map(|attr| {
-                        attr.key.as_ref().and_then(|key| {
-                            let key = String::from_iter(key.content.iter());
-                            if let Some(attr_key) = lookup::match_attribute(&key) {
-                                let value = if let Some(value) = &attr.value {
-                                    let value = String::from_iter(value.content.iter());
-                                    AttributeValue::Simple(Value::from(value))
-                                } else {
-                                    AttributeValue::Empty
-                                };
-                                Some(Attribute::new(None, attr_key, value))
-                            } else {
-                                log::warn!("Not a standard html attribute: {}", key);
-                                None
-                            }
-                        })
+            if tag_name.is_empty() {
+                return Err(ParseError::InvalidTag {
+                    tag: tag_name,
+                    location: Location::at_char_index(context.html, node.begin_at),
+                });
+            }
+            // custom elements are kept as is
+            let html_tag = lookup::match_tag(&tag_name)
+                .map(Tag::from)
+                .unwrap_or_else(|| Tag::from(tag_name));
+            let namespace = lookup::element_namespace(&html_tag, context.namespace);
+            // only html elements can be void elements
+            let is_self_closing = namespace.is_none() && HTML_SC_TAGS.contains(&html_tag.as_ref());
+            let child_context = Context {
+                namespace: lookup::children_namespace(&html_tag, namespace),
+                ..context
+            };
+            let attributes: Vec<Attribute<MSG>> = tag
+                .borrow()
+                .attrs
+                .iter()
+                .filter_map(|attr| {
+                    attr.key.as_ref().map(|key| {
+                        let key = String::from_iter(key.content.iter());
+                        // non-standard attributes such as `data-*` and `aria-*` are kept as is
+                        let attr_key = lookup::match_attribute(&key)
+                            .map(AttributeName::from)
+                            .unwrap_or_else(|| AttributeName::from(key));
+                        // the `xlink:` attributes such as `xlink:href` are set with the xlink namespace
+                        let attr_namespace = attr_key
+                            .starts_with("xlink:")
+                            .then_some(Namespace::Borrowed(XLINK_NAMESPACE));
+                        let value = if let Some(value) = &attr.value {
+                            let value = String::from_iter(value.content.iter());
+                            AttributeValue::Simple(Value::from(decode_entities(&value)))
+                        } else {
+                            AttributeValue::Empty
+                        };
+                        Attribute::new(attr_namespace, attr_key, value)
                     })
-                    .collect();
-
-                Ok(Some(html_element(
-                    None,
-                    html_tag,
-                    attributes,
-                    child_nodes,
-                    is_self_closing,
-                )))
-            } else {
-                log::error!("invalid tag: {}", tag_name);
-                Err(ParseError::InvalidTag(tag_name))
+                })
+                .collect();
+
+            // rphtml keeps the text of `title`, `script`, `style` and `textarea`
+            // in the content of the tag instead of its child nodes
+            let mut children: Vec<Node<MSG>> = vec![];
+            if let Some(content) = content.filter(|content| !content.is_empty()) {
+                // the text of `script` and `style` inside svg is parsed as normal text
+                if namespace.is_none() && matches!(html_tag.as_ref(), "script" | "style") {
+                    children.push(text(content));
+                } else {
+                    children.push(text(decode_entities(&content)));
+                }
             }
+            children.extend(process_child_nodes(node, child_context));
+
+            Ok(Some(html_element(
+                namespace.map(Namespace::from),
+                html_tag,
+                attributes,
+                children,
+                is_self_closing,
+            )))
         }
         NodeType::Text => {
             let content = content.expect("must have a content");
-            Ok(Some(text(content)))
+            Ok(Some(text(decode_entities(&content))))
         }
+        NodeType::Comment => Ok(Some(comment(content.unwrap_or_default()))),
+        NodeType::HTMLDOCTYPE => Ok(Some(doctype(doctype_content(node, content)))),
         NodeType::AbstractRoot => {
+            let mut child_nodes = process_child_nodes(node, context);
             let child_nodes_len = child_nodes.len();
             match child_nodes_len {
                 0 => Ok(Some(node_list([]))),
@@ -130,3 +302,61 @@ fn process_node<MSG>(node: &rphtml::parser::Node) -> Result<Option<Node<MSG>>, P
         _ => Ok(None),
     }
 }
+
+fn process_child_nodes<MSG>(node: &rphtml::parser::Node, context: Context) -> Vec<Node<MSG>> {
+    if let Some(childs) = &node.childs {
+        childs
+            .iter()
+            .flat_map(|child| process_node(child.borrow().deref(), context).ok().flatten())
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+/// decode the named and numeric html entities
+fn decode_entities(value: &str) -> String {
+    html_escape::decode_html_entities(value).into_owned()
+}
+
+/// the content of the doctype after the `<!doctype` keyword, ie: `html`.
+/// rphtml parses the parts of the doctype as the attributes of the tag,
+/// the quoted public and system identifiers are kept with their quotes.
+fn doctype_content(node: &rphtml::parser::Node, content: Option<String>) -> String {
+    if let Some(meta) = &node.meta {
+        meta.borrow()
+            .attrs
+            .iter()
+            .map(|attr| {
+                let key = attr
+                    .key
+                    .as_ref()
+                    .map(|key| String::from_iter(key.content.iter()));
+                let value = attr.value.as_ref().map(|value| {
+                    let value = String::from_iter(value.content.iter());
+                    match attr.quote {
+                        Some(quote) => format!("{quote}{value}{quote}"),
+                        None => value,
+                    }
+                });
+                match (key, value) {
+                    (Some(key), Some(value)) => format!("{key}={value}"),
+                    (Some(key), None) => key,
+                    (None, Some(value)) => value,
+                    (None, None) => String::new(),
+                }
+            })
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        let content = content.unwrap_or_default();
+        let content = content.trim();
+        content
+            .get(..7)
+            .filter(|keyword| keyword.eq_ignore_ascii_case("doctype"))
+            .map(|_| content[7..].trim())
+            .unwrap_or(content)
+            .to_string()
+    }
+}
@@ -0,0 +1,226 @@
+//! Check the nesting of the tags in the html, before it is auto-fixed by the parser,
+//! so the unclosed and misnested tags can be reported with their location.
+use sauron_core::html::lookup;
+use std::fmt;
+
+/// the max number of chars of the offending html shown in the snippet
+const MAX_SNIPPET_LEN: usize = 60;
+
+/// the elements which content is not parsed as html
+const RAW_TEXT_TAGS: &[&str] = &["script", "style", "textarea", "title"];
+
+/// The location of the malformed html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// the line number, starting from 1
+    pub line: usize,
+    /// the column in chars, starting from 1
+    pub column: usize,
+    /// the offending html, ie: the start tag which is not closed
+    pub snippet: String,
+}
+
+impl Location {
+    /// the location of the html in between the byte `start` and `end`
+    fn new(html: &str, start: usize, end: usize) -> Self {
+        let before = &html[..start];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let snippet = &html[start..end];
+        let snippet = if snippet.chars().count() > MAX_SNIPPET_LEN {
+            let truncated: String = snippet.chars().take(MAX_SNIPPET_LEN).collect();
+            format!("{truncated}...")
+        } else {
+            snippet.to_string()
+        };
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet,
+        }
+    }
+
+    /// the location of the tag starting at the char `index`, as reported by rphtml
+    pub(crate) fn at_char_index(html: &str, index: usize) -> Self {
+        let start = html
+            .char_indices()
+            .nth(index)
+            .map(|(i, _)| i)
+            .unwrap_or(html.len());
+        Self::new(html, start, tag_end(html, start))
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: `{}`",
+            self.line, self.column, self.snippet
+        )
+    }
+}
+
+/// The tags which are not properly closed or nested.
+/// These are rejected with [`crate::parse_html_strict`],
+/// and are returned as warnings with [`crate::parse_html_lenient`] after they are fixed by the parser.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Malformed {
+    /// the element is not closed with an end tag
+    #[error("unclosed tag `{tag}` at {location}")]
+    UnclosedTag {
+        /// the tag of the element
+        tag: String,
+        /// the location of the start tag
+        location: Location,
+    },
+    /// the end tag closes an element while an element inside of it is still open,
+    /// which is closed after it, ie: `<b><i></b></i>`
+    #[error("misnested tag `{tag}`, expecting the end tag of `{expected}` at {location}")]
+    MisnestedTag {
+        /// the tag of the end tag
+        tag: String,
+        /// the tag of the element inside of it, which should be closed first
+        expected: String,
+        /// the location of the end tag
+        location: Location,
+    },
+    /// the end tag has no matching start tag
+    #[error("unexpected end tag `{tag}` at {location}")]
+    UnexpectedEndTag {
+        /// the tag of the end tag
+        tag: String,
+        /// the location of the end tag
+        location: Location,
+    },
+}
+
+impl Malformed {
+    /// the location of the malformed html
+    pub fn location(&self) -> &Location {
+        match self {
+            Self::UnclosedTag { location, .. } => location,
+            Self::MisnestedTag { location, .. } => location,
+            Self::UnexpectedEndTag { location, .. } => location,
+        }
+    }
+}
+
+/// an element which end tag is not found yet
+struct OpenTag {
+    tag: String,
+    start: usize,
+    end: usize,
+}
+
+/// return the unclosed and misnested tags in the html, in the order they appear.
+/// The void elements such as `<br>` and the self closing tags such as `<circle/>` don't need an end tag,
+/// while the elements with optional end tags such as `<p>` and `<li>` are required to be closed.
+pub(crate) fn check(html: &str) -> Vec<Malformed> {
+    let mut issues = vec![];
+    let mut open_tags: Vec<OpenTag> = vec![];
+    // the elements which are closed by the end tag of their ancestor, ie: the `<i>` in `<b><i></b>`.
+    // These are unclosed tags, unless their end tag comes later, which means the tags are misnested
+    let mut implicitly_closed: Vec<(OpenTag, Malformed)> = vec![];
+    let mut index = 0;
+    while let Some(offset) = html[index..].find('<') {
+        let start = index + offset;
+        let rest = &html[start..];
+        if rest.starts_with("<!--") {
+            index = find_after(html, start, "-->");
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            index = find_after(html, start, ">");
+            continue;
+        }
+        let is_end_tag = rest.starts_with("</");
+        let name_start = if is_end_tag { start + 2 } else { start + 1 };
+        let name_end = html[name_start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+            .map(|i| name_start + i)
+            .unwrap_or(html.len());
+        let name = &html[name_start..name_end];
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            // a `<` which is not a tag, ie: `a < b`
+            index = start + 1;
+            continue;
+        }
+        let tag = name.to_ascii_lowercase();
+        let end = tag_end(html, name_end);
+        index = end;
+        if is_end_tag {
+            if let Some(position) = open_tags.iter().rposition(|open| open.tag == tag) {
+                let inner = open_tags.split_off(position + 1);
+                for open in inner {
+                    let misnested = Malformed::MisnestedTag {
+                        tag: tag.clone(),
+                        expected: open.tag.clone(),
+                        location: Location::new(html, start, end),
+                    };
+                    implicitly_closed.push((open, misnested));
+                }
+                open_tags.pop();
+            } else if let Some(position) = implicitly_closed
+                .iter()
+                .rposition(|(open, _)| open.tag == tag)
+            {
+                let (_open, misnested) = implicitly_closed.remove(position);
+                issues.push(misnested);
+            } else {
+                issues.push(Malformed::UnexpectedEndTag {
+                    tag,
+                    location: Location::new(html, start, end),
+                });
+            }
+        } else if !html[..end].ends_with("/>") && !lookup::is_self_closing(&tag) {
+            if RAW_TEXT_TAGS.contains(&tag.as_str()) {
+                // skip the content up to the end tag
+                index = find_ignore_ascii_case(html, end, &format!("</{tag}"));
+            }
+            open_tags.push(OpenTag { tag, start, end });
+        }
+    }
+    let unclosed = implicitly_closed
+        .into_iter()
+        .map(|(open, _)| open)
+        .chain(open_tags);
+    issues.extend(unclosed.map(|open| Malformed::UnclosedTag {
+        location: Location::new(html, open.start, open.end),
+        tag: open.tag,
+    }));
+    issues.sort_by_key(|issue| (issue.location().line, issue.location().column));
+    issues
+}
+
+/// the byte index right after the `pattern` found after `start`, or the end of the html
+fn find_after(html: &str, start: usize, pattern: &str) -> usize {
+    html[start..]
+        .find(pattern)
+        .map(|i| start + i + pattern.len())
+        .unwrap_or(html.len())
+}
+
+/// the byte index of the `pattern` found after `start` ignoring the ascii case, or the end of the html
+fn find_ignore_ascii_case(html: &str, start: usize, pattern: &str) -> usize {
+    let pattern = pattern.as_bytes();
+    html.as_bytes()[start..]
+        .windows(pattern.len())
+        .position(|window| window.eq_ignore_ascii_case(pattern))
+        .map(|i| start + i)
+        .unwrap_or(html.len())
+}
+
+/// the byte index right after the `>` of the tag, skipping the quoted attribute values
+fn tag_end(html: &str, from: usize) -> usize {
+    let mut quote = None;
+    for (i, c) in html[from..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if q == c => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return from + i + 1,
+            (None, _) => (),
+        }
+    }
+    html.len()
+}
@@ -6,20 +6,22 @@ use syn::{Expr, ExprForLoop, ExprIf, Stmt};
 
 pub fn to_token_stream(input: proc_macro::TokenStream) -> TokenStream {
     match rstml::parse(input) {
-        Ok(nodes) => multiple_nodes(nodes),
+        Ok(nodes) => multiple_nodes(nodes, None),
         Err(error) => error.to_compile_error(),
     }
 }
 
-fn multiple_nodes(mut nodes: Vec<Node>) -> TokenStream {
+/// `parent_namespace` is the namespace of the child elements of the parent element,
+/// so the elements inside of `svg` and `math` are created in their namespace
+fn multiple_nodes(mut nodes: Vec<Node>, parent_namespace: Option<&str>) -> TokenStream {
     let only_one_node = nodes.len() == 1;
     if only_one_node {
-        let node_tokens = single_node(nodes.remove(0));
+        let node_tokens = single_node(nodes.remove(0), parent_namespace);
         quote! {
             #node_tokens
         }
     } else {
-        let children_tokens = nodes_to_tokens(nodes);
+        let children_tokens = nodes_to_tokens(nodes, parent_namespace);
         quote! {
             sauron::html::node_list([
                 #children_tokens
@@ -28,18 +30,19 @@ fn multiple_nodes(mut nodes: Vec<Node>) -> TokenStream {
     }
 }
 
-fn single_node(node: Node) -> TokenStream {
+fn single_node(node: Node, parent_namespace: Option<&str>) -> TokenStream {
     match node {
         Node::Element(elm) => {
             let open_tag = elm.open_tag;
             let tag = open_tag.name.to_string();
 
             let self_closing = lookup::is_self_closing(&tag);
-            let namespace = lookup::tag_namespace(&tag);
+            let namespace = lookup::element_namespace(&tag, parent_namespace);
             let attributes = node_attributes(open_tag.attributes);
-            let children = nodes_to_tokens(elm.children);
+            let children =
+                nodes_to_tokens(elm.children, lookup::children_namespace(&tag, namespace));
             let ns = if let Some(namespace) = namespace {
-                quote! { Some(#namespace) }
+                quote! { Some(sauron::vdom::Namespace::Borrowed(#namespace)) }
             } else {
                 quote! { None }
             };
@@ -47,7 +50,7 @@ fn single_node(node: Node) -> TokenStream {
                 sauron::html::element_ns(#ns, #tag, [#attributes], [#children], #self_closing)
             }
         }
-        Node::Fragment(fragment) => multiple_nodes(fragment.children),
+        Node::Fragment(fragment) => multiple_nodes(fragment.children, parent_namespace),
         Node::Text(node_text) => {
             let text = node_text.value_string();
             quote! {
@@ -107,10 +110,10 @@ fn single_node(node: Node) -> TokenStream {
     }
 }
 
-fn nodes_to_tokens(nodes: Vec<Node>) -> TokenStream {
+fn nodes_to_tokens(nodes: Vec<Node>, parent_namespace: Option<&str>) -> TokenStream {
     let mut tokens = TokenStream::new();
     for node in nodes {
-        let node_token = single_node(node);
+        let node_token = single_node(node, parent_namespace);
         tokens.extend(quote! {
             #node_token,
         });
@@ -166,6 +169,15 @@ fn attribute_to_tokens(attribute: NodeAttribute) -> TokenStream {
                             #[allow(unused_braces)]
                             sauron::html::events::#event(#value)
                         }
+                    } else if attr.starts_with("xlink:") {
+                        quote! {
+                            #[allow(unused_braces)]
+                            sauron::vdom::attr_ns(
+                                Some(sauron::vdom::Namespace::Borrowed(sauron::svg::attributes::XLINK_NAMESPACE)),
+                                #attr,
+                                #value,
+                            )
+                        }
                     } else {
                         quote! {
                             #[allow(unused_braces)]
@@ -188,39 +188,24 @@ impl App {
     }
 }
 
-/// The serialized_state is supplied by the generated page from the webserver.
-/// The generated page in index function has a main function which is supplied by a json text
-/// serialized state. This json text is deserialized and used here as our `App` value which
-/// will then be injected into the view
+/// The server renders the view of the app along with its serialized state into the `#app` element.
+/// The app is reconstructed from that state, then the server rendered view is hydrated.
 #[wasm_bindgen]
-pub fn main(serialized_state: String) {
+pub fn main() {
     console_log::init_with_level(log::Level::Trace).unwrap();
     console_error_panic_hook::set_once();
 
-    /* Deserialize starting app data from the argument, which is passed in the generated index page
-     * (but generated in server/src/main.rs) */
-    let app =
-        if let Ok(app_state) = serde_json::from_str::<App>(&serialized_state) {
-            app_state
-        } else {
-            App::default()
-        };
-
-    /* If there's a window (i.e., if this is running in the browser)
-     * then mount the app by swapping out the <main> tag */
-    match web_sys::window() {
-        Some(window) => {
-            trace!("found window, will try to replace <main>");
-            let document =
-                window.document().expect("should have a document on window");
-            Program::replace_mount(
-                app,
-                &document.query_selector_all("main").unwrap().get(0).unwrap(),
-            );
+    match sauron::document().get_element_by_id("app") {
+        Some(mount) => {
+            trace!("found #app, will hydrate the server rendered view");
+            if let Err(e) = Program::<App>::hydrate_with_state(&mount) {
+                error!("unable to hydrate the app: {e}");
+                Program::clear_append_to_mount(App::default(), &mount);
+            }
         }
         None => {
-            trace!("window not found");
-            Program::mount_to_body(app);
+            trace!("#app not found");
+            Program::mount_to_body(App::default());
         }
     }
 }
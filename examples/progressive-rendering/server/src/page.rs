@@ -1,12 +1,11 @@
 use client::{App, Msg};
-use sauron::{html::units::*, html::*, *};
+use sauron::{dom::render_with_state, html::units::*, html::*, *};
 
 /// We are creating an index page.
-/// From the `App` supplied, we can derive the view by calling `App.view` function.
-/// we extract the state and serialize it.
+/// From the `App` supplied, we render its view along with its serialized state,
+/// which the client uses to hydrate the view.
 pub fn index(app: &App) -> Node<Msg> {
-    let serialized_state = serde_json::to_string(&app).unwrap();
-    let view = app.view();
+    let view = render_with_state(app).expect("must serialize the app state");
     let style = style();
     node! {
         <!doctype html>
@@ -16,14 +15,14 @@ pub fn index(app: &App) -> Node<Msg> {
             <title>"Progressive Rendering with Sauron"</title>
             <style>{text(style)}</style>
             <script type="module">
-                {text!("
-                      import init, {{ main }} from '/pkg/client.js';
-                      async function start() {{
+                {text("
+                      import init, { main } from '/pkg/client.js';
+                      async function start() {
                         await init();
-                        main(`{}`);
-                      }}
+                        main();
+                      }
                       await start();
-                ",serialized_state)}
+                ")}
             </script>
             <meta name="viewport" content="viewport-fit=cover, width=device-width, initial-scale=1.0"/>
           </head>
@@ -35,7 +34,7 @@ pub fn index(app: &App) -> Node<Msg> {
                 <h2>"with Sauron"</h2>
               </section>
             </header>
-              {view}
+            <div id="app">{view}</div>
             <footer>
                 <noscript><p>"This would still work even if javascript is disabled"</p></noscript>
               <a href="https://github.com/ivanceras/sauron" target="_blank">
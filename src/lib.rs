@@ -45,5 +45,5 @@ pub mod prelude {
     pub use sauron_macro::{jss, jss_with_media, style};
 
     #[cfg(feature = "html-parser")]
-    pub use sauron_html_parser::{parse_html, raw_html};
+    pub use sauron_html_parser::{parse_document, parse_html, raw_html};
 }
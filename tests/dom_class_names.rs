@@ -0,0 +1,52 @@
+use sauron::{html::attributes::*, html::*, *};
+
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn classes_added_outside_of_the_view_are_kept() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    console_error_panic_hook::set_once();
+
+    let old: Node<()> = div(
+        [
+            id("class-names-container"),
+            classes_flag([("active", true), ("disabled", false)]),
+        ],
+        [],
+    );
+    let update1: Node<()> = div(
+        [
+            id("class-names-container"),
+            classes_flag([("active", false), ("disabled", true)]),
+        ],
+        [],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old)
+        .expect("must update dom");
+
+    let container = document()
+        .get_element_by_id("class-names-container")
+        .expect("must exist");
+    // a class which is added by a third-party library, such as a css transition
+    container
+        .class_list()
+        .add_1("fade-in")
+        .expect("must add class");
+
+    simple_program
+        .update_dom_with_vdom(update1)
+        .expect("must update dom");
+
+    let class_list = container.class_list();
+    assert!(class_list.contains("disabled"));
+    assert!(!class_list.contains("active"));
+    assert!(class_list.contains("fade-in"));
+}
@@ -26,7 +26,7 @@ fn nodes_with_event_should_not_recycle() {
     assert_eq!(
         diff,
         vec![Patch::remove_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![0]),
             vec![&on_click(f)]
         )]
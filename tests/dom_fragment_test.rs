@@ -40,3 +40,43 @@ fn test_on_client() {
 
     assert_eq!(container.outer_html(), expected);
 }
+
+#[wasm_bindgen_test]
+fn keyed_nodes_in_fragment_keep_their_dom_nodes() {
+    let mut simple_program = simple_program();
+    let view = |keys: &[i32]| -> Node<()> {
+        div(
+            [id("keyed-fragment")],
+            [
+                h1([], [text("Header")]),
+                html::fragment(
+                    keys.iter()
+                        .map(|k| li([key(*k)], [text(format!("item{k}"))])),
+                ),
+            ],
+        )
+    };
+    let find_item = |k: i32| {
+        sauron_core::dom::document()
+            .query_selector(&format!("#keyed-fragment li[key='{k}']"))
+            .unwrap()
+            .unwrap()
+    };
+
+    simple_program
+        .update_dom_with_vdom(view(&[1, 2, 3, 4]))
+        .expect("must update dom");
+    let item4 = find_item(4);
+
+    let new = view(&[4, 1, 5, 3]);
+    let expected = new.render_to_string();
+    simple_program
+        .update_dom_with_vdom(new)
+        .expect("must update dom");
+
+    let container = sauron_core::dom::document()
+        .get_element_by_id("keyed-fragment")
+        .unwrap();
+    assert_eq!(container.outer_html(), expected);
+    assert!(item4.is_same_node(Some(&find_item(4))));
+}
@@ -0,0 +1,126 @@
+#![deny(warnings)]
+use sauron::{dom::MountProcedure, html::attributes::*, html::events::*, html::symbol, html::*, *};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct App {
+    label: &'static str,
+    clicked: Rc<Cell<bool>>,
+}
+
+impl Application for App {
+    type MSG = ();
+
+    fn update(&mut self, _msg: ()) -> Cmd<()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        let clicked = Rc::clone(&self.clicked);
+        main(
+            [class("app")],
+            [
+                h1([], [text(self.label)]),
+                button(
+                    [
+                        id("hydrate-btn"),
+                        on_click(move |_| {
+                            clicked.set(true);
+                        }),
+                    ],
+                    [text("click me")],
+                ),
+            ],
+        )
+    }
+}
+
+struct TextAndSymbol;
+
+impl Application for TextAndSymbol {
+    type MSG = ();
+
+    fn update(&mut self, _msg: ()) -> Cmd<()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        p([], [text("a"), symbol("&nbsp;"), text("b")])
+    }
+}
+
+fn server_render(mount_id: &str, html: &str) -> web_sys::Element {
+    let mount = document().create_element("div").unwrap();
+    mount.set_id(mount_id);
+    mount.set_inner_html(html);
+    document().body().unwrap().append_child(&mount).unwrap();
+    mount
+}
+
+#[wasm_bindgen_test]
+fn hydrate_reuses_server_rendered_nodes() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let clicked = Rc::new(Cell::new(false));
+    let app = App {
+        label: "hello",
+        clicked: Rc::clone(&clicked),
+    };
+    // render pretty, so whitespace in between elements are added
+    let mount = server_render("hydrate-mount-1", &app.view().render_to_string_pretty());
+    let server_button = document().get_element_by_id("hydrate-btn").unwrap();
+
+    let mut program = Program::new(app);
+    program.mount(&mount, MountProcedure::hydrate());
+
+    let button = document().get_element_by_id("hydrate-btn").unwrap();
+    assert!(button.is_same_node(Some(&server_button)));
+    assert_eq!(mount.child_element_count(), 1);
+
+    button.unchecked_ref::<web_sys::HtmlElement>().click();
+    assert!(clicked.get());
+}
+
+#[wasm_bindgen_test]
+fn hydrate_repairs_mismatches() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let server_app = App {
+        label: "from server",
+        clicked: Rc::new(Cell::new(false)),
+    };
+    let mount = server_render(
+        "hydrate-mount-2",
+        &server_app.view().render_to_string_pretty(),
+    );
+    mount
+        .first_element_child()
+        .unwrap()
+        .append_child(&document().create_element("aside").unwrap())
+        .unwrap();
+
+    let client_app = App {
+        label: "from client",
+        clicked: Rc::new(Cell::new(false)),
+    };
+    let expected = client_app.view().render_to_string();
+    Program::hydrate(client_app, &mount);
+
+    assert_eq!(mount.inner_html(), expected);
+}
+
+#[wasm_bindgen_test]
+fn hydrate_splits_the_text_merged_with_a_symbol() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let mount = server_render("hydrate-mount-3", &TextAndSymbol.view().render_to_string());
+    let server_p = mount.first_element_child().unwrap();
+    // the browser merges the texts and the symbol into one text node
+    assert_eq!(server_p.child_nodes().length(), 1);
+
+    Program::hydrate(TextAndSymbol, &mount);
+
+    let paragraph = mount.first_element_child().unwrap();
+    assert!(paragraph.is_same_node(Some(&server_p)));
+    assert_eq!(mount.inner_html(), "<p>a&nbsp;b</p>");
+}
@@ -0,0 +1,76 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{app_state_script, render_with_state, APP_STATE_ELEMENT_ID},
+    html::*,
+    *,
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct App {
+    name: String,
+    count: i32,
+}
+
+impl Application for App {
+    type MSG = ();
+
+    fn update(&mut self, _msg: ()) -> Cmd<()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div([], [text(&self.name), span([], [text(self.count)])])
+    }
+}
+
+/// extract the json from the rendered script element
+fn script_content(html: &str) -> &str {
+    let start = html.find(r#"type="application/json">"#).unwrap() + 24;
+    let end = html.rfind("</script>").unwrap();
+    &html[start..end]
+}
+
+#[test]
+fn state_script_contains_the_serialized_state() {
+    let app = App {
+        name: "sauron".to_string(),
+        count: 42,
+    };
+    let script: Node<()> = app_state_script(&app).unwrap();
+    let html = script.render_to_string();
+    assert!(html.starts_with(&format!(r#"<script id="{APP_STATE_ELEMENT_ID}""#)));
+    assert_eq!(script_content(&html), r#"{"name":"sauron","count":42}"#);
+}
+
+#[test]
+fn state_can_not_close_the_script() {
+    let app = App {
+        name: "</script><script>alert(1)</script>".to_string(),
+        count: 0,
+    };
+    let html = render_with_state(&app).unwrap().render_to_string();
+    assert_eq!(html.matches("</script>").count(), 1);
+
+    let json = script_content(&html);
+    let restored: App = serde_json::from_str(json).unwrap();
+    assert_eq!(restored, app);
+}
+
+#[wasm_bindgen_test]
+fn hydrate_with_state() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let app = App {
+        name: "from server".to_string(),
+        count: 7,
+    };
+    let mount = document().create_element("div").unwrap();
+    mount.set_inner_html(&render_with_state(&app).unwrap().render_to_string());
+    document().body().unwrap().append_child(&mount).unwrap();
+
+    let program = Program::<App>::hydrate_with_state(&mount).unwrap();
+    assert_eq!(*program.app(), app);
+}
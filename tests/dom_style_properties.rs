@@ -0,0 +1,56 @@
+use sauron::{html::attributes::*, html::*, *};
+
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn only_the_changed_style_properties_are_updated() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    console_error_panic_hook::set_once();
+
+    let old: Node<()> = div(
+        [
+            class("style_container"),
+            styles([("display", "block"), ("position", "absolute")]),
+        ],
+        [],
+    );
+    let update1: Node<()> = div(
+        [
+            class("style_container"),
+            styles([("display", "flex"), ("width", "10px")]),
+        ],
+        [],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(old)
+        .expect("must update dom");
+
+    let container: web_sys::HtmlElement = document()
+        .query_selector(".style_container")
+        .expect("must not error")
+        .expect("must exist")
+        .unchecked_into();
+    // a style property which is not managed by the view, such as one set by an animation
+    container
+        .style()
+        .set_property("color", "red")
+        .expect("must set property");
+
+    simple_program
+        .update_dom_with_vdom(update1)
+        .expect("must update dom");
+
+    let style = container.style();
+    assert_eq!(style.get_property_value("display").unwrap(), "flex");
+    assert_eq!(style.get_property_value("width").unwrap(), "10px");
+    assert_eq!(style.get_property_value("position").unwrap(), "");
+    assert_eq!(style.get_property_value("color").unwrap(), "red");
+}
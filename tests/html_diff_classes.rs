@@ -1,5 +1,5 @@
 #![deny(warnings)]
-use sauron::*;
+use sauron::{html::attributes::classes, *};
 
 #[test]
 fn class_with_bool_value() {
@@ -8,12 +8,13 @@ fn class_with_bool_value() {
     let new = div(vec![class(true)], vec![]);
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
-            &"div",
+        vec![Patch::update_classes(
+            "div",
             TreePath::new(vec![]),
-            vec![&class(true)]
+            vec!["true".to_string()],
+            vec!["false".to_string()]
         )],
-        "Should add the new attributes"
+        "Should update the class names"
     );
 }
 
@@ -38,11 +39,61 @@ fn parent_of_matching_keyed_are_ignored() {
     );
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
-            &"ul",
+        vec![Patch::update_classes(
+            "ul",
             TreePath::new(vec![]),
-            vec![&class("changed")]
+            vec!["changed".to_string()],
+            vec!["original".to_string()]
+        )],
+        "Should update the class names"
+    );
+}
+
+#[test]
+fn only_the_changed_class_names_are_updated() {
+    let old: Node<()> = div(
+        vec![
+            class("btn"),
+            classes_flag([("active", true), ("disabled", false)]),
+        ],
+        vec![],
+    );
+    let new = div(
+        vec![
+            class("btn"),
+            classes_flag([("active", false), ("disabled", true)]),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::update_classes(
+            "div",
+            TreePath::new(vec![]),
+            vec!["disabled".to_string()],
+            vec!["active".to_string()]
+        )],
+    );
+}
+
+#[test]
+fn reordered_class_names_are_not_updated() {
+    let old: Node<()> = div(vec![classes(["btn", "active"])], vec![]);
+    let new = div(vec![class("active btn")], vec![]);
+    assert_eq!(diff(&old, &new), vec![]);
+}
+
+#[test]
+fn dropped_class_attribute_only_removes_its_class_names() {
+    let old: Node<()> = div(vec![class("btn active"), id("submit")], vec![]);
+    let new = div(vec![id("submit")], vec![]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::update_classes(
+            "div",
+            TreePath::new(vec![]),
+            vec![],
+            vec!["btn".to_string(), "active".to_string()]
         )],
-        "Should add the new attributes"
     );
 }
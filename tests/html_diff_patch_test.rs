@@ -34,7 +34,7 @@ fn nodes_with_event_must_be_replaced() {
     assert_eq!(
         patch,
         vec![Patch::remove_attributes(
-            &"input",
+            "input",
             TreePath::new(vec![]),
             vec![&event1]
         )]
@@ -48,19 +48,13 @@ fn change_class_attribute() {
     let new = div(vec![classes(["class1", "difference_class"])], vec![]);
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
-            &"div",
+        vec![Patch::update_classes(
+            "div",
             TreePath::new(vec![]),
-            vec![&Attribute::with_multiple_values(
-                None,
-                "class",
-                vec![
-                    AttributeValue::from("class1".to_string()),
-                    AttributeValue::from("difference_class".to_string())
-                ]
-            )]
+            vec!["difference_class".to_string()],
+            vec!["class2".to_string()]
         )],
-        "Should add the new attributes"
+        "Should update the class names"
     );
 }
 
@@ -90,10 +84,10 @@ fn truncate_children() {
     assert_eq!(
         dbg!(diff(&old, &new)),
         vec![
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![3]),),
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![4]),),
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![5]),),
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![6]),),
+            Patch::remove_node(Some("div"), TreePath::new(vec![3]),),
+            Patch::remove_node(Some("div"), TreePath::new(vec![4]),),
+            Patch::remove_node(Some("div"), TreePath::new(vec![5]),),
+            Patch::remove_node(Some("div"), TreePath::new(vec![6]),),
         ],
         "Should truncate children"
     );
@@ -129,13 +123,28 @@ fn truncate_children_different_attributes() {
     assert_eq!(
         patch,
         vec![
-            Patch::add_attributes(&"div", TreePath::new(vec![0]), vec![&class("class5")]),
-            Patch::add_attributes(&"div", TreePath::new(vec![1]), vec![&class("class6")]),
-            Patch::add_attributes(&"div", TreePath::new(vec![2]), vec![&class("class7")]),
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![3]),),
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![4]),),
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![5]),),
-            Patch::remove_node(Some(&"div"), TreePath::new(vec![6]),),
+            Patch::update_classes(
+                "div",
+                TreePath::new(vec![0]),
+                vec!["class5".to_string()],
+                vec!["class1".to_string()]
+            ),
+            Patch::update_classes(
+                "div",
+                TreePath::new(vec![1]),
+                vec!["class6".to_string()],
+                vec!["class2".to_string()]
+            ),
+            Patch::update_classes(
+                "div",
+                TreePath::new(vec![2]),
+                vec!["class7".to_string()],
+                vec!["class3".to_string()]
+            ),
+            Patch::remove_node(Some("div"), TreePath::new(vec![3]),),
+            Patch::remove_node(Some("div"), TreePath::new(vec![4]),),
+            Patch::remove_node(Some("div"), TreePath::new(vec![5]),),
+            Patch::remove_node(Some("div"), TreePath::new(vec![6]),),
         ],
         "Should truncate children"
     );
@@ -152,11 +161,11 @@ fn replace_node2() {
         patch,
         vec![
             Patch::replace_node(
-                Some(&"b"),
+                Some("b"),
                 TreePath::new(vec![0]),
                 vec![&i(vec![], vec![text("1")])]
             ),
-            Patch::replace_node(Some(&"b"), TreePath::new(vec![1]), vec![&i(vec![], vec![])]),
+            Patch::replace_node(Some("b"), TreePath::new(vec![1]), vec![&i(vec![], vec![])]),
         ],
         "ReplaceNode node with a child",
     )
@@ -169,7 +178,7 @@ fn remove_nodes1() {
 
     assert_eq!(
         dbg!(diff(&old, &new)),
-        vec![Patch::clear_children(Some(&"div"), TreePath::new(vec![]),),],
+        vec![Patch::clear_children(Some("div"), TreePath::new(vec![]),),],
         "Remove all child nodes at and after child sibling index 1",
     );
 }
@@ -197,8 +206,8 @@ fn remove_nodes2() {
     assert_eq!(
         dbg!(diff(&old, &new)),
         vec![
-            Patch::remove_node(Some(&"i"), TreePath::new(vec![0, 1]),),
-            Patch::remove_node(Some(&"strong"), TreePath::new(vec![1]),),
+            Patch::remove_node(Some("i"), TreePath::new(vec![0, 1]),),
+            Patch::remove_node(Some("strong"), TreePath::new(vec![1]),),
         ],
         "Remove a child and a grandchild node",
     );
@@ -220,8 +229,8 @@ fn remove_nodes3() {
     assert_eq!(
         dbg!(diff(&old, &new)),
         vec![
-            Patch::remove_node(Some(&"i"), TreePath::new(vec![0, 1]),),
-            Patch::replace_node(Some(&"b"), TreePath::new(vec![1]), vec![&i(vec![], vec![])]),
+            Patch::remove_node(Some("i"), TreePath::new(vec![0, 1]),),
+            Patch::replace_node(Some("b"), TreePath::new(vec![1]), vec![&i(vec![], vec![])]),
         ],
         "Removing child and change next node after parent",
     )
@@ -234,7 +243,7 @@ fn add_attributes() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::add_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&id("hello")]
         )],
@@ -247,7 +256,7 @@ fn add_attributes() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::add_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&id("hello")]
         )],
@@ -261,12 +270,13 @@ fn add_style_attributes() {
     let new = div(vec![style!("display": "none")], vec![]);
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
-            &"div",
+        vec![Patch::update_styles(
+            "div",
             TreePath::new(vec![]),
-            vec![&style!("display": "none")]
+            vec![&Style::new("display", "none")],
+            vec![]
         )],
-        "Add attributes",
+        "Update styles",
     );
 }
 
@@ -280,19 +290,48 @@ fn add_style_attributes_1_change() {
         vec![styles([("display", "none"), ("position", "absolute")])],
         vec![],
     );
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::update_styles(
+            "div",
+            TreePath::new(vec![]),
+            vec![&Style::new("display", "none")],
+            vec![]
+        )],
+    );
+}
+
+#[test]
+fn update_style_properties_added_and_removed() {
+    let old: Node<()> = div(
+        vec![styles([("display", "block"), ("position", "absolute")])],
+        vec![],
+    );
+    let new = div(
+        vec![styles([("display", "block")]), styles([("width", "10px")])],
+        vec![],
+    );
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::update_styles(
+            "div",
+            TreePath::new(vec![]),
+            vec![&Style::new("width", "10px")],
+            vec![&Style::new("position", "absolute")]
+        )],
+    );
+}
+
+#[test]
+fn style_changed_into_plain_value_replaces_the_attribute() {
+    let old: Node<()> = div(vec![styles([("display", "block")])], vec![]);
+    let new: Node<()> = div(vec![attr("style", "display:none")], vec![]);
     assert_eq!(
         diff(&old, &new),
         vec![Patch::add_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
-            vec![&Attribute::with_multiple_values(
-                None,
-                "style",
-                vec![AttributeValue::Style(vec![
-                    Style::new("display", "none"),
-                    Style::new("position", "absolute")
-                ])]
-            )]
+            vec![&attr("style", "display:none")]
         )],
     );
 }
@@ -317,7 +356,7 @@ fn remove_style_attributes() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::remove_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&style!("display": "block")]
         )],
@@ -333,7 +372,7 @@ fn remove_events_will_become_replace_node() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::remove_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&event1]
         )],
@@ -377,7 +416,7 @@ fn text_changed_in_keyed_elements() {
                 TreePath::new(vec![0, 2, 0]),
                 vec![&text("item3 with changes")]
             ),
-            Patch::remove_node(Some(&"article"), TreePath::new(vec![0, 0]),),
+            Patch::remove_node(Some("article"), TreePath::new(vec![0, 0]),),
         ]
     );
 }
@@ -405,13 +444,11 @@ fn multiple_style_calls() {
     println!("patches: {:#?}", patches);
     assert_eq!(
         patches,
-        vec![Patch::add_attributes(
-            &"div",
+        vec![Patch::update_styles(
+            "div",
             TreePath::new(vec![]),
-            vec![
-                &style!("font-family": "monospace1"),
-                &style!("display": "flex")
-            ]
+            vec![&Style::new("font-family", "monospace1")],
+            vec![]
         )]
     );
 }
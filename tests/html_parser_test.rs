@@ -1,6 +1,11 @@
 use sauron::html::lookup::match_tag;
+use sauron::html::MATHML_NAMESPACE;
+use sauron::svg::{attributes::XLINK_NAMESPACE, SVG_NAMESPACE};
 use sauron::vdom::Node;
-use sauron_html_parser::parse_html;
+use sauron_html_parser::{
+    parse_document, parse_html, parse_html_lenient, parse_html_strict, raw_html, Malformed,
+    ParseError,
+};
 
 #[test]
 fn should_match_tags() {
@@ -35,9 +40,214 @@ fn test_node_list() {
     <html>
         <body>This is body</body>
     </html>"#;
-    let expected = "<html><body>This is body</body></html>";
+    let expected = "<!doctype html><html><body>This is body</body></html>";
     let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
     println!("node: {:#?}", node);
     println!("render: {}", node.render_to_string());
     assert_eq!(expected, node.render_to_string());
 }
+
+#[test]
+fn custom_elements_and_attributes_are_kept() {
+    let html = r#"<my-card data-id="42" aria-label="card" x-data="{ open: false }"><span slot="title">Hello</span></my-card>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    assert_eq!(node.tag(), Some("my-card"));
+    assert_eq!(
+        node.first_value("data-id").and_then(|v| v.as_str()),
+        Some("42")
+    );
+    assert_eq!(html, node.render_to_string());
+}
+
+#[test]
+fn standard_and_custom_attributes_in_the_same_element() {
+    let html = r#"<div class="box" data-role="panel" hx-get="/items"></div>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    assert_eq!(html, node.render_to_string());
+}
+
+#[test]
+fn comments_and_doctype_are_kept() {
+    let html = r#"<!DOCTYPE html><div><!-- the content --><p>Hi</p></div>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    assert_eq!(
+        "<!doctype html><div><!-- the content --><p>Hi</p></div>",
+        node.render_to_string()
+    );
+}
+
+#[test]
+fn entities_are_decoded_in_text_and_attributes() {
+    let html = r#"<p title="Tom &amp; Jerry &quot;&#x263A;&quot;">a &lt; b &amp;&amp; c &#9650; &hearts;&nbsp;</p>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    assert_eq!(
+        node.first_value("title").and_then(|v| v.as_str()),
+        Some("Tom & Jerry \"\u{263A}\"")
+    );
+    assert_eq!(
+        node.render_to_string(),
+        "<p title=\"Tom &amp; Jerry &quot;\u{263A}&quot;\">a &lt; b &amp;&amp; c \u{25B2} \u{2665}\u{A0}</p>"
+    );
+}
+
+#[test]
+fn entities_in_script_are_kept_as_is() {
+    let html = r#"<script>if (a &lt; b) { run(); }</script>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    assert_eq!(html, node.render_to_string());
+}
+
+#[test]
+fn parse_a_complete_document() {
+    let html = r#"<!DOCTYPE html>
+<!-- generated -->
+<html lang="en">
+    <head><title>Todo &amp; more</title></head>
+    <body><main id="app">Hello</main></body>
+</html>"#;
+    let node: Node<()> = parse_document(html).expect("must parse");
+    assert_eq!(
+        node.render_to_string(),
+        r#"<!doctype html><!-- generated --><html lang="en"><head><title>Todo &amp; more</title></head><body><main id="app">Hello</main></body></html>"#
+    );
+}
+
+#[test]
+fn document_without_html_is_wrapped() {
+    let node: Node<()> =
+        parse_document("<head><title>Hi</title></head><p>one</p><p>two</p>").expect("must parse");
+    assert_eq!(
+        node.render_to_string(),
+        "<html><head><title>Hi</title></head><body><p>one</p><p>two</p></body></html>"
+    );
+}
+
+#[test]
+fn svg_and_mathml_elements_have_their_namespace() {
+    let html = r##"<div><svg><a xlink:href="#shape"><title>Shape</title></a><foreignObject><p>html</p></foreignObject></svg><math><mi>x</mi></math></div>"##;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    let namespace_of = |selector: &str| {
+        let (found, _path) = node.query_selector(selector).unwrap().expect("must match");
+        found
+            .element_ref()
+            .and_then(|elm| elm.namespace())
+            .map(|ns| ns.to_string())
+    };
+    assert_eq!(namespace_of("div"), None);
+    assert_eq!(namespace_of("svg").as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of("a").as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of("title").as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of("p"), None);
+    assert_eq!(namespace_of("mi").as_deref(), Some(MATHML_NAMESPACE));
+
+    let (link, _) = node.query_selector("a").unwrap().unwrap();
+    let href = &link.attributes().unwrap()[0];
+    assert_eq!(href.name(), "xlink:href");
+    assert_eq!(
+        href.namespace().map(|ns| ns.as_ref()),
+        Some(XLINK_NAMESPACE)
+    );
+    assert_eq!(html, node.render_to_string());
+}
+
+#[test]
+fn entities_in_svg_style_are_decoded() {
+    let html = r#"<svg><style>text::after { content: "&gt;"; }</style></svg>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    let (style, _) = node.query_selector("style").unwrap().unwrap();
+    assert_eq!(
+        style.children()[0],
+        sauron::html::text(r#"text::after { content: ">"; }"#)
+    );
+}
+
+#[test]
+fn text_content_of_svg_elements() {
+    let html = r#"<svg><title>A &amp; B</title><script>if (a &lt; b) go();</script></svg>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    let (title, _) = node.query_selector("title").unwrap().unwrap();
+    assert_eq!(title.children(), [sauron::html::text("A & B")]);
+    let (script, _) = node.query_selector("script").unwrap().unwrap();
+    assert_eq!(script.children(), [sauron::html::text("if (a < b) go();")]);
+}
+
+#[test]
+fn strict_mode_rejects_unclosed_tags() {
+    let result: Result<Option<Node<()>>, _> = parse_html_strict("<div>\n  <p>hello</div>");
+    let err = result.expect_err("must be rejected");
+    assert_eq!(
+        err.to_string(),
+        "unclosed tag `p` at line 2, column 3: `<p>`"
+    );
+}
+
+#[test]
+fn strict_mode_rejects_misnested_tags() {
+    let result: Result<Option<Node<()>>, _> = parse_html_strict("<p><b><i>bold</b></i></p>");
+    match result {
+        Err(ParseError::Malformed(Malformed::MisnestedTag {
+            tag,
+            expected,
+            location,
+        })) => {
+            assert_eq!(tag, "b");
+            assert_eq!(expected, "i");
+            assert_eq!((location.line, location.column), (1, 14));
+            assert_eq!(location.snippet, "</b>");
+        }
+        _ => panic!("must be a misnested tag"),
+    }
+}
+
+#[test]
+fn strict_mode_accepts_void_and_self_closing_tags() {
+    let html = r#"<div><br><img src="a.png"><svg><circle r="1"/></svg><!-- <span> --><script>if (a < b) { go(); }</script></div>"#;
+    let node: Node<()> = parse_html_strict(html).ok().flatten().expect("must parse");
+    assert_eq!(node.tag(), Some("div"));
+}
+
+#[test]
+fn strict_mode_skips_the_content_of_raw_text_elements() {
+    let html = "<div><SCRIPT>let a = '<p>';</Script><textarea><b></TEXTAREA></div>";
+    let node: Node<()> = parse_html_strict(html).ok().flatten().expect("must parse");
+    assert_eq!(node.tag(), Some("div"));
+}
+
+#[test]
+fn rphtml_errors_have_their_location() {
+    let result: Result<Option<Node<()>>, _> = parse_html("<div>\n<p =x>a</p></div>");
+    match result {
+        Err(ParseError::RpHtmlError {
+            location: Some(location),
+            ..
+        }) => {
+            assert_eq!((location.line, location.column), (2, 4));
+            assert_eq!(location.snippet, "=x>");
+        }
+        _ => panic!("must be an rphtml error with a location"),
+    }
+}
+
+#[test]
+fn raw_html_displays_the_html_which_can_not_be_parsed() {
+    let node: Node<()> = raw_html("<p =x>a</p>");
+    assert_eq!(node.render_to_string(), "&lt;p =x&gt;a&lt;/p&gt;");
+}
+
+#[test]
+fn lenient_mode_returns_the_warnings() {
+    let html = "<ul>\n  <li>one\n  <li>two\n</ul></span>";
+    let (node, warnings): (Option<Node<()>>, _) = parse_html_lenient(html).expect("must parse");
+    assert!(node.is_some(), "the tree is still returned");
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "unclosed tag `li` at line 2, column 3: `<li>`",
+            "unclosed tag `li` at line 3, column 3: `<li>`",
+            "unexpected end tag `span` at line 4, column 6: `</span>`",
+        ]
+    );
+}
@@ -0,0 +1,94 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{component, render_document, DocumentHead},
+    html::{attributes::*, *},
+    *,
+};
+
+struct Button;
+
+impl Component for Button {
+    type MSG = ();
+    type XMSG = ();
+
+    fn update(&mut self, _msg: ()) -> Effects<(), ()> {
+        Effects::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        button([class("btn")], [text("click")])
+    }
+
+    fn stylesheet() -> Vec<String> {
+        vec![".btn { color: red; }".to_string()]
+    }
+}
+
+struct App;
+
+impl Application for App {
+    type MSG = ();
+
+    fn update(&mut self, _msg: ()) -> Cmd<()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        main([], [component(&Button), div([], [component(&Button)])])
+    }
+
+    fn stylesheet() -> Vec<String> {
+        vec!["body { margin: 0; }".to_string()]
+    }
+
+    fn style(&self) -> Vec<String> {
+        vec!["main { display: flex; }".to_string()]
+    }
+}
+
+#[test]
+fn document_contains_head_stylesheets_and_body() {
+    let head = DocumentHead::with_title("Sauron & friends").meta("description", "a demo");
+    let html = render_document(&App, &head);
+    let expected = "<!doctype html>\
+        <html>\
+        <head>\
+        <meta charset=\"utf-8\"/>\
+        <title>Sauron &amp; friends</title>\
+        <meta name=\"description\" content=\"a demo\"/>\
+        <style>body { margin: 0; }</style>\
+        <style>main { display: flex; }</style>\
+        <style>.btn { color: red; }</style>\
+        </head>\
+        <body>\
+        <main>\
+        <button class=\"btn\">click</button>\
+        <div><button class=\"btn\">click</button></div>\
+        </main>\
+        </body>\
+        </html>";
+    assert_eq!(html, expected);
+}
+
+#[test]
+fn empty_stylesheets_are_not_rendered() {
+    struct Plain;
+
+    impl Application for Plain {
+        type MSG = ();
+
+        fn update(&mut self, _msg: ()) -> Cmd<()> {
+            Cmd::none()
+        }
+
+        fn view(&self) -> Node<()> {
+            p([], [text("plain")])
+        }
+    }
+
+    let html = render_document(&Plain, &DocumentHead::default());
+    assert_eq!(
+        html,
+        "<!doctype html><html><head><meta charset=\"utf-8\"/></head><body><p>plain</p></body></html>"
+    );
+}
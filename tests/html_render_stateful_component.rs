@@ -0,0 +1,133 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{stateful_component, DomAttr, DomNode, StatefulComponent},
+    html::{
+        attributes::{title, *},
+        events::*,
+        *,
+    },
+    vdom::TreePath,
+    *,
+};
+
+#[derive(Default)]
+struct Card {
+    title: String,
+}
+
+impl Component for Card {
+    type MSG = ();
+    type XMSG = ();
+
+    fn update(&mut self, _msg: ()) -> Effects<(), ()> {
+        Effects::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        article(
+            [class("card")],
+            [
+                h1([on_click(|_| ())], [text(&self.title)]),
+                section([class("card-content")], []),
+            ],
+        )
+    }
+}
+
+impl StatefulComponent for Card {
+    fn attribute_changed(&mut self, attr: DomAttr) {
+        if attr.name == "title" {
+            if let Some(value) = attr.value[0].as_string() {
+                self.title = value;
+            }
+        }
+    }
+
+    fn child_container(&self) -> Option<DomNode> {
+        None
+    }
+
+    fn child_container_path(&self) -> Option<TreePath> {
+        Some(TreePath::new([1]))
+    }
+}
+
+#[test]
+fn stateful_component_renders_its_view_with_children() {
+    let card = Card {
+        title: "Hello".to_string(),
+    };
+    let view: Node<()> = div(
+        [],
+        [stateful_component(
+            card,
+            [id("card1")],
+            [p([], [text("content")])],
+        )],
+    );
+    let expected = "<div>\
+        <div class=\"component\" id=\"card1\">\
+        <article class=\"card\">\
+        <h1>Hello</h1>\
+        <section class=\"card-content\"><p>content</p></section>\
+        </article>\
+        </div>\
+        </div>";
+    assert_eq!(view.render_to_string(), expected);
+}
+
+#[test]
+fn attributes_are_applied_before_rendering_the_view() {
+    let view: Node<()> = stateful_component(Card::default(), [title("From attribute")], []);
+    let expected = "<div class=\"component\" title=\"From attribute\">\
+        <article class=\"card\">\
+        <h1>From attribute</h1>\
+        <section class=\"card-content\"></section>\
+        </article>\
+        </div>";
+    assert_eq!(view.render_to_string(), expected);
+}
+
+#[test]
+fn rendering_the_view_does_not_mutate_the_component() {
+    let view: Node<()> = stateful_component(Card::default(), [title("From attribute")], []);
+    let Node::Leaf(vdom::Leaf::StatefulComponent(comp)) = &view else {
+        panic!("expecting a stateful component");
+    };
+    // the component can be rendered while it is borrowed
+    let card = comp.comp.borrow();
+    assert!(view.render_to_string().contains("<h1>From attribute</h1>"));
+    drop(card);
+}
+
+#[test]
+fn children_are_placed_after_the_view_without_child_container() {
+    struct Plain;
+
+    impl Component for Plain {
+        type MSG = ();
+        type XMSG = ();
+
+        fn update(&mut self, _msg: ()) -> Effects<(), ()> {
+            Effects::none()
+        }
+
+        fn view(&self) -> Node<()> {
+            span([], [text("plain")])
+        }
+    }
+
+    impl StatefulComponent for Plain {
+        fn attribute_changed(&mut self, _attr: DomAttr) {}
+
+        fn child_container(&self) -> Option<DomNode> {
+            None
+        }
+    }
+
+    let view: Node<()> = stateful_component(Plain, [], [em([], [text("child")])]);
+    assert_eq!(
+        view.render_to_string(),
+        "<div class=\"component\"><span>plain</span><em>child</em></div>"
+    );
+}
@@ -0,0 +1,93 @@
+#![deny(warnings)]
+use futures::StreamExt;
+use sauron::{
+    html::{attributes::*, *},
+    vdom::FlushPoints,
+    *,
+};
+
+fn document() -> Node<()> {
+    html(
+        [],
+        [
+            head([], [meta([charset("utf-8")], [])]),
+            body(
+                [],
+                [
+                    h1([], [text("Hello")]),
+                    p([], [text("first & second")]),
+                    footer([], [text("end")]),
+                ],
+            ),
+        ],
+    )
+}
+
+#[test]
+fn chunks_are_split_after_head_and_each_body_child() {
+    let view = document();
+    let chunks: Vec<String> = view.render_chunks(FlushPoints::default()).collect();
+    assert_eq!(
+        chunks,
+        vec![
+            r#"<html><head><meta charset="utf-8"/></head>"#,
+            "<body><h1>Hello</h1>",
+            "<p>first &amp; second</p>",
+            "<footer>end</footer></body></html>",
+        ]
+    );
+    assert_eq!(chunks.concat(), view.render_to_string());
+}
+
+#[test]
+fn only_flush_after_head() {
+    let view = document();
+    let chunks: Vec<String> = view
+        .render_chunks(FlushPoints {
+            after_head: true,
+            after_each_child: false,
+        })
+        .collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks.concat(), view.render_to_string());
+}
+
+#[test]
+fn no_flush_points_renders_a_single_chunk() {
+    let view = document();
+    let chunks: Vec<String> = view.render_chunks(FlushPoints::none()).collect();
+    assert_eq!(chunks, vec![view.render_to_string()]);
+}
+
+#[test]
+fn children_of_the_root_element_are_flushed() {
+    let view: Node<()> = ul([], [li([], [text("1")]), li([], [text("2")])]);
+    let chunks: Vec<String> = view.render_chunks(FlushPoints::default()).collect();
+    assert_eq!(chunks, vec!["<ul><li>1</li>", "<li>2</li></ul>"]);
+}
+
+#[test]
+fn doctype_in_a_node_list() {
+    let view: Node<()> = node_list([doctype("html"), document()]);
+    let chunks: Vec<String> = view.render_chunks(FlushPoints::default()).collect();
+    assert!(chunks[0].starts_with("<!doctype html><html><head>"));
+    assert_eq!(chunks.concat(), view.render_to_string());
+}
+
+#[test]
+fn render_to_stream() {
+    let view = document();
+    let chunks: Vec<String> =
+        futures::executor::block_on(view.render_to_stream(FlushPoints::default()).collect());
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks.concat(), view.render_to_string());
+}
+
+#[test]
+fn render_to_writer() {
+    let view = document();
+    let mut writer: Vec<u8> = vec![];
+    view.render_to_writer(&mut writer, FlushPoints::default())
+        .expect("must write");
+    assert_eq!(String::from_utf8(writer).unwrap(), view.render_to_string());
+}
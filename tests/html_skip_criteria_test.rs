@@ -32,7 +32,7 @@ fn must_diff() {
     assert_eq!(
         patch,
         vec![
-            Patch::add_attributes(&"div", TreePath::new([]), &[skip_criteria(1001)]),
+            Patch::add_attributes("div", TreePath::new([]), &[skip_criteria(1001)]),
             Patch::replace_node(None, TreePath::new([0]), &[text("the difference here")])
         ]
     );
@@ -1,4 +1,5 @@
 #![deny(warnings)]
+use sauron::html::attributes::Style;
 use sauron::*;
 
 #[test]
@@ -14,9 +15,13 @@ fn style_calcd_changed() {
     );
 
     let patches: Vec<Patch<&'static str>> = diff(&old, &new);
-    let styl = style! {width: format!("calc(50% + {}", px(200))};
-    let expected: Vec<Patch<&'static str>> =
-        vec![Patch::add_attributes(&"div", TreePath::new([]), [&styl])];
+    let styl = Style::new("width", format!("calc(50% + {}", px(200)));
+    let expected: Vec<Patch<&'static str>> = vec![Patch::update_styles(
+        "div",
+        TreePath::new([]),
+        vec![&styl],
+        vec![],
+    )];
     assert_eq!(expected, patches);
 }
 
@@ -37,8 +42,13 @@ fn style_calcd_changed_with_event() {
     );
 
     let patches: Vec<Patch<()>> = diff(&old, &new);
-    let styl = style! {width: format!("calc(50% + {}", px(200))};
-    let expected: Vec<Patch<()>> = vec![Patch::add_attributes(&"div", TreePath::new([]), [&styl])];
+    let styl = Style::new("width", format!("calc(50% + {}", px(200)));
+    let expected: Vec<Patch<()>> = vec![Patch::update_styles(
+        "div",
+        TreePath::new([]),
+        vec![&styl],
+        vec![],
+    )];
     assert_eq!(expected, patches);
 }
 
@@ -92,11 +102,11 @@ fn app_editor_width_allocation_bug() {
     );
 
     let patches: Vec<Patch<()>> = diff(&old, &new);
-    let styl_1 = style! {width: format!("calc(50% + {}", px(200))};
-    let styl_2 = style! {width: format!("calc(50% - {}", px(200))};
+    let styl_1 = Style::new("width", format!("calc(50% + {}", px(200)));
+    let styl_2 = Style::new("width", format!("calc(50% - {}", px(200)));
     let expected: Vec<Patch<()>> = vec![
-        Patch::add_attributes(&"div", TreePath::new([0]), [&styl_1]),
-        Patch::add_attributes(&"div", TreePath::new([2]), [&styl_2]),
+        Patch::update_styles("div", TreePath::new([0]), vec![&styl_1], vec![]),
+        Patch::update_styles("div", TreePath::new([2]), vec![&styl_2], vec![]),
     ];
     assert_eq!(expected, patches);
 }
@@ -17,7 +17,7 @@ fn with_events() {
     let result: Node<()> = node! {
         <div id="hello" on_click=|_|{println!("clicked!")} >Hello world</div>
     };
-    let expected = "<div id=\"hello\" >Hello world</div>";
+    let expected = "<div id=\"hello\">Hello world</div>";
     assert_eq!(expected, result.render_to_string());
 }
 
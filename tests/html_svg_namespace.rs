@@ -0,0 +1,114 @@
+#![deny(warnings)]
+use sauron::{
+    html::{lookup, MATHML_NAMESPACE},
+    svg::{attributes::XLINK_NAMESPACE, SVG_NAMESPACE},
+    *,
+};
+
+/// the namespace of the first element matching the selector
+fn namespace_of<'a>(node: &'a Node<()>, selector: &str) -> Option<&'a str> {
+    let (found, _path) = node
+        .query_selector(selector)
+        .unwrap()
+        .expect("must match an element");
+    found
+        .element_ref()
+        .and_then(|elm| elm.namespace())
+        .map(|ns| ns.as_ref())
+}
+
+#[test]
+fn html_tags_inside_svg_are_in_the_svg_namespace() {
+    let node: Node<()> = node! {
+        <div>
+            <svg>
+                <a href="#shape"><title>"A shape"</title></a>
+                <style>"circle { fill: red; }"</style>
+                <circle cx="5" cy="5" r="5"/>
+            </svg>
+            <a href="/home">"home"</a>
+        </div>
+    };
+    assert_eq!(namespace_of(&node, "div"), None);
+    assert_eq!(namespace_of(&node, "svg"), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of(&node, "svg > a"), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of(&node, "title"), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of(&node, "style"), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of(&node, "circle"), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of(&node, "div > a"), None);
+}
+
+#[test]
+fn html_inside_foreign_object() {
+    let node: Node<()> = node! {
+        <svg>
+            <foreignObject>
+                <div><a href="/home">"home"</a></div>
+            </foreignObject>
+        </svg>
+    };
+    assert_eq!(namespace_of(&node, "foreignObject"), Some(SVG_NAMESPACE));
+    assert_eq!(namespace_of(&node, "div"), None);
+    assert_eq!(namespace_of(&node, "a"), None);
+}
+
+#[test]
+fn mathml_elements() {
+    let node: Node<()> = node! {
+        <p>
+            <math>
+                <mi>"x"</mi>
+                <mo>"="</mo>
+                <mn>"2"</mn>
+            </math>
+        </p>
+    };
+    assert_eq!(namespace_of(&node, "p"), None);
+    assert_eq!(namespace_of(&node, "math"), Some(MATHML_NAMESPACE));
+    assert_eq!(namespace_of(&node, "mi"), Some(MATHML_NAMESPACE));
+    assert_eq!(namespace_of(&node, "mn"), Some(MATHML_NAMESPACE));
+}
+
+#[test]
+fn xlink_attributes_have_the_xlink_namespace() {
+    let node: Node<()> = node! {
+        <svg><image xlink:href="logo.png"/></svg>
+    };
+    let (image, _) = node.query_selector("image").unwrap().unwrap();
+    let href = &image.attributes().unwrap()[0];
+    assert_eq!(href.name(), "xlink:href");
+    assert_eq!(
+        href.namespace().map(|ns| ns.as_ref()),
+        Some(XLINK_NAMESPACE)
+    );
+    assert_eq!(
+        node.render_to_string(),
+        r#"<svg><image xlink:href="logo.png"></image></svg>"#
+    );
+}
+
+#[test]
+fn element_namespace_lookup() {
+    assert_eq!(lookup::element_namespace("a", None), None);
+    assert_eq!(
+        lookup::element_namespace("a", Some(SVG_NAMESPACE)),
+        Some(SVG_NAMESPACE)
+    );
+    assert_eq!(lookup::element_namespace("rect", None), Some(SVG_NAMESPACE));
+    assert_eq!(
+        lookup::element_namespace("svg", Some(MATHML_NAMESPACE)),
+        Some(SVG_NAMESPACE)
+    );
+    assert_eq!(
+        lookup::children_namespace("foreignObject", Some(SVG_NAMESPACE)),
+        None
+    );
+    assert_eq!(
+        lookup::children_namespace("g", Some(SVG_NAMESPACE)),
+        Some(SVG_NAMESPACE)
+    );
+    assert_eq!(
+        lookup::children_namespace("mtext", Some(MATHML_NAMESPACE)),
+        None
+    );
+}
@@ -115,7 +115,7 @@ fn replace_node() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::replace_node(
-            Some(&"div"),
+            Some("div"),
             TreePath::new(vec![]),
             vec![&span(vec![], vec![])]
         )],
@@ -127,7 +127,7 @@ fn replace_node() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::replace_node(
-            Some(&"b"),
+            Some("b"),
             TreePath::new(vec![0]),
             vec![&strong(vec![], vec![])]
         )],
@@ -143,11 +143,11 @@ fn replace_node() {
         patch,
         vec![
             Patch::replace_node(
-                Some(&"b"),
+                Some("b"),
                 TreePath::new(vec![0]),
                 vec![&i(vec![], vec![text("1")])]
             ),
-            Patch::replace_node(Some(&"b"), TreePath::new(vec![1]), vec![&i(vec![], vec![])]),
+            Patch::replace_node(Some("b"), TreePath::new(vec![1]), vec![&i(vec![], vec![])]),
         ],
     )
 }
@@ -165,7 +165,7 @@ fn add_children() {
     assert_eq!(
         dbg!(diff(&old, &new)),
         vec![Patch::append_children(
-            Some(&"div"),
+            Some("div"),
             TreePath::new(vec![]),
             vec![&html_element(None, "new", vec![], vec![], false)]
         )],
@@ -180,7 +180,7 @@ fn add_attributes() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::add_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&id("hello")]
         )],
@@ -193,7 +193,7 @@ fn add_attributes() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::add_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&id("hello")]
         )],
@@ -208,7 +208,7 @@ fn remove_attributes() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::remove_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&id("hey-there")]
         )],
@@ -224,7 +224,7 @@ fn change_attribute() {
     assert_eq!(
         diff(&old, &new),
         vec![Patch::add_attributes(
-            &"div",
+            "div",
             TreePath::new(vec![]),
             vec![&id("changed")]
         )],
@@ -0,0 +1,221 @@
+#![deny(warnings)]
+use sauron::html::attributes::title;
+use sauron::{
+    html::{attributes::*, *},
+    *,
+};
+
+/// apply the patches of diffing old and new into old
+fn apply_diff(old: &Node<()>, new: &Node<()>) -> Node<()> {
+    let patches = diff(old, new);
+    let mut patched = old.clone();
+    patched.apply_patches(&patches);
+    patched
+}
+
+/// a small deterministic random number generator, so the generated trees are reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, max: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % max
+    }
+}
+
+fn random_tree(rng: &mut Rng, depth: usize) -> Node<()> {
+    if depth == 0 || rng.next(4) == 0 {
+        return match rng.next(3) {
+            0 => text(format!("text{}", rng.next(3))),
+            1 => comment(format!("comment{}", rng.next(3))),
+            _ => span([], []),
+        };
+    }
+    let tag = ["div", "p", "ul"][rng.next(3)];
+    let mut attrs = vec![];
+    if rng.next(2) == 0 {
+        attrs.push(id(format!("id{}", rng.next(3))));
+    }
+    if rng.next(2) == 0 {
+        attrs.push(class(format!("c{} d{}", rng.next(3), rng.next(3))));
+    }
+    if rng.next(3) == 0 {
+        attrs.push(title(format!("t{}", rng.next(3))));
+    }
+    let children: Vec<Node<()>> = (0..rng.next(5))
+        .map(|_| random_tree(rng, depth - 1))
+        .collect();
+    html_element(None, tag, attrs, children, false)
+}
+
+/// the node rendered with its attributes and class names sorted,
+/// since the order of these are not preserved in the DOM when patched
+fn canonical(node: &Node<()>) -> String {
+    match node {
+        Node::Element(elm) => {
+            let mut attrs: Vec<String> = elm
+                .attributes()
+                .iter()
+                .map(|attr| {
+                    let mut values: Vec<String> = attr
+                        .value()
+                        .iter()
+                        .filter_map(|value| value.get_simple())
+                        .flat_map(|value| {
+                            value
+                                .to_string()
+                                .split_whitespace()
+                                .map(|v| v.to_string())
+                                .collect::<Vec<_>>()
+                        })
+                        .collect();
+                    values.sort();
+                    format!("{}={}", attr.name(), values.join(" "))
+                })
+                .collect();
+            attrs.sort();
+            let children: Vec<String> = elm.children().iter().map(canonical).collect();
+            format!(
+                "<{} {}>{}</{}>",
+                elm.tag(),
+                attrs.join(" "),
+                children.join(""),
+                elm.tag()
+            )
+        }
+        _ => node.render_to_string(),
+    }
+}
+
+#[test]
+fn text_and_children_changes() {
+    let old: Node<()> = main([], [p([], [text("hello")]), p([], [text("world")])]);
+    let new: Node<()> = main(
+        [],
+        [
+            p([], [text("hello!")]),
+            p([], [text("world")]),
+            footer([], [text("end")]),
+        ],
+    );
+    assert_eq!(apply_diff(&old, &new), new);
+    assert_eq!(apply_diff(&new, &old), old);
+}
+
+#[test]
+fn attribute_changes() {
+    let old: Node<()> = div([id("a"), title("old")], [input([r#type("text")], [])]);
+    let new: Node<()> = div([id("b")], [input([r#type("text"), disabled(true)], [])]);
+    assert_eq!(apply_diff(&old, &new), new);
+    assert_eq!(apply_diff(&new, &old), old);
+}
+
+#[test]
+fn style_and_class_changes_render_the_same() {
+    let old: Node<()> = div([class("a b"), style! {"color": "red", "width": px(10)}], []);
+    let new: Node<()> = div(
+        [class("b c"), style! {"color": "blue", "height": px(5)}],
+        [],
+    );
+    let patched = apply_diff(&old, &new);
+    assert_eq!(patched.render_to_string(), new.render_to_string());
+}
+
+#[test]
+fn replace_the_root_node() {
+    let old: Node<()> = div([], [text("1")]);
+    let new: Node<()> = section([], [text("2")]);
+    assert_eq!(apply_diff(&old, &new), new);
+}
+
+fn keyed_list(keys: &[i32]) -> Node<()> {
+    ul(
+        [],
+        keys.iter()
+            .map(|k| li([key(*k)], [text(format!("item{k}"))])),
+    )
+}
+
+#[test]
+fn keyed_nodes_are_inserted_and_removed() {
+    for (old, new) in [
+        (&[1, 2, 3][..], &[1, 2, 3, 4][..]),
+        (&[1, 2, 3], &[0, 1, 2, 3]),
+        (&[1, 2, 3], &[1, 3]),
+        (&[1, 2, 3], &[1, 4, 3]),
+        (&[1, 2, 3, 4], &[1, 4]),
+        (&[1, 2, 3], &[4, 5, 6]),
+        (&[1, 2, 3], &[]),
+    ] {
+        let old = keyed_list(old);
+        let new = keyed_list(new);
+        assert_eq!(apply_diff(&old, &new), new);
+        assert_eq!(apply_diff(&new, &old), old);
+    }
+}
+
+#[test]
+fn keyed_nodes_are_moved() {
+    for (old, new) in [
+        (&[1, 2, 3][..], &[3, 1, 2][..]),
+        (&[1, 2, 3], &[2, 3, 1]),
+        (&[1, 2, 3, 4], &[1, 3, 2, 4]),
+        (&[1, 2, 3, 4], &[4, 3, 2, 1]),
+        (&[1, 2, 3, 4], &[4, 1, 5, 3]),
+    ] {
+        let old = keyed_list(old);
+        let new = keyed_list(new);
+        assert_eq!(apply_diff(&old, &new), new);
+        assert_eq!(apply_diff(&new, &old), old);
+    }
+}
+
+#[test]
+fn apply_diff_of_random_keyed_lists() {
+    let mut rng = Rng(11);
+    // the content of the keyed nodes also changes, to check that they are diffed in place
+    let mut random_list = || -> Node<()> {
+        let mut keys: Vec<usize> = vec![];
+        for _ in 0..rng.next(8) {
+            let k = rng.next(10);
+            if !keys.contains(&k) {
+                keys.push(k);
+            }
+        }
+        ul(
+            [],
+            keys.into_iter()
+                .map(|k| li([key(k)], [text(format!("item{k}-{}", rng.next(2)))])),
+        )
+    };
+    for _ in 0..500 {
+        let old = random_list();
+        let new = random_list();
+        assert_eq!(
+            apply_diff(&old, &new),
+            new,
+            "old: {}\nnew: {}",
+            old.render_to_string(),
+            new.render_to_string()
+        );
+    }
+}
+
+#[test]
+fn apply_diff_of_random_trees() {
+    let mut rng = Rng(7);
+    for _ in 0..500 {
+        let old = random_tree(&mut rng, 4);
+        let new = random_tree(&mut rng, 4);
+        assert_eq!(
+            canonical(&apply_diff(&old, &new)),
+            canonical(&new),
+            "old: {}\nnew: {}",
+            old.render_to_string(),
+            new.render_to_string()
+        );
+    }
+}
@@ -20,10 +20,11 @@ fn class_changed() {
 
     assert_eq!(
         diff,
-        vec![Patch::add_attributes(
-            &"main",
+        vec![Patch::update_classes(
+            "main",
             TreePath::new(vec![]),
-            vec![&attr("class", "class2")]
+            vec!["class2".to_string()],
+            vec!["class1".to_string()]
         )]
     );
 }
@@ -54,11 +55,12 @@ fn parent_of_matching_keyed_are_ignored() {
 
     assert_eq!(
         patches,
-        vec![Patch::add_attributes(
-            &"ul",
+        vec![Patch::update_classes(
+            "ul",
             TreePath::new(vec![]),
-            vec![&attr("class", "changed")]
+            vec!["changed".to_string()],
+            vec!["original".to_string()]
         )],
-        "Should add the new attributes"
+        "Should update the class names"
     );
 }
@@ -34,6 +34,79 @@ fn test_node_list() {
     let diff = diff(&old, &new);
     assert_eq!(
         diff,
-        vec![Patch::remove_node(Some(&"li"), TreePath::new(vec![2]),)],
+        vec![Patch::remove_node(Some("li"), TreePath::new(vec![2]),)],
     );
 }
+
+fn keyed_item(k: i32) -> Node<()> {
+    element("li", [attr("key", k)], [leaf(format!("item{k}"))])
+}
+
+/// a view with multiple root nodes, where the keyed items are created in a loop
+fn multi_root_view(keys: &[i32]) -> Node<()> {
+    node_list([
+        element("h1", [], [leaf("title")]),
+        node_list(keys.iter().map(|k| keyed_item(*k))),
+    ])
+}
+
+#[test]
+fn node_lists_are_unrolled_into_node_lists_and_fragments() {
+    assert_eq!(multi_root_view(&[1, 2]).children().len(), 3);
+    let fragment: Node<()> = fragment([node_list([keyed_item(1), keyed_item(2)])]);
+    assert_eq!(fragment.children().len(), 2);
+}
+
+#[test]
+fn keyed_nodes_in_a_root_node_list_are_moved() {
+    let old = multi_root_view(&[1, 2, 3]);
+    let new = multi_root_view(&[3, 1, 2]);
+
+    let patches = diff(&old, &new);
+    assert_eq!(
+        patches,
+        vec![Patch::move_before_node(
+            Some("li"),
+            TreePath::new([1]),
+            [TreePath::new([3])]
+        )]
+    );
+
+    let mut patched = old.clone();
+    patched.apply_patches(&patches);
+    assert_eq!(patched, new);
+    assert_eq!(
+        TreePath::new([3]).find_node_by_path(&old),
+        Some(&keyed_item(3))
+    );
+}
+
+#[test]
+fn keyed_nodes_in_a_fragment_are_located_in_the_fragment() {
+    let view = |keys: &[i32]| -> Node<()> {
+        element(
+            "div",
+            [],
+            [
+                element("h1", [], [leaf("title")]),
+                fragment(keys.iter().map(|k| keyed_item(*k))),
+            ],
+        )
+    };
+    let old = view(&[1, 2, 3]);
+    let new = view(&[1, 3, 4]);
+
+    let item4 = keyed_item(4);
+    let patches = diff(&old, &new);
+    assert_eq!(
+        patches,
+        vec![
+            Patch::remove_node(Some("li"), TreePath::new([1, 1])),
+            Patch::insert_after_node(Some("li"), TreePath::new([1, 2]), vec![&item4]),
+        ]
+    );
+
+    let mut patched = old.clone();
+    patched.apply_patches(&patches);
+    assert_eq!(patched, new);
+}
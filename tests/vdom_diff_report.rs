@@ -0,0 +1,115 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    vdom::diff_report,
+    *,
+};
+
+fn report_lines(old: &Node<()>, new: &Node<()>) -> Vec<String> {
+    diff_report(old, new)
+        .to_string()
+        .lines()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[test]
+fn unchanged_nodes_are_collapsed() {
+    let old: Node<()> = div([class("app")], [p([], [text("hello")])]);
+    let report = diff_report(&old, &old.clone());
+    assert!(!report.has_changes());
+    assert_eq!(report.to_string(), r#"  <div class="app"> ..."#);
+}
+
+#[test]
+fn changed_text_and_attributes() {
+    let old: Node<()> = div(
+        [class("app")],
+        [h1([], [text("hello")]), p([id("a")], [text("same")])],
+    );
+    let new: Node<()> = div(
+        [class("app")],
+        [h1([], [text("hello world")]), p([id("b")], [text("same")])],
+    );
+    assert_eq!(
+        report_lines(&old, &new),
+        [
+            r#"  <div class="app">"#,
+            r#"    <h1>"#,
+            r#"-     "hello""#,
+            r#"+     "hello world""#,
+            r#"-   <p id="a">"#,
+            r#"+   <p id="b">"#,
+            r#"      "same""#,
+        ]
+    );
+}
+
+#[test]
+fn added_and_removed_nodes() {
+    let old: Node<()> = ul([], [li([], [text("one")]), li([], [text("two")])]);
+    let new: Node<()> = ul([], [li([], [text("one")])]);
+    assert_eq!(
+        report_lines(&old, &new),
+        ["  <ul>", "    <li> ...", "-   <li>", r#"-     "two""#]
+    );
+    assert_eq!(
+        report_lines(&new, &old),
+        ["  <ul>", "    <li> ...", "+   <li>", r#"+     "two""#]
+    );
+}
+
+#[test]
+fn replaced_nodes_are_removed_and_added() {
+    let old: Node<()> = div([], [p([], [text("paragraph")])]);
+    let new: Node<()> = div([], [span([], [text("paragraph")])]);
+    assert_eq!(
+        report_lines(&old, &new),
+        [
+            "  <div>",
+            "-   <p>",
+            r#"-     "paragraph""#,
+            "+   <span>",
+            r#"+     "paragraph""#,
+        ]
+    );
+}
+
+#[test]
+fn moved_keyed_nodes_have_their_old_and_new_path() {
+    let list = |keys: &[usize]| -> Node<()> {
+        main(
+            [],
+            [
+                h1([], [text("list")]),
+                ul([], keys.iter().map(|k| li([key(*k)], [text(k)]))),
+            ],
+        )
+    };
+    let old = list(&[1, 2, 3, 4]);
+    let new = list(&[1, 3, 5, 4, 2]);
+    assert_eq!(
+        report_lines(&old, &new),
+        [
+            "  <main>",
+            "    <h1> ...",
+            "    <ul>",
+            r#"      <li key="1"> ..."#,
+            r#"      <li key="3"> ..."#,
+            r#"+     <li key="5">"#,
+            r#"+       "5""#,
+            r#"      <li key="4"> ..."#,
+            r#"~     <li key="2"> ...  (moved from [1, 1] to [1, 4])"#,
+        ]
+    );
+}
+
+#[test]
+fn colored_report() {
+    let old: Node<()> = p([], [text("old")]);
+    let new: Node<()> = p([], [text("new")]);
+    assert_eq!(
+        diff_report(&old, &new).to_colored_string(),
+        "  <p>\n\x1b[31m-   \"old\"\x1b[0m\n\x1b[32m+   \"new\"\x1b[0m"
+    );
+}
@@ -14,7 +14,7 @@ fn force_replace() {
     assert_eq!(
         diff,
         vec![Patch::replace_node(
-            Some(&"div"),
+            Some("div"),
             TreePath::new(vec![]),
             vec![&new]
         )],
@@ -60,7 +60,7 @@ fn replace_true_in_attribute_must_replace_old_node_regardless() {
     assert_eq!(
         diff,
         vec![Patch::replace_node(
-            Some(&"div"),
+            Some("div"),
             TreePath::new(vec![]),
             vec![&new]
         )],
@@ -169,7 +169,7 @@ fn replace_and_skip_in_sub_nodes() {
     assert_eq!(
         diff,
         vec![Patch::replace_node(
-            Some(&"div"),
+            Some("div"),
             TreePath::new(vec![1]),
             vec![&element(
                 "div",
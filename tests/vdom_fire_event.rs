@@ -0,0 +1,154 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{FireEventError, SyntheticEvent},
+    html::{attributes::*, events::*, lazy, *},
+    vdom::SelectorError,
+    *,
+};
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    Increment,
+    Decrement,
+    Clicked(&'static str),
+    SetName(String),
+    Toggle(bool),
+}
+
+fn view() -> Node<Msg> {
+    main(
+        [],
+        [
+            button([class("inc"), on_click(|_| Msg::Increment)], [text("+")]),
+            button([class("dec"), on_click(|_| Msg::Decrement)], [text("-")]),
+            input([r#type("text"), on_input(|e| Msg::SetName(e.value()))], []),
+            input([r#type("checkbox"), on_checked(Msg::Toggle)], []),
+            p([], [text("no listeners")]),
+        ],
+    )
+}
+
+#[test]
+fn fire_click_by_selector() {
+    let view = view();
+    assert_eq!(
+        view.fire_event(".inc", SyntheticEvent::new("click")),
+        Ok(vec![Msg::Increment])
+    );
+    assert_eq!(
+        view.fire_event("button:nth-child(2)", SyntheticEvent::new("click")),
+        Ok(vec![Msg::Decrement])
+    );
+}
+
+#[test]
+fn fire_input_with_value_and_checked() {
+    let view = view();
+    assert_eq!(
+        view.fire_event(
+            "[type=text]",
+            SyntheticEvent::new("input").with_value("hello")
+        ),
+        Ok(vec![Msg::SetName("hello".to_string())])
+    );
+    assert_eq!(
+        view.fire_event(
+            "[type=checkbox]",
+            SyntheticEvent::new("input").with_checked(true)
+        ),
+        Ok(vec![Msg::Toggle(true)])
+    );
+}
+
+#[test]
+fn fire_event_at_path() {
+    let view = view();
+    assert_eq!(
+        view.fire_event_at(&TreePath::new([1]), SyntheticEvent::new("click")),
+        Ok(vec![Msg::Decrement])
+    );
+    assert_eq!(
+        view.fire_event_at(&TreePath::new([9]), SyntheticEvent::new("click")),
+        Err(FireEventError::NoNodeAtPath(TreePath::new([9])))
+    );
+}
+
+#[test]
+fn no_messages_when_not_listening_to_the_event() {
+    let view = view();
+    assert_eq!(
+        view.fire_event("p", SyntheticEvent::new("click")),
+        Ok(vec![])
+    );
+    assert_eq!(
+        view.fire_event(".inc", SyntheticEvent::new("dblclick")),
+        Ok(vec![])
+    );
+}
+
+#[test]
+fn events_do_not_bubble() {
+    let view: Node<Msg> = div(
+        [on_click(|_| Msg::Clicked("div"))],
+        [span([], [text("child")])],
+    );
+    assert_eq!(
+        view.fire_event("span", SyntheticEvent::new("click")),
+        Ok(vec![])
+    );
+    assert_eq!(
+        view.fire_event("div", SyntheticEvent::new("click")),
+        Ok(vec![Msg::Clicked("div")])
+    );
+}
+
+#[test]
+fn all_the_listeners_of_the_event_are_called() {
+    let view: Node<Msg> = button(
+        [
+            on_click(|_| Msg::Clicked("first")),
+            on_click(|_| Msg::Clicked("second")),
+        ],
+        [],
+    );
+    assert_eq!(
+        view.fire_event("button", SyntheticEvent::new("click")),
+        Ok(vec![Msg::Clicked("first"), Msg::Clicked("second")])
+    );
+}
+
+#[test]
+fn elements_in_fragments_and_lazy_views() {
+    let view: Node<Msg> = div(
+        [],
+        [
+            fragment([button([on_click(|_| Msg::Clicked("fragment"))], [])]),
+            lazy(1, |_| button([on_click(|_| Msg::Clicked("lazy"))], [])),
+        ],
+    );
+    assert_eq!(
+        view.fire_event_at(&TreePath::new([0, 0]), SyntheticEvent::new("click")),
+        Ok(vec![Msg::Clicked("fragment")])
+    );
+    assert_eq!(
+        view.fire_event_at(&TreePath::new([1]), SyntheticEvent::new("click")),
+        Ok(vec![Msg::Clicked("lazy")])
+    );
+    assert_eq!(
+        view.fire_event("button:nth-child(2)", SyntheticEvent::new("click")),
+        Ok(vec![Msg::Clicked("lazy")])
+    );
+}
+
+#[test]
+fn selector_errors() {
+    let view = view();
+    assert_eq!(
+        view.fire_event("table", SyntheticEvent::new("click")),
+        Err(FireEventError::NoMatch("table".to_string()))
+    );
+    assert_eq!(
+        view.fire_event("", SyntheticEvent::new("click")),
+        Err(FireEventError::InvalidSelector(SelectorError::Empty))
+    );
+}
@@ -0,0 +1,118 @@
+#![deny(warnings)]
+use sauron::{html::lazy, vdom::Leaf, *};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// a lazy row, counting the number of times its view is built
+fn lazy_row(calls: &Rc<Cell<usize>>, row: (usize, &'static str)) -> Node<()> {
+    let calls = Rc::clone(calls);
+    lazy(row, move |(index, label)| {
+        calls.set(calls.get() + 1);
+        tr([], [td([], [text(index)]), td([], [text(label)])])
+    })
+}
+
+fn is_built(node: &Node<()>) -> bool {
+    match node {
+        Node::Leaf(Leaf::LazyView(view)) => view.is_built(),
+        _ => panic!("expecting a lazy view"),
+    }
+}
+
+#[test]
+fn view_is_only_built_when_needed() {
+    let calls = Rc::new(Cell::new(0));
+    let row = lazy_row(&calls, (1, "one"));
+    assert!(!is_built(&row));
+    assert_eq!(calls.get(), 0);
+
+    assert_eq!(row.render_to_string(), "<tr><td>1</td><td>one</td></tr>");
+    assert!(is_built(&row));
+    assert_eq!(calls.get(), 1);
+
+    row.render_to_string();
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn unchanged_args_are_skipped_from_diffing() {
+    let calls = Rc::new(Cell::new(0));
+    let old = lazy_row(&calls, (1, "one"));
+    old.render_to_string();
+    let new = lazy_row(&calls, (1, "one"));
+
+    assert_eq!(diff(&old, &new), vec![]);
+    assert_eq!(calls.get(), 1, "the view of new is reused from old");
+    assert!(is_built(&new));
+    assert_eq!(new.render_to_string(), "<tr><td>1</td><td>one</td></tr>");
+}
+
+#[test]
+fn changed_args_are_diffed() {
+    let calls = Rc::new(Cell::new(0));
+    let old = lazy_row(&calls, (1, "one"));
+    let new = lazy_row(&calls, (1, "uno"));
+
+    let new_text: Node<()> = text("uno");
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::replace_node(
+            None,
+            TreePath::new([1, 0]),
+            vec![&new_text]
+        )]
+    );
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn lazy_views_from_different_functions_are_diffed() {
+    let old: Node<()> = lazy(1, |n| text(n));
+    let new: Node<()> = lazy(1, |n| text(n + 1));
+    let new_text: Node<()> = text(2);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::replace_node(
+            None,
+            TreePath::new([]),
+            vec![&new_text]
+        )]
+    );
+}
+
+#[test]
+fn only_the_changed_rows_are_built() {
+    let calls = Rc::new(Cell::new(0));
+    let view = |labels: &[&'static str]| -> Node<()> {
+        table(
+            [],
+            labels
+                .iter()
+                .enumerate()
+                .map(|(index, label)| lazy_row(&calls, (index, *label))),
+        )
+    };
+    let old = view(&["one", "two", "three"]);
+    old.render_to_string();
+    assert_eq!(calls.get(), 3);
+
+    let new = view(&["one", "dos", "three", "four"]);
+    let patches = diff(&old, &new);
+    assert_eq!(patches.len(), 2);
+    assert_eq!(
+        calls.get(),
+        5,
+        "only the changed and the appended row are built"
+    );
+}
+
+#[test]
+fn mapped_lazy_views_are_skipped_from_diffing() {
+    let calls = Rc::new(Cell::new(0));
+    let old: Node<Option<()>> = lazy_row(&calls, (1, "one")).map_msg(Some);
+    old.render_to_string();
+    let new: Node<Option<()>> = lazy_row(&calls, (1, "one")).map_msg(Some);
+
+    assert_eq!(diff(&old, &new), vec![]);
+    assert_eq!(calls.get(), 1);
+}
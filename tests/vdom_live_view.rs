@@ -0,0 +1,294 @@
+#![deny(warnings)]
+use futures::{executor::block_on, future::LocalBoxFuture};
+use sauron::{
+    dom::live_view::{channel, ClientMessage, LiveServer, LiveViewError, ServerMessage, Transport},
+    dom::{Event, SyntheticEvent},
+    html::{attributes::*, events::*, *},
+    vdom::{ListenerId, ListenerRegistry, SerializableAttributeValue, SerializableNode},
+    *,
+};
+use std::{cell::RefCell, collections::VecDeque, task::Poll};
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    Increment,
+    SetName(String),
+    Reset,
+}
+
+#[derive(Default)]
+struct App {
+    count: i32,
+    name: String,
+}
+
+impl Application for App {
+    type MSG = Msg;
+
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Increment => {
+                self.count += 1;
+                if self.count == 3 {
+                    return Cmd::new(async { Msg::Reset });
+                }
+            }
+            Msg::SetName(name) => self.name = name,
+            Msg::Reset => self.count = 0,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        main(
+            [],
+            [
+                button([on_click(|_| Msg::Increment)], [text("+")]),
+                input(
+                    [value(&self.name), on_input(|e| Msg::SetName(e.value()))],
+                    [],
+                ),
+                span([class("count")], [text(self.count)]),
+            ],
+        )
+    }
+}
+
+/// find the id of the event listener of the element at the child index of the root
+fn listener_of(view: &SerializableNode, index: usize) -> ListenerId {
+    let SerializableNode::Element { children, .. } = view else {
+        panic!("expecting an element");
+    };
+    let SerializableNode::Element { attrs, .. } = &children[index] else {
+        panic!("expecting an element");
+    };
+    attrs
+        .iter()
+        .flat_map(|attr| attr.value.iter())
+        .find_map(|value| match value {
+            SerializableAttributeValue::EventListener(id) => Some(*id),
+            _ => None,
+        })
+        .expect("must have an event listener")
+}
+
+fn event_message(listener: ListenerId, event: SyntheticEvent) -> String {
+    serde_json::to_string(&ClientMessage::Event { listener, event }).unwrap()
+}
+
+fn receive(transport: &impl Transport) -> ServerMessage {
+    let message = block_on(transport.recv()).expect("must receive a message");
+    serde_json::from_str(&message).unwrap()
+}
+
+#[test]
+fn client_mirror_is_kept_in_sync_with_the_server_view() {
+    let (server_transport, client_transport) = channel();
+    let mut server = LiveServer::new(App::default(), server_transport);
+    server.mount().unwrap();
+
+    let ServerMessage::Mount { view: mut mirror } = receive(&client_transport) else {
+        panic!("expecting the view to be mounted first");
+    };
+    let increment = listener_of(&mirror, 0);
+    let set_name = listener_of(&mirror, 1);
+
+    server
+        .handle_message(&event_message(increment, SyntheticEvent::new("click")))
+        .unwrap();
+    server
+        .handle_message(&event_message(
+            set_name,
+            SyntheticEvent::new("input").with_value("live"),
+        ))
+        .unwrap();
+    assert_eq!(server.app().count, 1);
+    assert_eq!(server.app().name, "live");
+
+    for _ in 0..2 {
+        let ServerMessage::Patch { patches } = receive(&client_transport) else {
+            panic!("expecting patches");
+        };
+        mirror.apply_patches(&patches);
+    }
+
+    let expected =
+        SerializableNode::from_node(server.current_vdom().unwrap(), &mut ListenerRegistry::new());
+    assert_eq!(mirror, expected);
+}
+
+#[test]
+fn key_and_mouse_position_are_sent_with_the_event() {
+    let view: Node<String> = div(
+        [],
+        [
+            input([on("keydown", |e: Event| e.key().unwrap_or_default())], []),
+            canvas(
+                [on("click", |e: Event| format!("{:?}", e.client_position()))],
+                [],
+            ),
+        ],
+    );
+    let mut listeners = ListenerRegistry::new();
+    SerializableNode::from_node(&view, &mut listeners);
+    let emit = |message: &str| {
+        let ClientMessage::Event { listener, event } = serde_json::from_str(message).unwrap();
+        listeners.get(listener).unwrap().emit(Event::from(event))
+    };
+    assert_eq!(
+        emit(&event_message(
+            ListenerId(0),
+            SyntheticEvent::new("keydown").with_key("Enter"),
+        )),
+        "Enter"
+    );
+    assert_eq!(
+        emit(&event_message(
+            ListenerId(1),
+            SyntheticEvent::new("click").with_client_position(10, 20),
+        )),
+        "Some((10, 20))"
+    );
+}
+
+#[test]
+fn unknown_listener_is_an_error() {
+    let (server_transport, _client_transport) = channel();
+    let mut server = LiveServer::new(App::default(), server_transport);
+    server.mount().unwrap();
+    let result =
+        server.handle_message(&event_message(ListenerId(99), SyntheticEvent::new("click")));
+    assert!(result.is_err());
+}
+
+/// a dialog which is closed with the button inside of it
+struct Dialog {
+    open: bool,
+}
+
+impl Application for Dialog {
+    type MSG = bool;
+
+    fn update(&mut self, open: bool) -> Cmd<bool> {
+        self.open = open;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<bool> {
+        let dialog = if self.open {
+            div([], [button([on_click(|_| false)], [text("close")])])
+        } else {
+            span([], [])
+        };
+        main([], [button([on_click(|_| true)], [text("open")]), dialog])
+    }
+}
+
+#[test]
+fn listeners_of_the_removed_nodes_are_unknown() {
+    let (server_transport, client_transport) = channel();
+    let mut server = LiveServer::new(Dialog { open: true }, server_transport);
+    server.mount().unwrap();
+    let ServerMessage::Mount { view } = receive(&client_transport) else {
+        panic!("expecting the view to be mounted first");
+    };
+    let SerializableNode::Element { children, .. } = &view else {
+        panic!("expecting an element");
+    };
+    let open = listener_of(&view, 0);
+    let close = listener_of(&children[1], 0);
+
+    server
+        .handle_message(&event_message(close, SyntheticEvent::new("click")))
+        .unwrap();
+    assert!(!server.app().open);
+    assert!(matches!(
+        server.handle_message(&event_message(close, SyntheticEvent::new("click"))),
+        Err(LiveViewError::UnknownListener(id)) if id == close
+    ));
+
+    server
+        .handle_message(&event_message(open, SyntheticEvent::new("click")))
+        .unwrap();
+    assert!(server.app().open);
+}
+
+#[test]
+fn run_until_the_client_disconnects() {
+    let (server_transport, client_transport) = channel();
+    let mut server = LiveServer::new(App::default(), server_transport);
+
+    let client = async {
+        let message = client_transport.recv().await.unwrap();
+        let ServerMessage::Mount { view } = serde_json::from_str(&message).unwrap() else {
+            panic!("expecting the view to be mounted first");
+        };
+        let increment = listener_of(&view, 0);
+        for _ in 0..3 {
+            client_transport
+                .send(event_message(increment, SyntheticEvent::new("click")))
+                .unwrap();
+        }
+        // the patches of the 3 increments, then the reset from the command returned by update
+        for _ in 0..4 {
+            let message = client_transport.recv().await.unwrap();
+            let ServerMessage::Patch { .. } = serde_json::from_str(&message).unwrap() else {
+                panic!("expecting patches");
+            };
+        }
+        client_transport.close();
+    };
+
+    let (result, ()) = block_on(futures::future::join(server.run(), client));
+    assert!(result.is_ok());
+    assert_eq!(server.app().count, 0);
+}
+
+/// A transport which takes the message out of the queue before it is done receiving it,
+/// so the message is lost if the future is dropped half way through
+#[derive(Default)]
+struct SlowTransport {
+    incoming: RefCell<VecDeque<String>>,
+}
+
+impl Transport for SlowTransport {
+    fn send(&self, _message: String) -> Result<(), LiveViewError> {
+        Ok(())
+    }
+
+    fn recv(&self) -> LocalBoxFuture<'_, Option<String>> {
+        Box::pin(async move {
+            let message = self.incoming.borrow_mut().pop_front();
+            let mut is_read = false;
+            futures::future::poll_fn(|cx| {
+                if is_read {
+                    Poll::Ready(())
+                } else {
+                    is_read = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            })
+            .await;
+            message
+        })
+    }
+}
+
+#[test]
+fn message_being_received_is_kept_when_a_command_is_done_first() {
+    let view = SerializableNode::from_node(&App::default().view(), &mut ListenerRegistry::new());
+    let increment = listener_of(&view, 0);
+    let transport = SlowTransport::default();
+    // the 4th click is being received when the reset command of the 3rd click is done
+    for _ in 0..4 {
+        transport
+            .incoming
+            .borrow_mut()
+            .push_back(event_message(increment, SyntheticEvent::new("click")));
+    }
+    let mut server = LiveServer::new(App::default(), transport);
+
+    block_on(server.run()).unwrap();
+    assert_eq!(server.app().count, 1);
+}
@@ -0,0 +1,189 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, *},
+    vdom::SelectorError,
+    *,
+};
+
+fn page() -> Node<()> {
+    main(
+        [id("app"), class("page")],
+        [
+            header([], [h1([class("title")], [text("Todo")])]),
+            ul(
+                [class("todo-list")],
+                [
+                    li([class("todo done"), key(1)], [text("one")]),
+                    li([class("todo"), key(2)], [text("two")]),
+                    li(
+                        [class("todo"), key(3)],
+                        [a([href("/three")], [text("three")])],
+                    ),
+                ],
+            ),
+            footer([], [input([r#type("checkbox"), checked(true)], [])]),
+        ],
+    )
+}
+
+/// the rendered nodes and the paths of the matching elements
+fn query_all(node: &Node<()>, selector: &str) -> Vec<(String, Vec<usize>)> {
+    node.query_selector_all(selector)
+        .expect("must be a valid selector")
+        .into_iter()
+        .map(|(node, path)| (node.render_to_string(), path.path))
+        .collect()
+}
+
+#[test]
+fn tag_id_and_class() {
+    let page = page();
+    assert_eq!(
+        query_all(&page, "h1"),
+        [(r#"<h1 class="title">Todo</h1>"#.to_string(), vec![0, 0])]
+    );
+    assert_eq!(query_all(&page, "#app").len(), 1);
+    assert_eq!(query_all(&page, "main#app.page")[0].1, Vec::<usize>::new());
+    assert_eq!(query_all(&page, ".todo").len(), 3);
+    assert_eq!(
+        query_all(&page, "li.todo.done"),
+        [(
+            r#"<li class="todo done" key="1">one</li>"#.to_string(),
+            vec![1, 0]
+        )]
+    );
+    assert!(query_all(&page, "#missing").is_empty());
+}
+
+#[test]
+fn attributes() {
+    let page = page();
+    assert_eq!(query_all(&page, "[href]")[0].1, vec![1, 2, 0]);
+    assert_eq!(query_all(&page, r#"a[href="/three"]"#)[0].1, vec![1, 2, 0]);
+    assert_eq!(query_all(&page, "li[key='2']")[0].1, vec![1, 1]);
+    assert_eq!(
+        query_all(&page, "[type=checkbox][checked]")[0].1,
+        vec![2, 0]
+    );
+    assert!(query_all(&page, "[href='/one']").is_empty());
+}
+
+#[test]
+fn false_boolean_attributes_are_not_set() {
+    let view: Node<()> = div(
+        [],
+        [input([disabled(false)], []), input([disabled(true)], [])],
+    );
+    assert_eq!(query_all(&view, "[disabled]")[0].1, vec![1]);
+}
+
+#[test]
+fn combinators() {
+    let page = page();
+    assert_eq!(query_all(&page, "main li").len(), 3);
+    assert_eq!(query_all(&page, "main > li").len(), 0);
+    assert_eq!(query_all(&page, "main > ul > li").len(), 3);
+    assert_eq!(
+        query_all(&page, ".page  .todo-list   a")[0].1,
+        vec![1, 2, 0]
+    );
+    assert_eq!(query_all(&page, "header>h1")[0].1, vec![0, 0]);
+}
+
+#[test]
+fn nth_child() {
+    let page = page();
+    let keys = |selector: &str| -> Vec<Vec<usize>> {
+        query_all(&page, selector)
+            .into_iter()
+            .map(|(_, path)| path)
+            .collect()
+    };
+    assert_eq!(keys("li:nth-child(2)"), [vec![1, 1]]);
+    assert_eq!(keys("li:nth-child(odd)"), [vec![1, 0], vec![1, 2]]);
+    assert_eq!(keys("li:nth-child(even)"), [vec![1, 1]]);
+    assert_eq!(keys("li:nth-child(n+2)"), [vec![1, 1], vec![1, 2]]);
+    assert_eq!(keys("li:nth-child(-n + 2)"), [vec![1, 0], vec![1, 1]]);
+    assert_eq!(keys("main > :nth-child(3)"), [vec![2]]);
+}
+
+#[test]
+fn nth_child_counts_only_elements() {
+    let view: Node<()> = p(
+        [],
+        [
+            text("hello"),
+            b([], [text("bold")]),
+            i([], [text("italic")]),
+        ],
+    );
+    assert_eq!(query_all(&view, "p > :nth-child(2)")[0].1, vec![2]);
+}
+
+#[test]
+fn selector_list_is_in_tree_order() {
+    let page = page();
+    let paths: Vec<Vec<usize>> = query_all(&page, "footer, h1")
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect();
+    assert_eq!(paths, [vec![0, 0], vec![2]]);
+}
+
+#[test]
+fn first_match() {
+    let page = page();
+    let (node, path) = page.query_selector(".todo").unwrap().unwrap();
+    assert_eq!(path, TreePath::new([1, 0]));
+    assert_eq!(path.find_node_by_path(&page), Some(node));
+    assert_eq!(page.query_selector("table").unwrap(), None);
+}
+
+#[test]
+fn elements_in_fragments_are_children_of_the_parent_element() {
+    let view: Node<()> = ul(
+        [],
+        [
+            li([], [text("one")]),
+            fragment([li([], [text("two")]), li([], [text("three")])]),
+        ],
+    );
+    let found = query_all(&view, "ul > li:nth-child(3)");
+    assert_eq!(found, [("<li>three</li>".to_string(), vec![1, 1])]);
+}
+
+#[test]
+fn invalid_selectors() {
+    let page = page();
+    assert_eq!(
+        page.query_selector_all("").unwrap_err(),
+        SelectorError::Empty
+    );
+    assert_eq!(
+        page.query_selector_all("li,").unwrap_err(),
+        SelectorError::Empty
+    );
+    assert_eq!(
+        page.query_selector_all("li > ").unwrap_err(),
+        SelectorError::UnexpectedEnd
+    );
+    assert_eq!(
+        page.query_selector_all("[href").unwrap_err(),
+        SelectorError::UnexpectedEnd
+    );
+    assert_eq!(
+        page.query_selector_all("li $").unwrap_err(),
+        SelectorError::UnexpectedChar {
+            found: '$',
+            position: 3
+        }
+    );
+    assert_eq!(
+        page.query_selector_all("li:hover").unwrap_err(),
+        SelectorError::UnsupportedPseudoClass("hover".to_string())
+    );
+    assert_eq!(
+        page.query_selector_all("li:nth-child(x)").unwrap_err(),
+        SelectorError::InvalidNth("x".to_string())
+    );
+}
@@ -38,10 +38,10 @@ fn test_multiple_replace() {
     assert_eq!(
         patches,
         vec![
-            Patch::remove_node(Some(&"li"), TreePath::new(vec![0, 1]),),
-            Patch::remove_node(Some(&"li"), TreePath::new(vec![0, 2]),),
+            Patch::remove_node(Some("li"), TreePath::new(vec![0, 1]),),
+            Patch::remove_node(Some("li"), TreePath::new(vec![0, 2]),),
             Patch::replace_node(
-                Some(&"li"),
+                Some("li"),
                 TreePath::new(vec![0, 0]),
                 vec![
                     &element("li", vec![attr("key", "10")], vec![leaf("item10")]),
@@ -0,0 +1,126 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, lazy, *},
+    vdom::ResumableDiff,
+    *,
+};
+
+/// diff one node at a time, returning the patches and the number of calls to resume
+fn resume_every_node<'a>(old: &'a Node<()>, new: &'a Node<()>) -> (Vec<Patch<'a, ()>>, usize) {
+    let mut resumable = ResumableDiff::new();
+    let mut patches = vec![];
+    let mut calls = 0;
+    while !resumable.is_complete() {
+        patches.extend(resumable.resume(old, new, || true));
+        calls += 1;
+    }
+    (patches, calls)
+}
+
+/// the resumable diff has the same patches as diff, in any order,
+/// and applying them makes old the same as new
+fn assert_same_patches(old: &Node<()>, new: &Node<()>) {
+    let mut expected = diff(old, new);
+    let (patches, _) = resume_every_node(old, new);
+    assert_eq!(patches.len(), expected.len(), "patches: {patches:#?}");
+    for patch in patches.iter() {
+        let position = expected
+            .iter()
+            .position(|expected| expected == patch)
+            .unwrap_or_else(|| panic!("unexpected patch: {patch:#?}"));
+        expected.remove(position);
+    }
+
+    let mut patched = old.clone();
+    patched.apply_patches(&patches);
+    // compared as rendered, since the patched lazy views still have their old arguments
+    assert_eq!(patched.render_to_string(), new.render_to_string());
+}
+
+fn keyed_list(items: &[(usize, &str)]) -> Node<()> {
+    ul(
+        [class("list")],
+        items.iter().map(|(k, label)| {
+            li(
+                [key(*k)],
+                [span([class(format!("item{k}"))], [text(label)])],
+            )
+        }),
+    )
+}
+
+#[test]
+fn each_node_is_diffed_on_its_own() {
+    let old: Node<()> = ul([], [li([], [text("one")]), li([], [text("two")])]);
+    let new: Node<()> = ul([], [li([], [text("uno")]), li([], [text("dos")])]);
+    let (patches, calls) = resume_every_node(&old, &new);
+    assert_eq!(patches.len(), 2);
+    // ul, 2 li and 2 text nodes
+    assert_eq!(calls, 5);
+    assert_same_patches(&old, &new);
+}
+
+#[test]
+fn continues_where_it_left_off() {
+    let old: Node<()> = div([], (0..10).map(|i| p([], [text(i)])));
+    let new: Node<()> = div([], (0..10).map(|i| p([], [text(i * 2)])));
+    let mut resumable = ResumableDiff::new();
+    let mut budget = 3;
+    let patches = resumable.resume(&old, &new, || {
+        budget -= 1;
+        budget == 0
+    });
+    assert!(!resumable.is_complete());
+    assert_eq!(patches, vec![]);
+
+    let rest = resumable.resume(&old, &new, || false);
+    assert!(resumable.is_complete());
+    assert_eq!(patches.len() + rest.len(), diff(&old, &new).len());
+}
+
+#[test]
+fn attribute_and_children_changes() {
+    let old: Node<()> = main(
+        [id("app")],
+        [
+            h1([class("title")], [text("Hello")]),
+            section([], [p([], [text("a")]), p([], [text("b")])]),
+            footer([], []),
+        ],
+    );
+    let new: Node<()> = main(
+        [id("main")],
+        [
+            h1([class("title big")], [text("Hello!")]),
+            section([], [p([], [text("a")])]),
+            footer([], [text("end")]),
+            aside([], []),
+        ],
+    );
+    assert_same_patches(&old, &new);
+    assert_same_patches(&new, &old);
+}
+
+#[test]
+fn moved_keyed_nodes_are_diffed_at_their_new_location() {
+    let old = keyed_list(&[(1, "one"), (2, "two"), (3, "three"), (4, "four")]);
+    let new = keyed_list(&[(4, "FOUR"), (1, "one"), (5, "five"), (3, "THREE")]);
+    assert_same_patches(&old, &new);
+    assert_same_patches(&new, &old);
+}
+
+#[test]
+fn nodes_in_fragments_and_lazy_views() {
+    let view = |labels: [&'static str; 3]| -> Node<()> {
+        div(
+            [],
+            [
+                fragment([p([], [text(labels[0])]), p([], [text(labels[1])])]),
+                lazy(labels[2], |label| article([], [p([], [text(label)])])),
+            ],
+        )
+    };
+    let old = view(["a", "b", "c"]);
+    let new = view(["a", "B", "C"]);
+    assert_same_patches(&old, &new);
+}
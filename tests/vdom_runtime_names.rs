@@ -0,0 +1,77 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::class, *},
+    vdom::{attr, diff, element, Patch, TreePath},
+    Node,
+};
+
+/// tag and attribute names which are only known at runtime, ie: read from a config
+fn widget(tag_name: &str, attr_name: &str, value: &str) -> Node<()> {
+    element(
+        tag_name.to_string(),
+        [attr(attr_name.to_string(), value.to_string())],
+        [text("content")],
+    )
+}
+
+#[test]
+fn render_runtime_names() {
+    let node = widget("my-widget", "data-size", "large");
+    assert_eq!(node.tag(), Some("my-widget"));
+    assert_eq!(
+        node.first_value("data-size").and_then(|v| v.as_str()),
+        Some("large")
+    );
+    assert_eq!(
+        node.render_to_string(),
+        r#"<my-widget data-size="large">content</my-widget>"#
+    );
+}
+
+#[test]
+fn runtime_names_are_equal_to_static_names() {
+    let runtime = widget("div", "class", "box");
+    let from_static: Node<()> = div([class("box")], [text("content")]);
+    assert_eq!(runtime, from_static);
+    assert!(diff(&from_static, &runtime).is_empty());
+}
+
+#[test]
+fn diff_runtime_names() {
+    let old = widget("my-widget", "data-size", "large");
+    let new = widget("my-widget", "data-size", "small");
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::add_attributes(
+            "my-widget",
+            TreePath::new([]),
+            new.attributes().unwrap().iter().collect::<Vec<_>>()
+        )]
+    );
+
+    let replaced = widget("other-widget", "data-size", "large");
+    assert_eq!(
+        diff(&old, &replaced),
+        vec![Patch::replace_node(
+            Some("my-widget"),
+            TreePath::new([]),
+            [&replaced]
+        )]
+    );
+}
+
+#[test]
+fn query_runtime_names() {
+    let page: Node<()> = div(
+        [],
+        [
+            widget("my-widget", "data-size", "large"),
+            widget("my-widget", "data-size", "small"),
+        ],
+    );
+    let found = page
+        .query_selector_all("my-widget[data-size=small]")
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].1, TreePath::new([1]));
+}
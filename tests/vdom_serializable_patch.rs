@@ -0,0 +1,104 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, events::*, *},
+    vdom::{
+        ListenerRegistry, SerializableAttribute, SerializableAttributeValue, SerializableNode,
+        SerializablePatch, SerializablePatchType,
+    },
+    *,
+};
+
+#[test]
+fn patches_are_owned_and_can_be_serialized() {
+    let old: Node<i32> = main([], [div([class("a")], [text("hello")])]);
+    let new: Node<i32> = main(
+        [],
+        [
+            div([class("b")], [text("hello")]),
+            button([on_click(|_| 1)], [text("click")]),
+        ],
+    );
+
+    let mut listeners = ListenerRegistry::new();
+    let patches: Vec<SerializablePatch> = diff(&old, &new)
+        .iter()
+        .map(|patch| SerializablePatch::from_patch(patch, &mut listeners))
+        .collect();
+    // the patches no longer borrows the views
+    drop(old);
+    drop(new);
+
+    assert_eq!(
+        patches,
+        vec![
+            SerializablePatch {
+                tag: Some("div".to_string()),
+                patch_path: TreePath::new([0]),
+                patch_type: SerializablePatchType::UpdateClasses {
+                    add: vec!["b".to_string()],
+                    remove: vec!["a".to_string()],
+                },
+            },
+            SerializablePatch {
+                tag: Some("main".to_string()),
+                patch_path: TreePath::new([]),
+                patch_type: SerializablePatchType::AppendChildren {
+                    children: vec![SerializableNode::Element {
+                        namespace: None,
+                        tag: "button".to_string(),
+                        attrs: vec![SerializableAttribute {
+                            namespace: None,
+                            name: "click".to_string(),
+                            value: vec![SerializableAttributeValue::EventListener(
+                                sauron::vdom::ListenerId(0)
+                            )],
+                        }],
+                        children: vec![SerializableNode::Text("click".to_string())],
+                        self_closing: false,
+                    }],
+                },
+            },
+        ]
+    );
+    assert_eq!(listeners.len(), 1);
+
+    let json = serde_json::to_string(&patches).unwrap();
+    let restored: Vec<SerializablePatch> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, patches);
+}
+
+#[test]
+fn node_lists_and_fragments_are_kept() {
+    let node: Node<()> = node_list([text("a"), fragment([comment("b"), comment("c")])]);
+    let converted = SerializableNode::from_node(&node, &mut ListenerRegistry::new());
+    assert_eq!(
+        converted,
+        SerializableNode::Fragment(vec![
+            SerializableNode::Text("a".to_string()),
+            SerializableNode::Fragment(vec![
+                SerializableNode::Comment("b".to_string()),
+                SerializableNode::Comment("c".to_string()),
+            ]),
+        ])
+    );
+}
+
+#[test]
+fn unused_listeners_are_removed() {
+    let mut listeners = ListenerRegistry::new();
+    let old: Node<i32> = main([], [button([on_click(|_| 1)], [text("one")])]);
+    let mut client_view = SerializableNode::from_node(&old, &mut listeners);
+
+    let new: Node<i32> = main([], [a([on_click(|_| 2)], [text("two")])]);
+    let patches: Vec<SerializablePatch> = diff(&old, &new)
+        .iter()
+        .map(|patch| SerializablePatch::from_patch(patch, &mut listeners))
+        .collect();
+    assert_eq!(listeners.len(), 2);
+
+    client_view.apply_patches(&patches);
+    listeners.retain_used(&client_view);
+    assert_eq!(listeners.len(), 1);
+    assert!(listeners.get(sauron::vdom::ListenerId(0)).is_none());
+    assert!(listeners.get(sauron::vdom::ListenerId(1)).is_some());
+}
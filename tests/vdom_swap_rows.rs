@@ -36,8 +36,8 @@ fn swap_999() {
     assert_eq!(
         diff,
         vec![
-            Patch::move_before_node(Some(&"li"), TreePath::new([1]), [TreePath::new([998])]),
-            Patch::move_after_node(Some(&"li"), TreePath::new([997]), [TreePath::new([1])]),
+            Patch::move_before_node(Some("li"), TreePath::new([1]), [TreePath::new([998])]),
+            Patch::move_after_node(Some("li"), TreePath::new([997]), [TreePath::new([1])]),
         ]
     );
 }
@@ -75,9 +75,19 @@ fn swap_rows_non_keyed() {
     assert_eq!(
         diff,
         vec![
-            Patch::add_attributes(&"div", TreePath::new([1]), vec![&attr("class", "4")],),
+            Patch::update_classes(
+                "div",
+                TreePath::new([1]),
+                vec!["4".to_string()],
+                vec!["2".to_string()]
+            ),
             Patch::replace_node(None, TreePath::new([1, 0]), vec![&leaf("line4")]),
-            Patch::add_attributes(&"div", TreePath::new([3],), [&attr("class", "2")],),
+            Patch::update_classes(
+                "div",
+                TreePath::new([3]),
+                vec!["2".to_string()],
+                vec!["4".to_string()]
+            ),
             Patch::replace_node(None, TreePath::new([3, 0],), [&leaf("line2")],)
         ]
     );
@@ -124,8 +134,8 @@ fn move_key_2_to_after_node_index_6() {
     assert_eq!(
         diff,
         vec![Patch::move_after_node(
-            Some(&"div",),
-            TreePath::new([5]),
+            Some("div",),
+            TreePath::new([6]),
             [TreePath::new([1])]
         ),]
     );
@@ -172,7 +182,7 @@ fn move_key_7_to_before_node_index_1() {
     assert_eq!(
         diff,
         vec![Patch::move_before_node(
-            Some(&"div",),
+            Some("div",),
             TreePath::new([1]),
             [TreePath::new([6])]
         ),]
@@ -220,8 +230,8 @@ fn swap_rows_keyed() {
     assert_eq!(
         diff,
         vec![
-            Patch::move_before_node(Some(&"div"), TreePath::new([1]), [TreePath::new([6])]),
-            Patch::move_after_node(Some(&"div",), TreePath::new([5]), [TreePath::new([1])]),
+            Patch::move_before_node(Some("div"), TreePath::new([1]), [TreePath::new([6])]),
+            Patch::move_after_node(Some("div",), TreePath::new([5]), [TreePath::new([1])]),
         ]
     );
 }
@@ -261,8 +271,8 @@ fn swap_rows_keyed_6_items() {
     assert_eq!(
         diff,
         vec![
-            Patch::move_before_node(Some(&"div",), TreePath::new([1]), [TreePath::new([4])]),
-            Patch::move_after_node(Some(&"div"), TreePath::new([3]), [TreePath::new([1])]),
+            Patch::move_before_node(Some("div",), TreePath::new([1]), [TreePath::new([4])]),
+            Patch::move_after_node(Some("div"), TreePath::new([3]), [TreePath::new([1])]),
         ]
     );
 }
@@ -301,7 +311,7 @@ fn swap_rows_keyed_5_items() {
     assert_eq!(
         diff,
         vec![Patch::move_before_node(
-            Some(&"div",),
+            Some("div",),
             TreePath::new([1]),
             [TreePath::new([3]), TreePath::new([2])]
         ),]
@@ -0,0 +1,215 @@
+#![deny(warnings)]
+use futures::{channel::oneshot, executor::block_on, future};
+use sauron::{
+    dom::{FireEventError, SyntheticEvent, TestProgram},
+    html::{attributes::*, events::*, *},
+    *,
+};
+use std::task::Poll;
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    Increment,
+    SetName(String),
+    Fetch,
+    Fetched(Vec<String>),
+    Reset,
+    /// wait for a command which never completes, ie: a timer which only runs in the browser
+    Wait,
+    /// run a command which needs to be polled more than once
+    Yield,
+    /// receive the name from a channel which is sent by another task
+    Receive,
+}
+
+#[derive(Default)]
+struct App {
+    count: i32,
+    name: String,
+    items: Vec<String>,
+    loading: bool,
+    name_receiver: Option<oneshot::Receiver<String>>,
+}
+
+impl Application for App {
+    type MSG = Msg;
+
+    fn init(&mut self) -> Cmd<Msg> {
+        Cmd::new(async { Msg::Fetch })
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+            Msg::SetName(name) => self.name = name,
+            Msg::Fetch => {
+                self.loading = true;
+                return Cmd::new(async { Msg::Fetched(vec!["a".into(), "b".into()]) });
+            }
+            Msg::Fetched(items) => {
+                self.loading = false;
+                self.items = items;
+            }
+            Msg::Reset => {
+                return Cmd::batch([
+                    Cmd::new(async { Msg::SetName(String::new()) }),
+                    Cmd::new(async { Msg::Fetched(vec![]) }),
+                ])
+            }
+            Msg::Wait => {
+                return Cmd::batch([
+                    Cmd::new(futures::future::pending()),
+                    Cmd::new(async { Msg::Increment }),
+                ])
+            }
+            Msg::Yield => {
+                return Cmd::new(async {
+                    yield_once().await;
+                    Msg::Increment
+                })
+            }
+            Msg::Receive => {
+                if let Some(receiver) = self.name_receiver.take() {
+                    return Cmd::new(async move { Msg::SetName(receiver.await.unwrap()) });
+                }
+            }
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        main(
+            [],
+            [
+                button([class("inc"), on_click(|_| Msg::Increment)], [text("+")]),
+                span([class("count")], [text(self.count)]),
+                input(
+                    [value(&self.name), on_input(|e| Msg::SetName(e.value()))],
+                    [],
+                ),
+                if self.loading {
+                    p([class("loading")], [text("loading..")])
+                } else {
+                    ul([], self.items.iter().map(|item| li([], [text(item)])))
+                },
+            ],
+        )
+    }
+}
+
+/// wake the task, then return pending once, the same way a future which yields to the executor does
+async fn yield_once() {
+    let mut yielded = false;
+    future::poll_fn(|cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+fn count_text(program: &TestProgram<App>) -> String {
+    let (count, _) = program
+        .view()
+        .query_selector(".count")
+        .unwrap()
+        .expect("must have a count");
+    count.render_to_string()
+}
+
+#[test]
+fn init_cmd_runs_when_settled() {
+    let mut program = TestProgram::new(App::default());
+    assert!(program.has_pending_cmds());
+    assert!(!program.app().loading);
+
+    block_on(program.settle());
+    assert!(!program.has_pending_cmds());
+    assert_eq!(program.app().items, ["a", "b"]);
+    assert_eq!(
+        program.view().query_selector_all("li").unwrap().len(),
+        2,
+        "the view is updated with the fetched items"
+    );
+}
+
+#[test]
+fn dispatch_updates_the_view() {
+    let mut program = TestProgram::new(App::default());
+    program.dispatch(Msg::Increment);
+    program.dispatch_multiple([Msg::Increment, Msg::Increment]);
+    assert_eq!(program.app().count, 3);
+    assert_eq!(count_text(&program), r#"<span class="count">3</span>"#);
+}
+
+#[test]
+fn fire_events_in_the_view() {
+    let mut program = TestProgram::new(App::default());
+    program
+        .fire_event(".inc", SyntheticEvent::new("click"))
+        .unwrap();
+    program
+        .fire_event("input", SyntheticEvent::new("input").with_value("sauron"))
+        .unwrap();
+    assert_eq!(count_text(&program), r#"<span class="count">1</span>"#);
+    assert_eq!(program.app().name, "sauron");
+    assert_eq!(
+        program.fire_event("table", SyntheticEvent::new("click")),
+        Err(FireEventError::NoMatch("table".to_string()))
+    );
+}
+
+#[test]
+fn commands_returned_by_the_cmds_are_also_settled() {
+    let mut program = TestProgram::new(App::default());
+    block_on(program.settle());
+    program.dispatch(Msg::SetName("sauron".to_string()));
+
+    program.dispatch(Msg::Fetch);
+    assert!(program.view().query_selector(".loading").unwrap().is_some());
+    program.dispatch(Msg::Reset);
+    block_on(program.settle());
+
+    assert!(program.view().query_selector(".loading").unwrap().is_none());
+    assert_eq!(program.app().name, "");
+    assert!(!program.has_pending_cmds());
+}
+
+#[test]
+fn settle_returns_when_the_pending_cmds_are_not_ready() {
+    let mut program = TestProgram::new(App::default());
+    block_on(program.settle());
+    program.dispatch(Msg::Wait);
+    block_on(program.settle());
+    assert_eq!(program.app().count, 1, "the ready command is still settled");
+    assert!(program.has_pending_cmds());
+}
+
+#[test]
+fn commands_which_are_polled_more_than_once_are_settled() {
+    let mut program = TestProgram::new(App::default());
+    block_on(program.settle());
+    program.dispatch(Msg::Yield);
+    block_on(program.settle());
+    assert_eq!(program.app().count, 1);
+    assert!(!program.has_pending_cmds());
+}
+
+#[test]
+fn commands_woken_by_another_task_are_settled() {
+    let (sender, receiver) = oneshot::channel();
+    let mut program = TestProgram::new(App {
+        name_receiver: Some(receiver),
+        ..Default::default()
+    });
+    block_on(program.settle());
+    program.dispatch(Msg::Receive);
+    let send = async { sender.send("sauron".to_string()).unwrap() };
+    block_on(future::join(program.settle(), send));
+    assert_eq!(program.app().name, "sauron");
+    assert!(!program.has_pending_cmds());
+}